@@ -0,0 +1,300 @@
+use odbc_sys::SqlDataType;
+
+// SQL_CODE_TIMESTAMP, the datetime subcode of the timestamp types.
+const SQL_CODE_TIMESTAMP: i16 = 3;
+
+// The maximum size of a BSON document, which bounds the length of the values of the variable
+// length types.
+pub const MAX_VALUE_LENGTH: u32 = 16 * 1024 * 1024;
+
+// How a BSON type is reported through ODBC. This is the single mapping from BSON types to SQL
+// types, used both for the result sets metadata and for SQLGetTypeInfo.
+// The column size, octet length and display size of the variable length types are the maximum
+// length of a value. They, as well as the scale, are None when they don't apply to the type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BsonTypeInfo {
+    // The BSON type name, as it appears in the bsonType keyword of a schema.
+    pub type_name: &'static str,
+    // The SQL type the BSON type is mapped to.
+    pub sql_type: SqlDataType,
+    // Column size: maximum number of digits for numeric types, or maximum number of characters
+    // for character and date/time types.
+    pub column_size: Option<u32>,
+    // Number of digits after the decimal point.
+    pub scale: Option<u16>,
+    // Size in bytes of the value when transferred to the default C type.
+    pub octet_length: Option<u32>,
+    // Maximum number of characters needed to display the value.
+    pub display_size: Option<u32>,
+    // Whether the type is unsigned. Non-numeric types are always unsigned.
+    pub is_unsigned: bool,
+    // Whether string comparisons on the type are case sensitive.
+    pub is_case_sensitive: bool,
+    // The characters used to prefix and suffix a literal of the type in a query.
+    pub literal_prefix: Option<&'static str>,
+    pub literal_suffix: Option<&'static str>,
+    // How the type can be used in a WHERE clause.
+    pub searchable: Searchable,
+}
+
+// The SQL_PRED_* values telling how a type can be used in a WHERE clause.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i16)]
+pub enum Searchable {
+    // The type can't be used in a WHERE clause.
+    None = 0,
+    // The type can only be used with LIKE.
+    Char = 1,
+    // The type can be used with all the comparison operators except LIKE.
+    Basic = 2,
+    // The type can be used with any comparison operator.
+    All = 3,
+}
+
+impl BsonTypeInfo {
+    pub const DOUBLE: BsonTypeInfo = BsonTypeInfo {
+        type_name: "double",
+        sql_type: SqlDataType::DOUBLE,
+        column_size: Some(15),
+        scale: None,
+        octet_length: Some(8),
+        display_size: Some(24),
+        is_unsigned: false,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const STRING: BsonTypeInfo = BsonTypeInfo {
+        type_name: "string",
+        sql_type: SqlDataType::VARCHAR,
+        column_size: Some(MAX_VALUE_LENGTH),
+        scale: None,
+        octet_length: Some(MAX_VALUE_LENGTH),
+        display_size: Some(MAX_VALUE_LENGTH),
+        is_unsigned: true,
+        is_case_sensitive: true,
+        literal_prefix: Some("'"),
+        literal_suffix: Some("'"),
+        searchable: Searchable::All,
+    };
+    pub const OBJECT: BsonTypeInfo = BsonTypeInfo::varchar("object");
+    pub const ARRAY: BsonTypeInfo = BsonTypeInfo::varchar("array");
+    pub const BINDATA: BsonTypeInfo = BsonTypeInfo {
+        type_name: "binData",
+        sql_type: SqlDataType::EXT_BINARY,
+        column_size: Some(MAX_VALUE_LENGTH),
+        scale: None,
+        octet_length: Some(MAX_VALUE_LENGTH),
+        // Each byte is displayed as two hexadecimal digits.
+        display_size: Some(2 * MAX_VALUE_LENGTH),
+        is_unsigned: true,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::None,
+    };
+    pub const UNDEFINED: BsonTypeInfo = BsonTypeInfo::varchar("undefined");
+    pub const OBJECTID: BsonTypeInfo = BsonTypeInfo {
+        type_name: "objectId",
+        sql_type: SqlDataType::VARCHAR,
+        column_size: Some(24),
+        scale: None,
+        octet_length: Some(24),
+        display_size: Some(24),
+        is_unsigned: true,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const BOOL: BsonTypeInfo = BsonTypeInfo {
+        type_name: "bool",
+        sql_type: SqlDataType::EXT_BIT,
+        column_size: Some(1),
+        scale: None,
+        octet_length: Some(1),
+        display_size: Some(1),
+        is_unsigned: true,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const DATE: BsonTypeInfo = BsonTypeInfo {
+        type_name: "date",
+        sql_type: SqlDataType::TIMESTAMP,
+        column_size: Some(23),
+        scale: Some(3),
+        octet_length: Some(16),
+        display_size: Some(23),
+        is_unsigned: true,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const NULL: BsonTypeInfo = BsonTypeInfo {
+        type_name: "null",
+        sql_type: SqlDataType::UNKNOWN_TYPE,
+        column_size: None,
+        scale: None,
+        octet_length: None,
+        display_size: None,
+        is_unsigned: true,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::None,
+    };
+    pub const REGEX: BsonTypeInfo = BsonTypeInfo::varchar("regex");
+    pub const DBPOINTER: BsonTypeInfo = BsonTypeInfo::varchar("dbPointer");
+    pub const JAVASCRIPT: BsonTypeInfo = BsonTypeInfo::varchar("javascript");
+    pub const SYMBOL: BsonTypeInfo = BsonTypeInfo::varchar("symbol");
+    pub const JAVASCRIPTWITHSCOPE: BsonTypeInfo = BsonTypeInfo::varchar("javascriptWithScope");
+    pub const INT: BsonTypeInfo = BsonTypeInfo {
+        type_name: "int",
+        sql_type: SqlDataType::INTEGER,
+        column_size: Some(10),
+        scale: Some(0),
+        octet_length: Some(4),
+        display_size: Some(11),
+        is_unsigned: false,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const TIMESTAMP: BsonTypeInfo = BsonTypeInfo {
+        type_name: "timestamp",
+        sql_type: SqlDataType::TIMESTAMP,
+        column_size: Some(19),
+        scale: Some(0),
+        octet_length: Some(16),
+        display_size: Some(19),
+        is_unsigned: true,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const LONG: BsonTypeInfo = BsonTypeInfo {
+        type_name: "long",
+        sql_type: SqlDataType::EXT_BIG_INT,
+        column_size: Some(19),
+        scale: Some(0),
+        octet_length: Some(8),
+        display_size: Some(20),
+        is_unsigned: false,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const DECIMAL: BsonTypeInfo = BsonTypeInfo {
+        type_name: "decimal",
+        sql_type: SqlDataType::DECIMAL,
+        column_size: Some(34),
+        scale: None,
+        octet_length: Some(16),
+        display_size: Some(34),
+        is_unsigned: false,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+    pub const MINKEY: BsonTypeInfo = BsonTypeInfo::varchar("minKey");
+    pub const MAXKEY: BsonTypeInfo = BsonTypeInfo::varchar("maxKey");
+    // Values of any type, used for columns whose values have mixed types.
+    pub const BSON: BsonTypeInfo = BsonTypeInfo::varchar("bson");
+
+    // Not a BSON type of its own: int values reported as SMALLINT, for the columns of the catalog
+    // result sets which ODBC defines as SMALLINT. It is not part of ALL.
+    pub const SMALLINT: BsonTypeInfo = BsonTypeInfo {
+        type_name: "int",
+        sql_type: SqlDataType::SMALLINT,
+        column_size: Some(5),
+        scale: Some(0),
+        octet_length: Some(2),
+        display_size: Some(6),
+        is_unsigned: false,
+        is_case_sensitive: false,
+        literal_prefix: None,
+        literal_suffix: None,
+        searchable: Searchable::Basic,
+    };
+
+    // All the known BSON types, in the order of their BSON type number.
+    pub const ALL: [BsonTypeInfo; 22] = [
+        BsonTypeInfo::DOUBLE,
+        BsonTypeInfo::STRING,
+        BsonTypeInfo::OBJECT,
+        BsonTypeInfo::ARRAY,
+        BsonTypeInfo::BINDATA,
+        BsonTypeInfo::UNDEFINED,
+        BsonTypeInfo::OBJECTID,
+        BsonTypeInfo::BOOL,
+        BsonTypeInfo::DATE,
+        BsonTypeInfo::NULL,
+        BsonTypeInfo::REGEX,
+        BsonTypeInfo::DBPOINTER,
+        BsonTypeInfo::JAVASCRIPT,
+        BsonTypeInfo::SYMBOL,
+        BsonTypeInfo::JAVASCRIPTWITHSCOPE,
+        BsonTypeInfo::INT,
+        BsonTypeInfo::TIMESTAMP,
+        BsonTypeInfo::LONG,
+        BsonTypeInfo::DECIMAL,
+        BsonTypeInfo::MINKEY,
+        BsonTypeInfo::MAXKEY,
+        BsonTypeInfo::BSON,
+    ];
+
+    // Variable length types which are reported as strings.
+    const fn varchar(type_name: &'static str) -> BsonTypeInfo {
+        BsonTypeInfo {
+            type_name,
+            sql_type: SqlDataType::VARCHAR,
+            column_size: Some(MAX_VALUE_LENGTH),
+            scale: None,
+            octet_length: Some(MAX_VALUE_LENGTH),
+            display_size: Some(MAX_VALUE_LENGTH),
+            is_unsigned: true,
+            is_case_sensitive: false,
+            literal_prefix: None,
+            literal_suffix: None,
+            searchable: Searchable::None,
+        }
+    }
+
+    // Whether the type is an exact or approximate numeric type.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self.sql_type,
+            SqlDataType::DOUBLE
+                | SqlDataType::SMALLINT
+                | SqlDataType::INTEGER
+                | SqlDataType::EXT_BIG_INT
+                | SqlDataType::DECIMAL
+        )
+    }
+
+    // The verbose SQL type of the type and its datetime subcode, as reported in the SQL_DATA_TYPE
+    // and SQL_DATETIME_SUB columns of the catalog functions: datetime types have the verbose
+    // type SQL_DATETIME and a subcode, other types are their own verbose type.
+    pub fn verbose_type(&self) -> (SqlDataType, Option<i16>) {
+        match self.sql_type {
+            SqlDataType::TIMESTAMP => (SqlDataType::DATETIME, Some(SQL_CODE_TIMESTAMP)),
+            sql_type => (sql_type, None),
+        }
+    }
+
+    // Find the type information for the given BSON type name.
+    pub fn from_type_name(type_name: &str) -> Option<BsonTypeInfo> {
+        BsonTypeInfo::ALL
+            .iter()
+            .find(|type_info| type_info.type_name == type_name)
+            .copied()
+    }
+}
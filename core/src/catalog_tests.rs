@@ -0,0 +1,286 @@
+use crate::bson_type_info::BsonTypeInfo;
+use crate::collections::MongoCollections;
+use crate::databases::MongoDatabases;
+use crate::fields::{fields_from_schema, MongoFields};
+use crate::primary_keys::MongoPrimaryKeys;
+use crate::special_columns::{id_type, MongoSpecialColumns};
+use crate::statistics::{index_rows, table_stat_row, MongoStatistics};
+use crate::stmt::MongoStatement;
+use crate::table_types::MongoTableTypes;
+use bson::{doc, Bson};
+use mongodb::{options::IndexOptions, IndexModel};
+use odbc_sys::{Nullability, SqlDataType};
+
+// Collect the values of the columns 1 to col_count of all the rows of the given statement.
+fn rows(mut stmt: impl MongoStatement) -> Vec<Vec<Option<Bson>>> {
+    let col_count = stmt._get_col_count() as u16;
+    let mut rows = vec![];
+    while stmt.next().unwrap() {
+        rows.push(
+            (1..=col_count)
+                .map(|col_index| stmt.get_value(col_index).unwrap().cloned())
+                .collect(),
+        );
+    }
+    // There is no current row once all the rows have been returned.
+    assert!(stmt.get_value(1).is_err());
+    rows
+}
+
+// The value of a NULL column.
+const NULL: Option<Bson> = Some(Bson::Null);
+
+fn string(value: &str) -> Option<Bson> {
+    Some(Bson::String(value.to_string()))
+}
+
+#[test]
+fn list_databases() {
+    let databases = MongoDatabases::from_names(vec!["admin".to_string(), "test".to_string()]);
+    assert!(databases.get_value(1).is_err());
+    assert_eq!(
+        vec![
+            vec![string("admin"), NULL, NULL, NULL, NULL],
+            vec![string("test"), NULL, NULL, NULL, NULL],
+        ],
+        rows(databases)
+    );
+}
+
+#[test]
+fn list_collections() {
+    let collections = MongoCollections::from_tables(vec![
+        ("test".to_string(), "foo".to_string(), "TABLE"),
+        ("test".to_string(), "bar".to_string(), "VIEW"),
+        ("admin".to_string(), "foo".to_string(), "TABLE"),
+    ]);
+    assert_eq!(5, collections._get_col_count());
+    assert_eq!(
+        "TABLE_TYPE",
+        collections._get_col_metadata(4).unwrap().col_name
+    );
+    // The tables are ordered by TABLE_TYPE, TABLE_CAT and TABLE_NAME.
+    assert_eq!(
+        vec![
+            vec![string("admin"), NULL, string("foo"), string("TABLE"), NULL],
+            vec![string("test"), NULL, string("foo"), string("TABLE"), NULL],
+            vec![string("test"), NULL, string("bar"), string("VIEW"), NULL],
+        ],
+        rows(collections)
+    );
+    assert!(rows(MongoCollections::empty()).is_empty());
+}
+
+#[test]
+fn list_table_types() {
+    assert_eq!(
+        vec![
+            vec![NULL, NULL, NULL, string("TABLE"), NULL],
+            vec![NULL, NULL, NULL, string("VIEW"), NULL],
+        ],
+        rows(MongoTableTypes::list_table_types())
+    );
+}
+
+#[test]
+fn list_fields() {
+    let json_schema = doc! {
+        "bsonType": "object",
+        "properties": {
+            "name": {"bsonType": "string"},
+            "_id": {"bsonType": "objectId"},
+            "created": {"bsonType": ["date", "null"]},
+            "count": {"bsonType": "int"},
+            "address": {"bsonType": "object", "properties": {"city": {"bsonType": "string"}}},
+        },
+        "required": ["_id", "count", "created"],
+    };
+    let fields = fields_from_schema("db", "foo", &json_schema, "%").unwrap();
+    let names: Vec<(&str, i32)> = fields
+        .iter()
+        .map(|row| (row[3].as_str().unwrap(), row[16].as_i32().unwrap()))
+        .collect();
+    // The nested fields are not flattened into their own columns.
+    assert_eq!(
+        vec![
+            ("_id", 1),
+            ("address", 2),
+            ("count", 3),
+            ("created", 4),
+            ("name", 5)
+        ],
+        names
+    );
+    let created = &fields[3];
+    assert_eq!(
+        vec![
+            Bson::String("db".to_string()),
+            Bson::Null,
+            Bson::String("foo".to_string()),
+            Bson::String("created".to_string()),
+            Bson::Int32(SqlDataType::TIMESTAMP.0 as i32),
+            Bson::String("date".to_string()),
+            Bson::Int32(23),
+            Bson::Int32(16),
+            Bson::Int32(3),
+            Bson::Null,
+            Bson::Int32(Nullability::NULLABLE.0 as i32),
+            Bson::Null,
+            Bson::Null,
+            Bson::Int32(SqlDataType::DATETIME.0 as i32),
+            Bson::Int32(3),
+            Bson::Null,
+            Bson::Int32(4),
+            Bson::String("YES".to_string()),
+        ],
+        *created
+    );
+    let count = &fields[2];
+    assert_eq!(Bson::Int32(10), count[9]);
+    assert_eq!(Bson::Int32(Nullability::NO_NULLS.0 as i32), count[10]);
+    assert_eq!(Bson::String("NO".to_string()), count[17]);
+
+    // The ordinal positions don't depend on the filter.
+    let filtered = fields_from_schema("db", "foo", &json_schema, "c%").unwrap();
+    assert_eq!(
+        vec![Bson::Int32(3), Bson::Int32(4)],
+        filtered
+            .iter()
+            .map(|row| row[16].clone())
+            .collect::<Vec<_>>()
+    );
+
+    let fields = MongoFields::from_rows(fields);
+    assert_eq!(18, fields._get_col_count());
+    assert_eq!(5, rows(fields).len());
+}
+
+#[test]
+fn list_primary_keys() {
+    let keys = MongoPrimaryKeys::from_collections(vec![
+        ("test".to_string(), "foo".to_string()),
+        ("admin".to_string(), "foo".to_string()),
+    ]);
+    assert_eq!(6, keys._get_col_count());
+    assert_eq!(
+        vec![
+            vec![
+                string("admin"),
+                Some(Bson::Null),
+                string("foo"),
+                string("_id"),
+                Some(Bson::Int32(1)),
+                string("_id_")
+            ],
+            vec![
+                string("test"),
+                Some(Bson::Null),
+                string("foo"),
+                string("_id"),
+                Some(Bson::Int32(1)),
+                string("_id_")
+            ],
+        ],
+        rows(keys)
+    );
+}
+
+#[test]
+fn list_best_rowid() {
+    assert_eq!(BsonTypeInfo::OBJECTID, id_type(None).unwrap());
+    assert_eq!(
+        BsonTypeInfo::OBJECTID,
+        id_type(Some(&doc! {"properties": {"a": {"bsonType": "int"}}})).unwrap()
+    );
+    assert_eq!(
+        BsonTypeInfo::INT,
+        id_type(Some(&doc! {"properties": {"_id": {"bsonType": "int"}}})).unwrap()
+    );
+
+    let columns = MongoSpecialColumns::from_id_types(vec![BsonTypeInfo::OBJECTID]);
+    assert_eq!(8, columns._get_col_count());
+    assert_eq!(
+        vec![vec![
+            Some(Bson::Int32(2)),
+            string("_id"),
+            Some(Bson::Int32(SqlDataType::VARCHAR.0 as i32)),
+            string("objectId"),
+            Some(Bson::Int32(24)),
+            Some(Bson::Int32(24)),
+            Some(Bson::Null),
+            Some(Bson::Int32(1)),
+        ]],
+        rows(columns)
+    );
+    assert!(rows(MongoSpecialColumns::empty()).is_empty());
+}
+
+#[test]
+fn list_statistics() {
+    let index = |keys, name: &str, unique| {
+        IndexModel::builder()
+            .keys(keys)
+            .options(
+                IndexOptions::builder()
+                    .name(name.to_string())
+                    .unique(unique)
+                    .build(),
+            )
+            .build()
+    };
+    let indexes = vec![
+        index(doc! {"b": 1, "c": -1}, "b_1_c_-1", None),
+        index(doc! {"_id": 1}, "_id_", None),
+        index(doc! {"d": "hashed"}, "d_hashed", Some(false)),
+        index(doc! {"e": "text"}, "e_text", Some(true)),
+    ];
+    let row = |non_unique, name: &str, index_type, position, column: &str, order: Option<&str>| {
+        vec![
+            Bson::String("test".to_string()),
+            Bson::Null,
+            Bson::String("foo".to_string()),
+            Bson::Int32(non_unique),
+            Bson::Null,
+            Bson::String(name.to_string()),
+            Bson::Int32(index_type),
+            Bson::Int32(position),
+            Bson::String(column.to_string()),
+            order.map_or(Bson::Null, |order| Bson::String(order.to_string())),
+            Bson::Null,
+            Bson::Null,
+            Bson::Null,
+        ]
+    };
+    // The unique indexes come first, then the indexes are ordered by name.
+    assert_eq!(
+        vec![
+            row(0, "_id_", 3, 1, "_id", Some("A")),
+            row(0, "e_text", 3, 1, "e", None),
+            row(1, "b_1_c_-1", 3, 1, "b", Some("A")),
+            row(1, "b_1_c_-1", 3, 2, "c", Some("D")),
+            row(1, "d_hashed", 2, 1, "d", None),
+        ],
+        index_rows("test", "foo", indexes.clone(), false)
+    );
+    assert_eq!(
+        vec![
+            row(0, "_id_", 3, 1, "_id", Some("A")),
+            row(0, "e_text", 3, 1, "e", None),
+        ],
+        index_rows("test", "foo", indexes, true)
+    );
+
+    let table_stat = table_stat_row("test", "foo", 42);
+    assert_eq!(Bson::Int32(0), table_stat[6]);
+    assert_eq!(Bson::Int32(42), table_stat[10]);
+    assert_eq!(
+        Bson::Int32(i32::MAX),
+        table_stat_row("test", "foo", u64::MAX)[10]
+    );
+    let statistics = MongoStatistics::from_rows(vec![table_stat.clone()]);
+    assert_eq!(13, statistics._get_col_count());
+    assert_eq!(
+        vec![table_stat.into_iter().map(Some).collect::<Vec<_>>()],
+        rows(statistics)
+    );
+}
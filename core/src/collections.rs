@@ -1,54 +1,115 @@
+use crate::bson_type_info::BsonTypeInfo;
 use crate::conn::MongoConnection;
 use crate::err::Result;
-use crate::stmt::MongoStatement;
+use crate::query::{fixed_resultset_metadata, MongoColMetadata};
+use crate::rows::MongoRows;
+use crate::util::is_match;
 use bson::Bson;
-use mongodb::results::CollectionSpecification;
-use mongodb::sync::Cursor;
+use mongodb::results::CollectionType;
 
-#[derive(Debug)]
-struct CollectionsForDb {
-    database_name: String,
-    collection_list: Cursor<CollectionSpecification>,
-}
+// The TABLE_TYPE of regular collections.
+pub const TABLE: &str = "TABLE";
+// The TABLE_TYPE of views.
+pub const VIEW: &str = "VIEW";
 
-#[derive(Debug)]
-pub struct MongoCollections {
-    // The current collection specification.
-    current_collection: Option<CollectionSpecification>,
-    // The cursor on the collections specification for the db.
-    current_collection_list: Option<CollectionsForDb>,
-}
+// Builds the MongoRows statements listing the tables.
+pub struct MongoCollections;
 
-// Statement related to a SQLColumns call.
+// Statement related to a SQLTables call.
 impl MongoCollections {
     // Create a new MongoStatement to list tables with the given database (catalogs) and collection
-    // (tables) names filters.
+    // (tables) names filters. The filters are search patterns.
+    // Only the tables whose TABLE_TYPE is in table_type_filter are listed, or all the tables if
+    // table_type_filter is empty.
     // The query timeout comes from the statement attribute SQL_ATTR_QUERY_TIMEOUT. If there is a
     // timeout, the query must finish before the timeout or an error is returned.
     pub fn list_tables(
-        _client: &MongoConnection,
+        client: &MongoConnection,
         _query_timeout: Option<i32>,
-        _db_name_filter: &str,
-        _collection_name_filter: &str,
-    ) -> Self {
-        unimplemented!()
+        db_name_filter: &str,
+        collection_name_filter: &str,
+        table_type_filter: &[&str],
+    ) -> Result<MongoRows> {
+        Ok(MongoCollections::from_tables(list_collections(
+            client,
+            db_name_filter,
+            collection_name_filter,
+            table_type_filter,
+        )?))
+    }
+
+    // Create a statement with no rows, for SQLTables("", SQL_ALL_SCHEMAS, ""): MongoDB has no
+    // schemas.
+    pub fn empty() -> MongoRows {
+        MongoCollections::from_tables(vec![])
+    }
+
+    // Create the statement listing the given tables, as (database name, collection name,
+    // table type). The tables are ordered by TABLE_TYPE, TABLE_CAT and TABLE_NAME.
+    pub(crate) fn from_tables(mut tables: Vec<(String, String, &str)>) -> MongoRows {
+        tables.sort_by(|a, b| (a.2, &a.0, &a.1).cmp(&(b.2, &b.0, &b.1)));
+        MongoRows::new(
+            tables
+                .into_iter()
+                .map(|(database_name, collection_name, table_type)| {
+                    vec![
+                        Bson::String(database_name),
+                        // TABLE_SCHEM
+                        Bson::Null,
+                        Bson::String(collection_name),
+                        Bson::String(table_type.to_string()),
+                        // REMARKS
+                        Bson::Null,
+                    ]
+                })
+                .collect(),
+            MongoCollections::resultset_metadata(),
+        )
     }
-}
 
-impl MongoStatement for MongoCollections {
-    // Move the cursor to the next CollectionSpecification.
-    // Return true if moving was successful, false otherwise.
-    fn next(&mut self) -> Result<bool> {
-        unimplemented!()
+    // The columns of the SQLTables result set: TABLE_CAT, TABLE_SCHEM, TABLE_NAME, TABLE_TYPE,
+    // REMARKS.
+    fn resultset_metadata() -> Vec<MongoColMetadata> {
+        fixed_resultset_metadata(&[
+            ("TABLE_CAT", BsonTypeInfo::STRING, false),
+            ("TABLE_SCHEM", BsonTypeInfo::STRING, true),
+            ("TABLE_NAME", BsonTypeInfo::STRING, false),
+            ("TABLE_TYPE", BsonTypeInfo::STRING, false),
+            ("REMARKS", BsonTypeInfo::STRING, true),
+        ])
     }
+}
 
-    // Get the BSON value for the given colIndex on the current CollectionSpecification.
-    // Fails if the first row as not been retrieved (next must be called at least once before getValue).
-    fn get_value(&self, _col_index: u16) -> Result<Option<&Bson>> {
-        // The mapping for col_index <-> Value will be hard-coded and handled in this function
-        // 1-> current_coll_list.0
-        // 2 -> current_coll.name
-        // 3 -> current_coll.CollectionType
-        unimplemented!()
+// List the collections whose database and collection names match the given search patterns and
+// whose table type is in table_type_filter, or of any type if table_type_filter is empty.
+// Return the database name, collection name and table type of each collection.
+pub(crate) fn list_collections(
+    client: &MongoConnection,
+    db_name_filter: &str,
+    collection_name_filter: &str,
+    table_type_filter: &[&str],
+) -> Result<Vec<(String, String, &'static str)>> {
+    let mut collections = vec![];
+    for database_name in client.client.list_database_names(None, None)? {
+        if !is_match(&database_name, db_name_filter) {
+            continue;
+        }
+        let collection_list = client
+            .client
+            .database(&database_name)
+            .list_collections(None, None)?;
+        for collection in collection_list {
+            let collection = collection?;
+            let table_type = match collection.collection_type {
+                CollectionType::View => VIEW,
+                _ => TABLE,
+            };
+            if is_match(&collection.name, collection_name_filter)
+                && (table_type_filter.is_empty() || table_type_filter.contains(&table_type))
+            {
+                collections.push((database_name.clone(), collection.name, table_type));
+            }
+        }
     }
+    Ok(collections)
 }
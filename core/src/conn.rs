@@ -1,7 +1,17 @@
-use crate::err::Result;
-use mongodb::sync::Client;
+use crate::err::{Error, Result};
+use bson::{doc, Document};
+use mongodb::{
+    error::ErrorKind,
+    options::{ClientOptions, ConnectionString},
+    sync::Client,
+};
 use std::time::Duration;
 
+// The database used for the validation command when no current database is set.
+const ADMIN_DB: &str = "admin";
+// Server error code returned when the credentials are rejected.
+const AUTHENTICATION_FAILED: i32 = 18;
+
 #[derive(Debug)]
 pub struct MongoConnection {
     // The mongo DB client
@@ -17,19 +27,102 @@ pub struct MongoConnection {
 }
 
 impl MongoConnection {
-    // Creates a new MongoConnection with the given settings and execute a dummy aggregation to
-    // validate it works.
+    // Creates a new MongoConnection with the given settings and run a ping command on the current
+    // database to validate it works.
     // The operation will timeout if it takes more than loginTimeout seconds.
     // The initial current database if provided should come from SQL_ATTR_CURRENT_CATALOG
     // and will take precedence over the database setting specified in the uri if any.
-    // The initial operation time if provided should come from  and will take precedence over the
-    // setting specified in the uri if any.
+    // The initial operation time if provided should come from SQL_ATTR_CONNECTION_TIMEOUT and
+    // will take precedence over the setting specified in the uri if any.
     pub fn connect(
-        _uri: &str,
-        _current_db: Option<&str>,
-        _operation_timeout: Option<i32>,
-        _login_timeout: Option<i32>,
+        uri: &str,
+        current_db: Option<&str>,
+        operation_timeout: Option<i32>,
+        login_timeout: Option<i32>,
     ) -> Result<Self> {
-        unimplemented!()
+        let connection_string = ConnectionString::parse(uri).map_err(connect_error)?;
+        let mut client_options = ClientOptions::parse_connection_string_sync(connection_string)
+            .map_err(connect_error)?;
+        if let Some(timeout) = to_duration(login_timeout) {
+            client_options.connect_timeout = Some(timeout);
+            client_options.server_selection_timeout = Some(timeout);
+        }
+        let current_db = current_db
+            .map(String::from)
+            .or_else(|| client_options.default_database.clone());
+        let client = Client::with_options(client_options).map_err(connect_error)?;
+        let connection = MongoConnection {
+            client,
+            current_db,
+            operation_timeout: to_duration(operation_timeout),
+        };
+
+        // Validate that the client is able to reach the server and authenticate.
+        connection
+            .client
+            .database(connection.current_db.as_deref().unwrap_or(ADMIN_DB))
+            .run_command(connection.with_max_time(doc! {"ping": 1}), None)
+            .map_err(connect_error)?;
+
+        Ok(connection)
+    }
+
+    // Returns the maximum time an operation can run on the server: the given query timeout
+    // coming from SQL_ATTR_QUERY_TIMEOUT if set, the connection operation timeout otherwise.
+    pub(crate) fn max_time(&self, query_timeout: Option<i32>) -> Option<Duration> {
+        to_duration(query_timeout).or(self.operation_timeout)
+    }
+
+    // Adds the connection operation timeout to the given command as its maxTimeMS, if set.
+    pub(crate) fn with_max_time(&self, command: Document) -> Document {
+        with_max_time_ms(command, self.operation_timeout)
+    }
+
+    // Returns the version of the server, from the buildInfo command.
+    pub fn get_dbms_version(&self) -> Result<String> {
+        let build_info = self
+            .client
+            .database(self.current_db.as_deref().unwrap_or(ADMIN_DB))
+            .run_command(self.with_max_time(doc! {"buildInfo": 1}), None)?;
+        Ok(build_info
+            .get_str("version")
+            .unwrap_or_default()
+            .to_string())
+    }
+}
+
+// Converts a timeout in seconds coming from an ODBC attribute into a Duration.
+// A timeout of 0 or less means there is no timeout.
+fn to_duration(timeout: Option<i32>) -> Option<Duration> {
+    match timeout {
+        Some(secs) if secs > 0 => Some(Duration::from_secs(secs as u64)),
+        _ => None,
+    }
+}
+
+// Adds the given timeout to the command as its maxTimeMS, if set.
+pub(crate) fn with_max_time_ms(mut command: Document, max_time: Option<Duration>) -> Document {
+    if let Some(max_time) = max_time {
+        command.insert("maxTimeMS", max_time.as_millis() as i64);
+    }
+    command
+}
+
+// Classifies an error which happened while establishing a connection.
+pub(crate) fn connect_error(error: mongodb::error::Error) -> Error {
+    match error.kind.as_ref() {
+        ErrorKind::InvalidArgument { .. } => Error::InvalidUriFormat(error.to_string()),
+        ErrorKind::Authentication { .. } => Error::AuthenticationFailed(error),
+        // The server selection only fails once the login timeout has elapsed without a
+        // suitable server being found.
+        ErrorKind::ServerSelection { .. } => Error::LoginTimeout(error),
+        ErrorKind::Io(io_error) if io_error.kind() == std::io::ErrorKind::TimedOut => {
+            Error::LoginTimeout(error)
+        }
+        ErrorKind::Io(_) | ErrorKind::DnsResolve { .. } => Error::ServerUnreachable(error),
+        ErrorKind::Command(command_error) if command_error.code == AUTHENTICATION_FAILED => {
+            Error::AuthenticationFailed(error)
+        }
+        _ => Error::MongoDriver(error),
     }
 }
@@ -0,0 +1,65 @@
+use crate::{
+    conn::{connect_error, with_max_time_ms, MongoConnection},
+    err::Error,
+};
+use bson::doc;
+use mongodb::{
+    error::{CommandError, Error as DriverError, ErrorKind},
+    options::ConnectionString,
+};
+use std::{io, time::Duration};
+
+fn command_error(code: i32, code_name: &str) -> DriverError {
+    let command_error: CommandError = bson::from_document(doc! {
+        "code": code,
+        "codeName": code_name,
+        "errmsg": "server message",
+    })
+    .unwrap();
+    DriverError::from(ErrorKind::Command(command_error))
+}
+
+#[test]
+fn invalid_uri() {
+    let error = connect_error(ConnectionString::parse("localhost:27017").unwrap_err());
+    assert!(matches!(error, Error::InvalidUriFormat(_)));
+    assert!(matches!(
+        MongoConnection::connect("mongodb://localhost/?foo=", None, None, None),
+        Err(Error::InvalidUriFormat(_))
+    ));
+}
+
+#[test]
+fn authentication_failed() {
+    // The driver only reports ErrorKind::Authentication from a server handshake, the server
+    // rejecting the credentials of a command is handled the same way.
+    let error = connect_error(command_error(18, "AuthenticationFailed"));
+    assert!(matches!(error, Error::AuthenticationFailed(_)));
+}
+
+#[test]
+fn login_timeout() {
+    let error = connect_error(DriverError::from(ErrorKind::from(io::ErrorKind::TimedOut)));
+    assert!(matches!(error, Error::LoginTimeout(_)));
+}
+
+#[test]
+fn max_time() {
+    let command = with_max_time_ms(doc! {"ping": 1}, Some(Duration::from_millis(1500)));
+    assert_eq!(doc! {"ping": 1, "maxTimeMS": 1500_i64}, command);
+    assert_eq!(doc! {"ping": 1}, with_max_time_ms(doc! {"ping": 1}, None));
+}
+
+#[test]
+fn server_unreachable() {
+    let error = connect_error(DriverError::from(ErrorKind::from(
+        io::ErrorKind::ConnectionRefused,
+    )));
+    assert!(matches!(error, Error::ServerUnreachable(_)));
+}
+
+#[test]
+fn other_errors() {
+    let error = connect_error(command_error(13, "Unauthorized"));
+    assert!(matches!(error, Error::MongoDriver(_)));
+}
@@ -0,0 +1,147 @@
+use crate::conn::MongoConnection;
+use crate::err::{Error, Result};
+use bson::{doc, Bson, Document};
+use mongodb::sync::{ClientSession, Database};
+use std::{collections::VecDeque, fmt};
+
+// A cursor on the result set of a command run with run_command, like an aggregate command.
+// Unlike the driver cursors, it gives access to the whole cursor document of the command reply,
+// where the server can return information about the result set alongside its first batch.
+// The following batches are retrieved with getMore commands sent in the session the cursor was
+// opened in, as the server requires.
+pub(crate) struct CommandCursor {
+    db: Database,
+    session: ClientSession,
+    // The collection part of the cursor namespace, which getMore and killCursors are run on.
+    collection: String,
+    // The id of the cursor on the server, 0 once all the batches have been retrieved.
+    id: i64,
+    // The documents of the current batch which have not been returned yet.
+    batch: VecDeque<Document>,
+}
+
+// The cursor document of a command reply, split into the cursor id, its namespace, its batch
+// and the remaining fields.
+#[derive(Debug)]
+pub(crate) struct CursorReply {
+    pub(crate) id: i64,
+    pub(crate) ns: String,
+    pub(crate) batch: VecDeque<Document>,
+    pub(crate) cursor: Document,
+}
+
+impl CommandCursor {
+    // Run the given command on the database db_name and open a cursor on the result set it
+    // returns. The remaining fields of the reply cursor document are returned along with the
+    // cursor.
+    pub(crate) fn open(
+        client: &MongoConnection,
+        db_name: &str,
+        command: Document,
+    ) -> Result<(Self, Document)> {
+        let mut session = client.client.start_session(None)?;
+        let db = client.client.database(db_name);
+        let reply = db.run_command_with_session(command, None, &mut session)?;
+        let CursorReply {
+            id,
+            ns,
+            batch,
+            cursor,
+        } = parse_cursor_reply(reply, "firstBatch")?;
+        let collection = ns
+            .split_once('.')
+            .map(|(_, collection)| collection.to_string())
+            .ok_or_else(|| invalid_reply(&format!("invalid cursor namespace {}", ns)))?;
+        Ok((
+            CommandCursor {
+                db,
+                session,
+                collection,
+                id,
+                batch,
+            },
+            cursor,
+        ))
+    }
+
+    // Return the next document of the result set, retrieving the next batch if needed, or None
+    // once all the documents have been returned.
+    pub(crate) fn next(&mut self) -> Result<Option<Document>> {
+        while self.batch.is_empty() && self.id != 0 {
+            let reply = self.db.run_command_with_session(
+                doc! {"getMore": self.id, "collection": &self.collection},
+                None,
+                &mut self.session,
+            )?;
+            let CursorReply { id, batch, .. } = parse_cursor_reply(reply, "nextBatch")?;
+            self.id = id;
+            self.batch = batch;
+        }
+        Ok(self.batch.pop_front())
+    }
+}
+
+impl Drop for CommandCursor {
+    // Kill the cursor on the server if it still has batches to return. A failure is ignored, the
+    // server eventually times the cursor out.
+    fn drop(&mut self) {
+        if self.id != 0 {
+            let _ = self.db.run_command_with_session(
+                doc! {"killCursors": &self.collection, "cursors": [self.id]},
+                None,
+                &mut self.session,
+            );
+        }
+    }
+}
+
+impl fmt::Debug for CommandCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandCursor")
+            .field("db", &self.db.name())
+            .field("collection", &self.collection)
+            .field("id", &self.id)
+            .field("batch", &self.batch)
+            .finish()
+    }
+}
+
+// Split the cursor document of the given command reply, whose documents are in the batch_field
+// array: firstBatch for the command opening the cursor and nextBatch for getMore.
+pub(crate) fn parse_cursor_reply(mut reply: Document, batch_field: &str) -> Result<CursorReply> {
+    let mut cursor = match reply.remove("cursor") {
+        Some(Bson::Document(cursor)) => cursor,
+        _ => return Err(invalid_reply("missing cursor document")),
+    };
+    let id = cursor
+        .get_i64("id")
+        .map_err(|_| invalid_reply("missing cursor.id"))?;
+    let ns = match cursor.remove("ns") {
+        Some(Bson::String(ns)) => ns,
+        _ => return Err(invalid_reply("missing cursor.ns")),
+    };
+    let batch = match cursor.remove(batch_field) {
+        Some(Bson::Array(batch)) => batch
+            .into_iter()
+            .map(|document| match document {
+                Bson::Document(document) => Ok(document),
+                _ => Err(invalid_reply(&format!(
+                    "cursor.{} contains a value which is not a document",
+                    batch_field
+                ))),
+            })
+            .collect::<Result<VecDeque<_>>>()?,
+        _ => return Err(invalid_reply(&format!("missing cursor.{}", batch_field))),
+    };
+    cursor.remove("id");
+    Ok(CursorReply {
+        id,
+        ns,
+        batch,
+        cursor,
+    })
+}
+
+fn invalid_reply(reason: &str) -> Error {
+    Error::InvalidCursorReply(reason.to_string())
+}
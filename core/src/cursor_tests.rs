@@ -0,0 +1,75 @@
+use crate::{
+    cursor::parse_cursor_reply,
+    err::Error,
+    query::{json_schema, resultset_metadata_from_schema},
+};
+use bson::doc;
+
+#[test]
+fn aggregate_reply() {
+    let reply = doc! {
+        "cursor": {
+            "id": 42_i64,
+            "ns": "db.$cmd.aggregate",
+            "firstBatch": [{"foo": {"a": 1}}, {"foo": {"a": 2}}],
+            "schema": {
+                "version": 1,
+                "jsonSchema": {
+                    "bsonType": "object",
+                    "properties": {
+                        "foo": {
+                            "bsonType": "object",
+                            "properties": {"a": {"bsonType": "int"}},
+                            "required": ["a"],
+                        },
+                    },
+                    "required": ["foo"],
+                },
+            },
+        },
+        "ok": 1,
+    };
+    let cursor_reply = parse_cursor_reply(reply, "firstBatch").unwrap();
+    assert_eq!(42, cursor_reply.id);
+    assert_eq!("db.$cmd.aggregate", cursor_reply.ns);
+    assert_eq!(
+        vec![doc! {"foo": {"a": 1}}, doc! {"foo": {"a": 2}}],
+        Vec::from(cursor_reply.batch)
+    );
+
+    // The result set metadata is derived from the schema returned with the cursor.
+    let metadata =
+        resultset_metadata_from_schema("db", json_schema(&cursor_reply.cursor).unwrap()).unwrap();
+    assert_eq!(1, metadata.len());
+    assert_eq!("foo", metadata[0].table_name);
+    assert_eq!("a", metadata[0].col_name);
+    assert!(!metadata[0].is_nullable);
+}
+
+#[test]
+fn get_more_reply() {
+    let reply = doc! {
+        "cursor": {"id": 0_i64, "ns": "db.$cmd.aggregate", "nextBatch": [{"foo": {"a": 3}}]},
+        "ok": 1,
+    };
+    let cursor_reply = parse_cursor_reply(reply, "nextBatch").unwrap();
+    assert_eq!(0, cursor_reply.id);
+    assert_eq!(1, cursor_reply.batch.len());
+    assert!(json_schema(&cursor_reply.cursor).is_err());
+}
+
+#[test]
+fn invalid_replies() {
+    for reply in [
+        doc! {"ok": 1},
+        doc! {"cursor": {"ns": "db.coll", "firstBatch": []}},
+        doc! {"cursor": {"id": 0_i64, "firstBatch": []}},
+        doc! {"cursor": {"id": 0_i64, "ns": "db.coll", "nextBatch": []}},
+        doc! {"cursor": {"id": 0_i64, "ns": "db.coll", "firstBatch": [1]}},
+    ] {
+        assert!(matches!(
+            parse_cursor_reply(reply, "firstBatch"),
+            Err(Error::InvalidCursorReply(_))
+        ));
+    }
+}
@@ -1,15 +1,12 @@
+use crate::bson_type_info::BsonTypeInfo;
 use crate::conn::MongoConnection;
 use crate::err::Result;
-use crate::stmt::MongoStatement;
+use crate::query::{fixed_resultset_metadata, MongoColMetadata};
+use crate::rows::MongoRows;
 use bson::Bson;
 
-#[derive(Debug)]
-pub struct MongoDatabases {
-    // The list of all the databases
-    databases_names: Vec<String>,
-    // The current database index.
-    current_db_index: u32,
-}
+// Builds the MongoRows statements listing the databases.
+pub struct MongoDatabases;
 
 // Statement for SQLTables(SQL_ALL_CATALOGS, "","").
 impl MongoDatabases {
@@ -18,22 +15,44 @@ impl MongoDatabases {
     // All columns except the TABLE_CAT column contain NULLs.
     // The query timeout comes from the statement attribute SQL_ATTR_QUERY_TIMEOUT. If there is a
     // timeout, the query must finish before the timeout or an error is returned.
-    pub fn list_all_catalogs(_client: &MongoConnection, _query_timeout: Option<i32>) -> Self {
-        unimplemented!()
+    // The databases are ordered by name.
+    pub fn list_all_catalogs(
+        client: &MongoConnection,
+        _query_timeout: Option<i32>,
+    ) -> Result<MongoRows> {
+        let mut databases_names = client.client.list_database_names(None, None)?;
+        databases_names.sort();
+        Ok(MongoDatabases::from_names(databases_names))
     }
-}
 
-impl MongoStatement for MongoDatabases {
-    // Increment current_db_index.
-    // Return true if current_db_index index is < for databases_names.length.
-    fn next(&mut self) -> Result<bool> {
-        unimplemented!()
+    // Create the statement listing the given databases, in order.
+    pub(crate) fn from_names(databases_names: Vec<String>) -> MongoRows {
+        MongoRows::new(
+            databases_names
+                .into_iter()
+                .map(|database_name| {
+                    vec![
+                        Bson::String(database_name),
+                        Bson::Null,
+                        Bson::Null,
+                        Bson::Null,
+                        Bson::Null,
+                    ]
+                })
+                .collect(),
+            MongoDatabases::resultset_metadata(),
+        )
     }
 
-    // Get the BSON value for the value at the given colIndex on the current row.
-    fn get_value(&self, _col_index: u16) -> Result<Option<&Bson>> {
-        // The mapping for col_index <-> Value will be hard-coded and handled in this function
-        // 1-> databases_names[current_row_index]
-        unimplemented!()
+    // The columns of the SQLTables result set. Only TABLE_CAT has values when listing the
+    // catalogs.
+    fn resultset_metadata() -> Vec<MongoColMetadata> {
+        fixed_resultset_metadata(&[
+            ("TABLE_CAT", BsonTypeInfo::STRING, false),
+            ("TABLE_SCHEM", BsonTypeInfo::STRING, true),
+            ("TABLE_NAME", BsonTypeInfo::STRING, true),
+            ("TABLE_TYPE", BsonTypeInfo::STRING, true),
+            ("REMARKS", BsonTypeInfo::STRING, true),
+        ])
     }
 }
@@ -4,6 +4,24 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Error, Debug)]
 pub enum Error {
+    #[error("Invalid Uri: {0}")]
+    InvalidUriFormat(String),
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(mongodb::error::Error),
+    #[error("Login timeout expired: {0}")]
+    LoginTimeout(mongodb::error::Error),
+    #[error("Unable to reach the server: {0}")]
+    ServerUnreachable(mongodb::error::Error),
+    #[error("No database provided for the query")]
+    NoDatabase,
+    #[error("Invalid cursor state: the cursor is not positioned on a row")]
+    InvalidCursorState,
+    #[error("Column index {0} out of bounds")]
+    ColIndexOutOfBounds(u16),
+    #[error("Invalid result set schema: {0}")]
+    InvalidResultSetJsonSchema(String),
+    #[error("Invalid cursor reply: {0}")]
+    InvalidCursorReply(String),
     #[error(transparent)]
     MongoDriver(#[from] mongodb::error::Error), // Source and Display delegate to mongodb::Error
 }
@@ -1,52 +1,172 @@
-use crate::conn::MongoConnection;
+use crate::bson_type_info::BsonTypeInfo;
+use crate::collections::list_collections;
+use crate::conn::{with_max_time_ms, MongoConnection};
 use crate::err::Result;
-use crate::stmt::MongoStatement;
-use bson::{Bson, Document};
-use mongodb::sync::Cursor;
+use crate::query::{field_type, fixed_resultset_metadata, properties, required, MongoColMetadata};
+use crate::rows::MongoRows;
+use crate::util::{is_match, opt_int};
+use bson::{doc, Bson, Document};
+use odbc_sys::{Nullability, SqlDataType};
 
-#[derive(Debug)]
-struct FieldsForCollection {
-    database_name: String,
-    collection_name: String,
-    // Info retrieved via sqlgetschema
-    // See https://docs.mongodb.com/datalake/reference/cli/sql/sqlgetschema/ for more details.
-    schema: Cursor<Document>,
-}
-
-#[derive(Debug)]
-pub struct MongoFields {
-    // The current collection specification.
-    current_field_list: Option<FieldsForCollection>,
-}
+// Builds the MongoRows statements listing the fields of collections.
+pub struct MongoFields;
 
-// Statement related to a SQLTables call.
+// Statement related to a SQLColumns call.
 // The Resultset columns are hard-coded and follow the ODBC resultset for SQLColumns :
-// TABLE_CAT, TABLE_SCHEMA, TABLE_NAME, TABLE_TYPE.
+// TABLE_CAT, TABLE_SCHEM, TABLE_NAME, COLUMN_NAME, DATA_TYPE, TYPE_NAME, COLUMN_SIZE,
+// BUFFER_LENGTH, DECIMAL_DIGITS, NUM_PREC_RADIX, NULLABLE, REMARKS, COLUMN_DEF, SQL_DATA_TYPE,
+// SQL_DATETIME_SUB, CHAR_OCTET_LENGTH, ORDINAL_POSITION, IS_NULLABLE.
 impl MongoFields {
-    // Create a new MongoStatement to list tables with the given database (catalogs) and collection
-    // (tables) names filters.
+    // Create a new MongoStatement to list the fields of the collections matching the given
+    // database (catalogs) and collection (tables) names filters. Only the fields matching the
+    // field (columns) name filter are listed. The filters are search patterns.
+    // The fields of a collection come from its schema, retrieved with sqlGetSchema. Collections
+    // without a schema have no fields.
+    // See https://docs.mongodb.com/datalake/reference/cli/sql/sqlgetschema/ for more details.
     // The query timeout comes from the statement attribute SQL_ATTR_QUERY_TIMEOUT. If there is a
     // timeout, the query must finish before the timeout or an error is returned.
     pub fn list_columns(
-        _client: &MongoConnection,
-        _query_timeout: Option<i32>,
-        _db_name_filter: &str,
-        _collection_name_filter: &str,
-    ) -> Self {
-        unimplemented!()
+        client: &MongoConnection,
+        query_timeout: Option<i32>,
+        db_name_filter: &str,
+        collection_name_filter: &str,
+        field_name_filter: &str,
+    ) -> Result<MongoRows> {
+        let mut collections =
+            list_collections(client, db_name_filter, collection_name_filter, &[])?;
+        collections.sort();
+        let mut fields = vec![];
+        for (database_name, collection_name, _) in collections {
+            if let Some(json_schema) =
+                get_collection_schema(client, query_timeout, &database_name, &collection_name)?
+            {
+                fields.extend(fields_from_schema(
+                    &database_name,
+                    &collection_name,
+                    &json_schema,
+                    field_name_filter,
+                )?);
+            }
+        }
+        Ok(MongoFields::from_rows(fields))
     }
-}
 
-impl MongoStatement for MongoFields {
-    // Move the cursor to the next document and update the current row.
-    // Return true if moving was successful, false otherwise.
-    fn next(&mut self) -> Result<bool> {
-        unimplemented!()
+    // Create the statement returning the given rows.
+    pub(crate) fn from_rows(fields: Vec<Vec<Bson>>) -> MongoRows {
+        MongoRows::new(fields, MongoFields::resultset_metadata())
     }
 
-    // Get the BSON value for the cell at the given colIndex on the current row.
-    // Fails if the first row as not been retrieved (next must be called at least once before getValue).
-    fn get_value(&self, _col_index: u16) -> Result<Option<&Bson>> {
-        unimplemented!()
+    // The columns of the SQLColumns result set.
+    fn resultset_metadata() -> Vec<MongoColMetadata> {
+        fixed_resultset_metadata(&[
+            ("TABLE_CAT", BsonTypeInfo::STRING, true),
+            ("TABLE_SCHEM", BsonTypeInfo::STRING, true),
+            ("TABLE_NAME", BsonTypeInfo::STRING, false),
+            ("COLUMN_NAME", BsonTypeInfo::STRING, false),
+            ("DATA_TYPE", BsonTypeInfo::SMALLINT, false),
+            ("TYPE_NAME", BsonTypeInfo::STRING, false),
+            ("COLUMN_SIZE", BsonTypeInfo::INT, true),
+            ("BUFFER_LENGTH", BsonTypeInfo::INT, true),
+            ("DECIMAL_DIGITS", BsonTypeInfo::SMALLINT, true),
+            ("NUM_PREC_RADIX", BsonTypeInfo::SMALLINT, true),
+            ("NULLABLE", BsonTypeInfo::SMALLINT, false),
+            ("REMARKS", BsonTypeInfo::STRING, true),
+            ("COLUMN_DEF", BsonTypeInfo::STRING, true),
+            ("SQL_DATA_TYPE", BsonTypeInfo::SMALLINT, false),
+            ("SQL_DATETIME_SUB", BsonTypeInfo::SMALLINT, true),
+            ("CHAR_OCTET_LENGTH", BsonTypeInfo::INT, true),
+            ("ORDINAL_POSITION", BsonTypeInfo::INT, false),
+            ("IS_NULLABLE", BsonTypeInfo::STRING, true),
+        ])
+    }
+}
+
+// Retrieve the JSON schema of the given collection with sqlGetSchema. Return None if the
+// collection has no schema set.
+pub(crate) fn get_collection_schema(
+    client: &MongoConnection,
+    query_timeout: Option<i32>,
+    database_name: &str,
+    collection_name: &str,
+) -> Result<Option<Document>> {
+    let command = with_max_time_ms(
+        doc! {"sqlGetSchema": collection_name},
+        client.max_time(query_timeout),
+    );
+    let mut response = client
+        .client
+        .database(database_name)
+        .run_command(command, None)?;
+    // A collection without a schema has an empty schema.
+    Ok(response
+        .get_document_mut("schema")
+        .ok()
+        .and_then(|schema| schema.remove("jsonSchema"))
+        .and_then(|json_schema| match json_schema {
+            Bson::Document(json_schema) => Some(json_schema),
+            _ => None,
+        }))
+}
+
+// Build the SQLColumns rows describing the top-level fields of the given collection schema whose
+// name matches field_name_filter. The fields are ordered by name, and the ordinal position of a
+// field is its position among all the fields of the collection.
+pub(crate) fn fields_from_schema(
+    database_name: &str,
+    collection_name: &str,
+    json_schema: &Document,
+    field_name_filter: &str,
+) -> Result<Vec<Vec<Bson>>> {
+    let required = required(json_schema)?;
+    let mut rows = vec![];
+    for (ordinal_position, (field, field_schema)) in
+        properties(json_schema)?.into_iter().enumerate()
+    {
+        if !is_match(field, field_name_filter) {
+            continue;
+        }
+        let (type_info, allows_null) = field_type(field_schema)?;
+        let is_nullable = allows_null || !required.contains(field.as_str());
+        let (sql_data_type, datetime_sub) = type_info.verbose_type();
+        let is_character = matches!(
+            type_info.sql_type,
+            SqlDataType::VARCHAR | SqlDataType::EXT_BINARY
+        );
+        rows.push(vec![
+            Bson::String(database_name.to_string()),
+            // TABLE_SCHEM
+            Bson::Null,
+            Bson::String(collection_name.to_string()),
+            Bson::String(field.to_string()),
+            Bson::Int32(type_info.sql_type.0 as i32),
+            Bson::String(type_info.type_name.to_string()),
+            opt_int(type_info.column_size),
+            opt_int(type_info.octet_length),
+            opt_int(type_info.scale),
+            if type_info.is_numeric() {
+                Bson::Int32(10)
+            } else {
+                Bson::Null
+            },
+            Bson::Int32(if is_nullable {
+                Nullability::NULLABLE.0
+            } else {
+                Nullability::NO_NULLS.0
+            } as i32),
+            // REMARKS
+            Bson::Null,
+            // COLUMN_DEF
+            Bson::Null,
+            Bson::Int32(sql_data_type.0 as i32),
+            opt_int(datetime_sub.map(|datetime_sub| datetime_sub as u16)),
+            if is_character {
+                opt_int(type_info.octet_length)
+            } else {
+                Bson::Null
+            },
+            Bson::Int32(ordinal_position as i32 + 1),
+            Bson::String(if is_nullable { "YES" } else { "NO" }.to_string()),
+        ]);
     }
+    Ok(rows)
 }
@@ -1,18 +1,47 @@
 #![allow(dead_code)]
+mod bson_type_info;
+pub use bson_type_info::{BsonTypeInfo, Searchable};
+#[cfg(test)]
+mod catalog_tests;
 mod collections;
 pub use collections::MongoCollections;
 mod conn;
 pub use conn::MongoConnection;
+#[cfg(test)]
+mod conn_tests;
+mod cursor;
+#[cfg(test)]
+mod cursor_tests;
 mod databases;
 pub use databases::MongoDatabases;
 mod err;
 mod fields;
 pub use fields::MongoFields;
+mod primary_keys;
+pub use primary_keys::MongoPrimaryKeys;
 mod query;
 pub use query::{MongoColMetadata, MongoQuery};
+#[cfg(test)]
+mod query_tests;
+mod rows;
+pub use rows::MongoRows;
+mod special_columns;
+pub use special_columns::MongoSpecialColumns;
+mod statistics;
+pub use statistics::MongoStatistics;
 mod stmt;
 pub use err::{Error, Result};
 pub use stmt::MongoStatement;
+mod table_types;
+pub use table_types::MongoTableTypes;
+mod type_info;
+pub use type_info::MongoTypesInfo;
+#[cfg(test)]
+mod type_info_tests;
+mod util;
+pub use util::{escape_pattern, SEARCH_PATTERN_ESCAPE};
+#[cfg(test)]
+mod util_tests;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,72 @@
+use crate::bson_type_info::BsonTypeInfo;
+use crate::collections::{list_collections, TABLE};
+use crate::conn::MongoConnection;
+use crate::err::Result;
+use crate::query::fixed_resultset_metadata;
+use crate::rows::MongoRows;
+use bson::Bson;
+
+// The field every document of a collection has, with a unique value.
+pub const ID_FIELD: &str = "_id";
+// The name of the unique index on _id, reported as the name of the primary key.
+pub const ID_INDEX_NAME: &str = "_id_";
+
+// Builds the MongoRows statements listing the primary keys of collections.
+pub struct MongoPrimaryKeys;
+
+// Statement related to a SQLPrimaryKeys call.
+// The Resultset columns are hard-coded and follow the ODBC resultset for SQLPrimaryKeys :
+// TABLE_CAT, TABLE_SCHEM, TABLE_NAME, COLUMN_NAME, KEY_SEQ, PK_NAME.
+impl MongoPrimaryKeys {
+    // Create a new MongoStatement to list the primary keys of the collections matching the given
+    // database (catalogs) and collection (tables) names filters, which are search patterns.
+    // The primary key of a collection is its _id field. Views have no primary key.
+    // The query timeout comes from the statement attribute SQL_ATTR_QUERY_TIMEOUT. If there is a
+    // timeout, the query must finish before the timeout or an error is returned.
+    pub fn list_primary_keys(
+        client: &MongoConnection,
+        _query_timeout: Option<i32>,
+        db_name_filter: &str,
+        collection_name_filter: &str,
+    ) -> Result<MongoRows> {
+        let collections =
+            list_collections(client, db_name_filter, collection_name_filter, &[TABLE])?;
+        Ok(MongoPrimaryKeys::from_collections(
+            collections
+                .into_iter()
+                .map(|(database_name, collection_name, _)| (database_name, collection_name))
+                .collect(),
+        ))
+    }
+
+    // Create the statement listing the primary keys of the given collections, as (database name,
+    // collection name). The keys are ordered by TABLE_CAT and TABLE_NAME.
+    pub(crate) fn from_collections(mut collections: Vec<(String, String)>) -> MongoRows {
+        collections.sort();
+        MongoRows::new(
+            collections
+                .into_iter()
+                .map(|(database_name, collection_name)| {
+                    vec![
+                        Bson::String(database_name),
+                        // TABLE_SCHEM
+                        Bson::Null,
+                        Bson::String(collection_name),
+                        Bson::String(ID_FIELD.to_string()),
+                        // KEY_SEQ
+                        Bson::Int32(1),
+                        Bson::String(ID_INDEX_NAME.to_string()),
+                    ]
+                })
+                .collect(),
+            fixed_resultset_metadata(&[
+                ("TABLE_CAT", BsonTypeInfo::STRING, true),
+                ("TABLE_SCHEM", BsonTypeInfo::STRING, true),
+                ("TABLE_NAME", BsonTypeInfo::STRING, false),
+                ("COLUMN_NAME", BsonTypeInfo::STRING, false),
+                ("KEY_SEQ", BsonTypeInfo::SMALLINT, false),
+                ("PK_NAME", BsonTypeInfo::STRING, true),
+            ]),
+        )
+    }
+}
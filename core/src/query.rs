@@ -1,52 +1,128 @@
-use crate::conn::MongoConnection;
-use crate::err::Result;
+use crate::bson_type_info::{BsonTypeInfo, Searchable, MAX_VALUE_LENGTH};
+use crate::conn::{with_max_time_ms, MongoConnection};
+use crate::cursor::CommandCursor;
+use crate::err::{Error, Result};
 use crate::stmt::MongoStatement;
-use bson::{Bson, Document};
-use mongodb::sync::Cursor;
+use bson::{doc, Bson, Document};
+use odbc_sys::SqlDataType;
+use std::collections::BTreeSet;
 
 #[derive(Debug)]
 pub struct MongoQuery {
-    // The cursor on the result set.
-    resultset_cursor: Cursor<Document>,
-    // The result set metadata.
+    // The query, run again each time the cursor is opened.
+    query: String,
+    // The cursor on the result set, None when the query is only prepared or the cursor closed.
+    pub(crate) resultset_cursor: Option<CommandCursor>,
+    // The result set metadata, from the schema returned along with the cursor once the query is
+    // executed.
     resultset_metadata: Vec<MongoColMetadata>,
+    // The document the cursor is currently positioned on, if any.
+    pub(crate) current: Option<Document>,
 }
 
 impl MongoQuery {
+    // Prepare the given query on the connection currentDB, without running it.
+    // Only the result set metadata is retrieved, with the sqlGetResultSchema command, so that the
+    // columns can be described before the cursor is opened.
+    pub fn prepare(client: &MongoConnection, query: &str) -> Result<Self> {
+        let current_db = client.current_db.as_deref().ok_or(Error::NoDatabase)?;
+        let response = client.client.database(current_db).run_command(
+            client
+                .with_max_time(doc! {"sqlGetResultSchema": 1, "query": query, "schemaVersion": 1}),
+            None,
+        )?;
+        let resultset_metadata =
+            resultset_metadata_from_schema(current_db, json_schema(&response)?)?;
+
+        Ok(MongoQuery {
+            query: query.to_string(),
+            resultset_cursor: None,
+            resultset_metadata,
+            current: None,
+        })
+    }
+
     // Create a new MongoStatement with StmtKind::Query on the connection currentDB.
-    // Executes a $sql aggregation with the given query and initialize the Resultset cursor.
+    // Executes a $sql aggregation with the given query and initialize the Resultset cursor and
+    // metadata.
     // The query timeout comes from the statement attribute SQL_ATTR_QUERY_TIMEOUT. If there is a
     // timeout, the query must finish before the timeout or an error is returned
     pub fn execute(
-        _client: &MongoConnection,
-        _query_timeout: Option<i32>,
-        _query: &str,
+        client: &MongoConnection,
+        query_timeout: Option<i32>,
+        query: &str,
     ) -> Result<Self> {
-        unimplemented!()
-    }
-
-    // Return the number of fields/columns in the resultset
-    fn _get_col_count(&self) -> u32 {
-        unimplemented!()
-    }
-
-    // Get the metadata for the column with the given index.
-    fn _get_col_metadata(&self, _col_index: u16) -> Result<MongoColMetadata> {
-        unimplemented!()
+        let mut mongo_query = MongoQuery {
+            query: query.to_string(),
+            resultset_cursor: None,
+            resultset_metadata: vec![],
+            current: None,
+        };
+        mongo_query.open_cursor(client, query_timeout)?;
+        Ok(mongo_query)
     }
 }
 
 impl MongoStatement for MongoQuery {
     // Move the cursor to the next document and update the current row.
     // Return true if moving was successful, false otherwise.
+    // Fails if the query is only prepared or the cursor is closed.
     fn next(&mut self) -> Result<bool> {
-        unimplemented!()
+        let cursor = self
+            .resultset_cursor
+            .as_mut()
+            .ok_or(Error::InvalidCursorState)?;
+        self.current = cursor.next()?;
+        Ok(self.current.is_some())
     }
 
     // Get the BSON value for the cell at the given colIndex on the current row.
     // Fails if the first row as not been retrieved (next must be called at least once before getValue).
-    fn get_value(&self, _col_index: u16) -> Result<Option<&Bson>> {
-        unimplemented!()
+    // Column indexes start at 1. The $sql results are nested by datasource, so the value is
+    // looked up under the table name of the column first.
+    fn get_value(&self, col_index: u16) -> Result<Option<&Bson>> {
+        let current = self.current.as_ref().ok_or(Error::InvalidCursorState)?;
+        let metadata = self._get_col_metadata(col_index)?;
+        Ok(current
+            .get_document(&metadata.table_name)
+            .ok()
+            .and_then(|datasource| datasource.get(&metadata.col_name)))
+    }
+
+    fn get_resultset_metadata(&self) -> &[MongoColMetadata] {
+        &self.resultset_metadata
+    }
+
+    // Run the $sql aggregation of the query and open a cursor on its result set. The $sql stage
+    // returns the schema of the result set in the reply cursor document, from which the result
+    // set metadata is derived, so that the metadata always describes the documents the cursor
+    // returns.
+    fn open_cursor(&mut self, client: &MongoConnection, query_timeout: Option<i32>) -> Result<()> {
+        let current_db = client.current_db.as_deref().ok_or(Error::NoDatabase)?;
+        let command = with_max_time_ms(
+            doc! {
+                "aggregate": 1,
+                "pipeline": [{"$sql": {
+                    "format": "odbc",
+                    "formatVersion": 1,
+                    "statement": &self.query,
+                }}],
+                "cursor": {},
+            },
+            client.max_time(query_timeout),
+        );
+        let (cursor, cursor_reply) = CommandCursor::open(client, current_db, command)?;
+        self.resultset_metadata =
+            resultset_metadata_from_schema(current_db, json_schema(&cursor_reply)?)?;
+        self.resultset_cursor = Some(cursor);
+        self.current = None;
+        Ok(())
+    }
+
+    // Drop the cursor, which kills it on the server, and keep the result set metadata.
+    fn close_cursor(&mut self) {
+        self.resultset_cursor = None;
+        self.current = None;
     }
 }
 
@@ -68,10 +144,227 @@ pub struct MongoColMetadata {
     pub octet_length: u128,
     pub precision: u16,
     pub scale: u16,
-    pub is_searchable: bool,
+    // How the column can be used in a WHERE clause.
+    pub searchable: Searchable,
+    // Whether the column has an exact or approximate numeric type.
+    pub is_numeric: bool,
     pub table_name: String,
     // BSON type name
     pub type_name: String,
+    // The SQL type the values of the column are reported as.
+    pub sql_type: SqlDataType,
     pub is_unsigned: bool,
     pub is_updatable: bool,
+    pub is_case_sensitive: bool,
+    // The characters used to prefix and suffix a literal of the column type in a query.
+    pub literal_prefix: Option<&'static str>,
+    pub literal_suffix: Option<&'static str>,
+}
+
+impl MongoColMetadata {
+    // Create the metadata for the given field of the given datasource, in a result set with
+    // col_count columns.
+    fn new(
+        current_db: &str,
+        datasource: &str,
+        field: &str,
+        type_info: BsonTypeInfo,
+        is_nullable: bool,
+        col_count: u16,
+    ) -> Self {
+        let column_size = type_info.column_size.unwrap_or_default();
+        MongoColMetadata {
+            base_col_name: field.to_string(),
+            base_table_name: datasource.to_string(),
+            catalog_name: current_db.to_string(),
+            col_count,
+            display_size: type_info.display_size.unwrap_or_default() as u64,
+            fixed_prec_scale: false,
+            label: field.to_string(),
+            length: column_size as u128,
+            col_name: field.to_string(),
+            is_nullable,
+            octet_length: type_info.octet_length.unwrap_or_default() as u128,
+            // The precision doesn't apply to the variable length types.
+            precision: match type_info.column_size {
+                Some(MAX_VALUE_LENGTH) | None => 0,
+                Some(size) => size as u16,
+            },
+            scale: type_info.scale.unwrap_or_default(),
+            searchable: type_info.searchable,
+            is_numeric: type_info.is_numeric(),
+            table_name: datasource.to_string(),
+            type_name: type_info.type_name.to_string(),
+            sql_type: type_info.sql_type,
+            is_unsigned: type_info.is_unsigned,
+            is_updatable: false,
+            is_case_sensitive: type_info.is_case_sensitive,
+            literal_prefix: type_info.literal_prefix,
+            literal_suffix: type_info.literal_suffix,
+        }
+    }
+}
+
+// Create the metadata of a result set whose columns don't depend on the data, like the result
+// sets of the catalog functions. The columns are given in order, with their name, type and
+// whether they can contain null values.
+pub(crate) fn fixed_resultset_metadata(
+    columns: &[(&str, BsonTypeInfo, bool)],
+) -> Vec<MongoColMetadata> {
+    let col_count = columns.len() as u16;
+    columns
+        .iter()
+        .map(|(name, type_info, is_nullable)| {
+            MongoColMetadata::new("", "", name, *type_info, *is_nullable, col_count)
+        })
+        .collect()
+}
+
+// Return the JSON schema of a result set, found under schema.jsonSchema both in the
+// sqlGetResultSchema response and in the cursor document of the $sql aggregation reply.
+pub(crate) fn json_schema(response: &Document) -> Result<&Document> {
+    response
+        .get_document("schema")
+        .and_then(|schema| schema.get_document("jsonSchema"))
+        .map_err(|_| invalid_schema("missing schema.jsonSchema"))
+}
+
+// Derive the columns metadata from the JSON schema of a $sql result set.
+// The result set documents contain one sub-document per datasource, holding the fields of the
+// datasource. There is one column per field, ordered by datasource and then by field name so
+// that the columns order is stable.
+// A column is nullable if its field is not required or if its schema allows null values.
+// A column whose values can have more than one (non-null) type is reported as a bson column.
+pub(crate) fn resultset_metadata_from_schema(
+    current_db: &str,
+    json_schema: &Document,
+) -> Result<Vec<MongoColMetadata>> {
+    let mut columns = vec![];
+    for (datasource, datasource_schema) in properties(json_schema)? {
+        let required = required(datasource_schema)?;
+        for (field, field_schema) in properties(datasource_schema)? {
+            let (type_info, allows_null) = field_type(field_schema)?;
+            columns.push((
+                datasource,
+                field,
+                type_info,
+                allows_null || !required.contains(field.as_str()),
+            ));
+        }
+    }
+    let col_count = columns.len() as u16;
+    Ok(columns
+        .into_iter()
+        .map(|(datasource, field, type_info, is_nullable)| {
+            MongoColMetadata::new(
+                current_db,
+                datasource,
+                field,
+                type_info,
+                is_nullable,
+                col_count,
+            )
+        })
+        .collect())
+}
+
+// Return the type of the values matching the given field schema, and whether they can be null.
+// Values which can have more than one (non-null) type, or whose type is not restricted, are
+// reported as bson values.
+pub(crate) fn field_type(field_schema: &Document) -> Result<(BsonTypeInfo, bool)> {
+    Ok(match bson_types(field_schema)? {
+        None => (BsonTypeInfo::BSON, true),
+        Some(mut types) => {
+            let allows_null = types.remove(BsonTypeInfo::NULL.type_name);
+            let type_info = match types.len() {
+                0 => BsonTypeInfo::NULL,
+                1 => types
+                    .iter()
+                    .next()
+                    .and_then(|type_name| BsonTypeInfo::from_type_name(type_name))
+                    .unwrap_or(BsonTypeInfo::BSON),
+                _ => BsonTypeInfo::BSON,
+            };
+            (type_info, allows_null)
+        }
+    })
+}
+
+// Return the properties of the given object schema, sorted by name.
+pub(crate) fn properties(schema: &Document) -> Result<Vec<(&String, &Document)>> {
+    let mut properties = match schema.get("properties") {
+        None => vec![],
+        Some(Bson::Document(properties)) => properties
+            .iter()
+            .map(|(name, property)| match property {
+                Bson::Document(property) => Ok((name, property)),
+                _ => Err(invalid_schema(&format!(
+                    "the schema of property {} is not a document",
+                    name
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Some(_) => return Err(invalid_schema("properties is not a document")),
+    };
+    properties.sort_by_key(|(name, _)| *name);
+    Ok(properties)
+}
+
+// Return the names of the required properties of the given object schema.
+pub(crate) fn required(schema: &Document) -> Result<BTreeSet<&str>> {
+    match schema.get("required") {
+        None => Ok(BTreeSet::new()),
+        Some(Bson::Array(required)) => required
+            .iter()
+            .map(|name| {
+                name.as_str()
+                    .ok_or_else(|| invalid_schema("required is not an array of strings"))
+            })
+            .collect(),
+        Some(_) => Err(invalid_schema("required is not an array of strings")),
+    }
+}
+
+// Collect the BSON types the values matching the given schema can have, from its bsonType or
+// anyOf keywords. Return None if the schema doesn't restrict the type of the values.
+fn bson_types(schema: &Document) -> Result<Option<BTreeSet<String>>> {
+    if let Some(bson_type) = schema.get("bsonType") {
+        return match bson_type {
+            Bson::String(type_name) => Ok(Some(BTreeSet::from([type_name.clone()]))),
+            Bson::Array(type_names) => type_names
+                .iter()
+                .map(|type_name| {
+                    type_name.as_str().map(String::from).ok_or_else(|| {
+                        invalid_schema("bsonType is not a string or an array of strings")
+                    })
+                })
+                .collect::<Result<_>>()
+                .map(Some),
+            _ => Err(invalid_schema(
+                "bsonType is not a string or an array of strings",
+            )),
+        };
+    }
+    match schema.get("anyOf") {
+        None => Ok(None),
+        Some(Bson::Array(schemas)) => {
+            let mut types = BTreeSet::new();
+            for schema in schemas {
+                let schema = schema
+                    .as_document()
+                    .ok_or_else(|| invalid_schema("anyOf is not an array of schemas"))?;
+                match bson_types(schema)? {
+                    Some(schema_types) => types.extend(schema_types),
+                    // One of the alternatives allows any type.
+                    None => return Ok(None),
+                }
+            }
+            Ok(Some(types))
+        }
+        Some(_) => Err(invalid_schema("anyOf is not an array of schemas")),
+    }
+}
+
+fn invalid_schema(reason: &str) -> Error {
+    Error::InvalidResultSetJsonSchema(reason.to_string())
 }
@@ -0,0 +1,122 @@
+use crate::{
+    conn::MongoConnection, err::Error, query::resultset_metadata_from_schema, stmt::MongoStatement,
+    MongoQuery,
+};
+use bson::doc;
+use odbc_sys::SqlDataType;
+use std::env;
+
+#[test]
+fn metadata_from_schema() {
+    let json_schema = doc! {
+        "bsonType": "object",
+        "properties": {
+            "foo": {
+                "bsonType": "object",
+                "properties": {
+                    "b": {"bsonType": "string"},
+                    "a": {"bsonType": "int"},
+                    "c": {"anyOf": [{"bsonType": "long"}, {"bsonType": "null"}]},
+                    "d": {"anyOf": [{"bsonType": "int"}, {"bsonType": "string"}]},
+                    "e": {"bsonType": ["double", "null", "string"]},
+                    "f": {},
+                },
+                "required": ["a", "b", "c", "d", "e", "f"],
+            },
+            "bar": {
+                "bsonType": "object",
+                "properties": {
+                    "_id": {"bsonType": "objectId"},
+                    "date": {"bsonType": "date"},
+                },
+                "required": ["_id"],
+            },
+        },
+        "required": ["foo", "bar"],
+    };
+    let metadata = resultset_metadata_from_schema("db", &json_schema).unwrap();
+    let columns: Vec<(&str, &str, &str, SqlDataType, bool)> = metadata
+        .iter()
+        .map(|col| {
+            (
+                col.table_name.as_str(),
+                col.col_name.as_str(),
+                col.type_name.as_str(),
+                col.sql_type,
+                col.is_nullable,
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("bar", "_id", "objectId", SqlDataType::VARCHAR, false),
+            ("bar", "date", "date", SqlDataType::TIMESTAMP, true),
+            ("foo", "a", "int", SqlDataType::INTEGER, false),
+            ("foo", "b", "string", SqlDataType::VARCHAR, false),
+            ("foo", "c", "long", SqlDataType::EXT_BIG_INT, true),
+            ("foo", "d", "bson", SqlDataType::VARCHAR, false),
+            ("foo", "e", "bson", SqlDataType::VARCHAR, true),
+            ("foo", "f", "bson", SqlDataType::VARCHAR, true),
+        ],
+        columns
+    );
+
+    let int_col = &metadata[2];
+    assert_eq!("db", int_col.catalog_name);
+    assert_eq!(10, int_col.precision);
+    assert_eq!(0, int_col.scale);
+    assert_eq!(11, int_col.display_size);
+    assert_eq!(4, int_col.octet_length);
+    assert!(!int_col.is_unsigned);
+    assert_eq!(8, int_col.col_count);
+}
+
+#[test]
+fn invalid_schema() {
+    assert!(resultset_metadata_from_schema("db", &doc! {"properties": 1}).is_err());
+    assert!(resultset_metadata_from_schema(
+        "db",
+        &doc! {"properties": {"foo": {"properties": {"a": {"bsonType": 1}}}}}
+    )
+    .is_err());
+    assert!(
+        resultset_metadata_from_schema("db", &doc! {"properties": {"foo": {"required": "a"}}})
+            .is_err()
+    );
+}
+
+/// Connect to the server the integration tests run against, using the same 'ADL_TEST_*'
+/// environment variables.
+fn test_connection() -> MongoConnection {
+    let user_name = env::var("ADL_TEST_USER").expect("ADL_TEST_USER is not set");
+    let password = env::var("ADL_TEST_PWD").expect("ADL_TEST_PWD is not set");
+    let host = env::var("ADL_TEST_HOST").expect("ADL_TEST_HOST is not set");
+    let auth_db = env::var("ADL_TEST_AUTH_DB").unwrap_or_else(|_| "admin".to_string());
+    let db = env::var("ADL_TEST_DB").unwrap_or_else(|_| "test".to_string());
+    let uri = format!("mongodb://{user_name}:{password}@{host}/?authSource={auth_db}");
+    MongoConnection::connect(&uri, Some(&db), None, None).unwrap()
+}
+
+#[test]
+#[ignore = "needs the server set by the ADL_TEST_* environment variables"]
+fn close_cursor_after_execute() {
+    let connection = test_connection();
+    let mut query = MongoQuery::prepare(&connection, "SELECT * FROM [{'a': 1}] AS arr").unwrap();
+    assert!(query.resultset_cursor.is_none());
+
+    // SQLExecute opens the cursor of the prepared query.
+    query.open_cursor(&connection, None).unwrap();
+    assert!(query.resultset_cursor.is_some());
+    assert!(query.next().unwrap());
+
+    // SQLCloseCursor drops it but keeps the query prepared.
+    query.close_cursor();
+    assert!(query.resultset_cursor.is_none());
+    assert!(query.current.is_none());
+    assert!(matches!(query.next(), Err(Error::InvalidCursorState)));
+    assert_eq!(1, query.get_resultset_metadata().len());
+
+    // The prepared query can then be executed again.
+    query.open_cursor(&connection, None).unwrap();
+    assert!(query.next().unwrap());
+}
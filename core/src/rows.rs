@@ -0,0 +1,61 @@
+use crate::err::{Error, Result};
+use crate::query::MongoColMetadata;
+use crate::stmt::MongoStatement;
+use bson::Bson;
+
+// A statement whose result set is computed by the driver, as the catalog functions' are. Each row
+// holds the values of all the columns, NULL being Bson::Null.
+#[derive(Debug)]
+pub struct MongoRows {
+    // The rows of the result set, in the order they are returned.
+    rows: Vec<Vec<Bson>>,
+    // The number of rows returned so far, so the current row is at current_row_index - 1.
+    current_row_index: usize,
+    // The result set metadata.
+    resultset_metadata: Vec<MongoColMetadata>,
+}
+
+impl MongoRows {
+    // Create the statement returning the given rows, described by resultset_metadata.
+    pub(crate) fn new(rows: Vec<Vec<Bson>>, resultset_metadata: Vec<MongoColMetadata>) -> Self {
+        MongoRows {
+            rows,
+            current_row_index: 0,
+            resultset_metadata,
+        }
+    }
+}
+
+impl MongoStatement for MongoRows {
+    // Move to the next row.
+    // Return true if moving was successful, false otherwise.
+    fn next(&mut self) -> Result<bool> {
+        if self.current_row_index < self.rows.len() {
+            self.current_row_index += 1;
+            Ok(true)
+        } else {
+            // Move past the last row so that there is no current row anymore.
+            self.current_row_index = self.rows.len() + 1;
+            Ok(false)
+        }
+    }
+
+    // Get the BSON value for the cell at the given colIndex on the current row.
+    // Fails if the first row as not been retrieved (next must be called at least once before getValue).
+    fn get_value(&self, col_index: u16) -> Result<Option<&Bson>> {
+        let current_row = self
+            .current_row_index
+            .checked_sub(1)
+            .and_then(|i| self.rows.get(i))
+            .ok_or(Error::InvalidCursorState)?;
+        col_index
+            .checked_sub(1)
+            .and_then(|i| current_row.get(i as usize))
+            .map(Some)
+            .ok_or(Error::ColIndexOutOfBounds(col_index))
+    }
+
+    fn get_resultset_metadata(&self) -> &[MongoColMetadata] {
+        &self.resultset_metadata
+    }
+}
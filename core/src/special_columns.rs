@@ -0,0 +1,103 @@
+use crate::bson_type_info::BsonTypeInfo;
+use crate::collections::{list_collections, TABLE};
+use crate::conn::MongoConnection;
+use crate::err::Result;
+use crate::fields::get_collection_schema;
+use crate::primary_keys::ID_FIELD;
+use crate::query::{field_type, fixed_resultset_metadata, properties};
+use crate::rows::MongoRows;
+use crate::util::opt_int;
+use bson::{Bson, Document};
+
+// SQL_SCOPE_SESSION: the row id is valid for the duration of the session.
+const SQL_SCOPE_SESSION: i32 = 2;
+// SQL_PC_NOT_PSEUDO: the column is not a pseudo-column.
+const SQL_PC_NOT_PSEUDO: i32 = 1;
+
+// Builds the MongoRows statements listing the row id columns of collections.
+pub struct MongoSpecialColumns;
+
+// Statement related to a SQLSpecialColumns call.
+// The Resultset columns are hard-coded and follow the ODBC resultset for SQLSpecialColumns :
+// SCOPE, COLUMN_NAME, DATA_TYPE, TYPE_NAME, COLUMN_SIZE, BUFFER_LENGTH, DECIMAL_DIGITS,
+// PSEUDO_COLUMN.
+impl MongoSpecialColumns {
+    // Create a new MongoStatement to list the columns uniquely identifying a row
+    // (SQL_BEST_ROWID) of the collections matching the given database (catalogs) and collection
+    // (tables) names filters, which are search patterns.
+    // The _id field identifies the documents of a collection for the whole session. Its type
+    // comes from the collection schema, and is objectId if the collection has no schema. Views
+    // have no row id.
+    // The query timeout comes from the statement attribute SQL_ATTR_QUERY_TIMEOUT. If there is a
+    // timeout, the query must finish before the timeout or an error is returned.
+    pub fn list_best_rowid(
+        client: &MongoConnection,
+        query_timeout: Option<i32>,
+        db_name_filter: &str,
+        collection_name_filter: &str,
+    ) -> Result<MongoRows> {
+        let mut collections =
+            list_collections(client, db_name_filter, collection_name_filter, &[TABLE])?;
+        collections.sort();
+        let mut id_types = vec![];
+        for (database_name, collection_name, _) in collections {
+            let json_schema =
+                get_collection_schema(client, query_timeout, &database_name, &collection_name)?;
+            id_types.push(id_type(json_schema.as_ref())?);
+        }
+        Ok(MongoSpecialColumns::from_id_types(id_types))
+    }
+
+    // Create a statement with no rows, for the row version columns (SQL_ROWVER): no column is
+    // automatically updated when a document is updated.
+    pub fn empty() -> MongoRows {
+        MongoSpecialColumns::from_id_types(vec![])
+    }
+
+    // Create the statement listing an _id column of each of the given types.
+    pub(crate) fn from_id_types(id_types: Vec<BsonTypeInfo>) -> MongoRows {
+        MongoRows::new(
+            id_types
+                .into_iter()
+                .map(|type_info| {
+                    vec![
+                        Bson::Int32(SQL_SCOPE_SESSION),
+                        Bson::String(ID_FIELD.to_string()),
+                        Bson::Int32(type_info.sql_type.0 as i32),
+                        Bson::String(type_info.type_name.to_string()),
+                        opt_int(type_info.column_size),
+                        opt_int(type_info.octet_length),
+                        opt_int(type_info.scale),
+                        Bson::Int32(SQL_PC_NOT_PSEUDO),
+                    ]
+                })
+                .collect(),
+            fixed_resultset_metadata(&[
+                ("SCOPE", BsonTypeInfo::SMALLINT, true),
+                ("COLUMN_NAME", BsonTypeInfo::STRING, false),
+                ("DATA_TYPE", BsonTypeInfo::SMALLINT, false),
+                ("TYPE_NAME", BsonTypeInfo::STRING, false),
+                ("COLUMN_SIZE", BsonTypeInfo::INT, true),
+                ("BUFFER_LENGTH", BsonTypeInfo::INT, true),
+                ("DECIMAL_DIGITS", BsonTypeInfo::SMALLINT, true),
+                ("PSEUDO_COLUMN", BsonTypeInfo::SMALLINT, true),
+            ]),
+        )
+    }
+}
+
+// Return the type of the _id field in the given collection schema, objectId if there is no
+// schema or the schema doesn't describe _id.
+pub(crate) fn id_type(json_schema: Option<&Document>) -> Result<BsonTypeInfo> {
+    let json_schema = match json_schema {
+        None => return Ok(BsonTypeInfo::OBJECTID),
+        Some(json_schema) => json_schema,
+    };
+    match properties(json_schema)?
+        .into_iter()
+        .find(|(field, _)| *field == ID_FIELD)
+    {
+        None => Ok(BsonTypeInfo::OBJECTID),
+        Some((_, field_schema)) => Ok(field_type(field_schema)?.0),
+    }
+}
@@ -0,0 +1,200 @@
+use crate::bson_type_info::BsonTypeInfo;
+use crate::collections::{list_collections, TABLE};
+use crate::conn::MongoConnection;
+use crate::err::Result;
+use crate::primary_keys::ID_INDEX_NAME;
+use crate::query::fixed_resultset_metadata;
+use crate::rows::MongoRows;
+use bson::Bson;
+use mongodb::{
+    bson::Document,
+    options::{CountOptions, ListIndexesOptions},
+    IndexModel,
+};
+
+// The values of the TYPE column.
+const SQL_TABLE_STAT: i32 = 0;
+const SQL_INDEX_HASHED: i32 = 2;
+const SQL_INDEX_OTHER: i32 = 3;
+// The key value of a hashed index field.
+const HASHED: &str = "hashed";
+
+// Builds the MongoRows statements listing the statistics and indexes of collections.
+pub struct MongoStatistics;
+
+// Statement related to a SQLStatistics call.
+// The Resultset columns are hard-coded and follow the ODBC resultset for SQLStatistics :
+// TABLE_CAT, TABLE_SCHEM, TABLE_NAME, NON_UNIQUE, INDEX_QUALIFIER, INDEX_NAME, TYPE,
+// ORDINAL_POSITION, COLUMN_NAME, ASC_OR_DESC, CARDINALITY, PAGES, FILTER_CONDITION.
+impl MongoStatistics {
+    // Create a new MongoStatement to list the indexes of the collections matching the given
+    // database (catalogs) and collection (tables) names filters, which are search patterns.
+    // There is one row per index key, from the listIndexes command. Only the unique indexes are
+    // listed if unique_only is true. If ensure is true, each collection also has an
+    // SQL_TABLE_STAT row holding its number of documents. Views have no indexes.
+    // The query timeout comes from the statement attribute SQL_ATTR_QUERY_TIMEOUT. If there is a
+    // timeout, the query must finish before the timeout or an error is returned.
+    pub fn list_statistics(
+        client: &MongoConnection,
+        query_timeout: Option<i32>,
+        db_name_filter: &str,
+        collection_name_filter: &str,
+        unique_only: bool,
+        ensure: bool,
+    ) -> Result<MongoRows> {
+        let max_time = client.max_time(query_timeout);
+        let mut collections =
+            list_collections(client, db_name_filter, collection_name_filter, &[TABLE])?;
+        collections.sort();
+        let mut statistics = vec![];
+        for (database_name, collection_name, _) in collections {
+            let collection = client
+                .client
+                .database(&database_name)
+                .collection::<Document>(&collection_name);
+            if ensure {
+                let count = collection
+                    .count_documents(None, CountOptions::builder().max_time(max_time).build())?;
+                statistics.push(table_stat_row(&database_name, &collection_name, count));
+            }
+            let indexes = collection
+                .list_indexes(ListIndexesOptions::builder().max_time(max_time).build())?
+                .collect::<std::result::Result<Vec<IndexModel>, _>>()?;
+            statistics.extend(index_rows(
+                &database_name,
+                &collection_name,
+                indexes,
+                unique_only,
+            ));
+        }
+        Ok(MongoStatistics::from_rows(statistics))
+    }
+
+    // Create the statement returning the given SQLStatistics rows.
+    pub(crate) fn from_rows(statistics: Vec<Vec<Bson>>) -> MongoRows {
+        MongoRows::new(
+            statistics,
+            fixed_resultset_metadata(&[
+                ("TABLE_CAT", BsonTypeInfo::STRING, true),
+                ("TABLE_SCHEM", BsonTypeInfo::STRING, true),
+                ("TABLE_NAME", BsonTypeInfo::STRING, false),
+                ("NON_UNIQUE", BsonTypeInfo::SMALLINT, true),
+                ("INDEX_QUALIFIER", BsonTypeInfo::STRING, true),
+                ("INDEX_NAME", BsonTypeInfo::STRING, true),
+                ("TYPE", BsonTypeInfo::SMALLINT, false),
+                ("ORDINAL_POSITION", BsonTypeInfo::SMALLINT, true),
+                ("COLUMN_NAME", BsonTypeInfo::STRING, true),
+                ("ASC_OR_DESC", BsonTypeInfo::STRING, true),
+                ("CARDINALITY", BsonTypeInfo::INT, true),
+                ("PAGES", BsonTypeInfo::INT, true),
+                ("FILTER_CONDITION", BsonTypeInfo::STRING, true),
+            ]),
+        )
+    }
+}
+
+// Build the SQL_TABLE_STAT row of a collection holding count documents.
+pub(crate) fn table_stat_row(database_name: &str, collection_name: &str, count: u64) -> Vec<Bson> {
+    vec![
+        Bson::String(database_name.to_string()),
+        // TABLE_SCHEM
+        Bson::Null,
+        Bson::String(collection_name.to_string()),
+        // NON_UNIQUE, INDEX_QUALIFIER and INDEX_NAME
+        Bson::Null,
+        Bson::Null,
+        Bson::Null,
+        Bson::Int32(SQL_TABLE_STAT),
+        // ORDINAL_POSITION, COLUMN_NAME and ASC_OR_DESC
+        Bson::Null,
+        Bson::Null,
+        Bson::Null,
+        // CARDINALITY is an INTEGER, so larger counts are clamped.
+        Bson::Int32(i32::try_from(count).unwrap_or(i32::MAX)),
+        // PAGES and FILTER_CONDITION
+        Bson::Null,
+        Bson::Null,
+    ]
+}
+
+// Build the rows describing the keys of the given indexes of a collection, ordered by NON_UNIQUE,
+// INDEX_NAME and ORDINAL_POSITION. The _id index is unique even though listIndexes doesn't say so.
+// A key sorted with 1 or -1 is ascending or descending, and any other index kind (text,
+// 2dsphere, ...) has no sort order.
+pub(crate) fn index_rows(
+    database_name: &str,
+    collection_name: &str,
+    indexes: Vec<IndexModel>,
+    unique_only: bool,
+) -> Vec<Vec<Bson>> {
+    let mut indexes = indexes
+        .into_iter()
+        .map(|index| {
+            let options = index.options.unwrap_or_default();
+            let name = options.name.unwrap_or_default();
+            let non_unique = !(options.unique.unwrap_or(false) || name == ID_INDEX_NAME);
+            (
+                non_unique,
+                name,
+                index.keys,
+                options.partial_filter_expression,
+            )
+        })
+        .filter(|(non_unique, _, _, _)| !(unique_only && *non_unique))
+        .collect::<Vec<_>>();
+    indexes.sort_by(
+        |(non_unique_a, name_a, _, _), (non_unique_b, name_b, _, _)| {
+            (non_unique_a, name_a).cmp(&(non_unique_b, name_b))
+        },
+    );
+    let mut rows = vec![];
+    for (non_unique, name, keys, filter) in indexes {
+        let index_type = if keys.values().any(|kind| kind.as_str() == Some(HASHED)) {
+            SQL_INDEX_HASHED
+        } else {
+            SQL_INDEX_OTHER
+        };
+        for (position, (field, kind)) in keys.iter().enumerate() {
+            rows.push(vec![
+                Bson::String(database_name.to_string()),
+                // TABLE_SCHEM
+                Bson::Null,
+                Bson::String(collection_name.to_string()),
+                Bson::Int32(non_unique as i32),
+                // INDEX_QUALIFIER
+                Bson::Null,
+                Bson::String(name.clone()),
+                Bson::Int32(index_type),
+                Bson::Int32(position as i32 + 1),
+                Bson::String(field.clone()),
+                asc_or_desc(kind),
+                // CARDINALITY and PAGES
+                Bson::Null,
+                Bson::Null,
+                filter.as_ref().map_or(Bson::Null, |filter| {
+                    Bson::String(
+                        Bson::Document(filter.clone())
+                            .into_relaxed_extjson()
+                            .to_string(),
+                    )
+                }),
+            ]);
+        }
+    }
+    rows
+}
+
+// Return the ASC_OR_DESC value of an index key of the given kind.
+fn asc_or_desc(kind: &Bson) -> Bson {
+    let direction = match kind {
+        Bson::Int32(direction) => *direction as f64,
+        Bson::Int64(direction) => *direction as f64,
+        Bson::Double(direction) => *direction,
+        _ => return Bson::Null,
+    };
+    if direction < 0.0 {
+        Bson::String("D".to_string())
+    } else {
+        Bson::String("A".to_string())
+    }
+}
@@ -1,11 +1,43 @@
-use crate::err::Result;
+use crate::conn::MongoConnection;
+use crate::err::{Error, Result};
+use crate::query::MongoColMetadata;
 use bson::Bson;
+use std::fmt::Debug;
 
-pub trait MongoStatement {
+pub trait MongoStatement: Debug {
     // Move the cursor to the next item.
     // Return true if moving was successful, false otherwise.
     fn next(&mut self) -> Result<bool>;
     // Get the BSON value for the cell at the given colIndex on the current row.
     // Fails if the first row has not been retrieved (next must be called at least once before getValue).
     fn get_value(&self, col_index: u16) -> Result<Option<&Bson>>;
+    // Return the metadata of all the columns of the result set, in order.
+    fn get_resultset_metadata(&self) -> &[MongoColMetadata];
+    // Open a new cursor on the result set of a prepared statement, whose cursor is closed.
+    // Statements whose result set is computed by the driver are never prepared, and always have
+    // their result set.
+    fn open_cursor(
+        &mut self,
+        _client: &MongoConnection,
+        _query_timeout: Option<i32>,
+    ) -> Result<()> {
+        Ok(())
+    }
+    // Close the cursor on the result set, keeping the result set metadata. Statements whose
+    // result set is computed by the driver have no cursor to close.
+    fn close_cursor(&mut self) {}
+
+    // Return the number of fields/columns in the resultset
+    fn _get_col_count(&self) -> u32 {
+        self.get_resultset_metadata().len() as u32
+    }
+
+    // Get the metadata for the column with the given index.
+    // Column indexes start at 1.
+    fn _get_col_metadata(&self, col_index: u16) -> Result<&MongoColMetadata> {
+        col_index
+            .checked_sub(1)
+            .and_then(|i| self.get_resultset_metadata().get(i as usize))
+            .ok_or(Error::ColIndexOutOfBounds(col_index))
+    }
 }
@@ -0,0 +1,38 @@
+use crate::bson_type_info::BsonTypeInfo;
+use crate::collections::{TABLE, VIEW};
+use crate::query::fixed_resultset_metadata;
+use crate::rows::MongoRows;
+use bson::Bson;
+
+// Builds the MongoRows statements listing the table types.
+pub struct MongoTableTypes;
+
+// Statement for SQLTables("", "", "", SQL_ALL_TABLE_TYPES).
+impl MongoTableTypes {
+    // Create a new MongoStatement to list the table types: TABLE for collections and VIEW for
+    // views.
+    // All columns except the TABLE_TYPE column contain NULLs.
+    pub fn list_table_types() -> MongoRows {
+        MongoRows::new(
+            [TABLE, VIEW]
+                .iter()
+                .map(|table_type| {
+                    vec![
+                        Bson::Null,
+                        Bson::Null,
+                        Bson::Null,
+                        Bson::String(table_type.to_string()),
+                        Bson::Null,
+                    ]
+                })
+                .collect(),
+            fixed_resultset_metadata(&[
+                ("TABLE_CAT", BsonTypeInfo::STRING, true),
+                ("TABLE_SCHEM", BsonTypeInfo::STRING, true),
+                ("TABLE_NAME", BsonTypeInfo::STRING, true),
+                ("TABLE_TYPE", BsonTypeInfo::STRING, false),
+                ("REMARKS", BsonTypeInfo::STRING, true),
+            ]),
+        )
+    }
+}
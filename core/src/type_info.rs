@@ -0,0 +1,112 @@
+use crate::bson_type_info::BsonTypeInfo;
+use crate::query::fixed_resultset_metadata;
+use crate::rows::MongoRows;
+use crate::util::{opt_int, opt_str};
+use bson::Bson;
+use odbc_sys::{Nullability, SqlDataType};
+
+// The SQL_ALL_TYPES value of the SQLGetTypeInfo DataType argument.
+pub const SQL_ALL_TYPES: SqlDataType = SqlDataType(0);
+
+// Builds the MongoRows statements listing the types supported by the driver.
+pub struct MongoTypesInfo;
+
+// Statement related to a SQLGetTypeInfo call.
+// The Resultset columns are hard-coded and follow the ODBC resultset for SQLGetTypeInfo :
+// TYPE_NAME, DATA_TYPE, COLUMN_SIZE, LITERAL_PREFIX, LITERAL_SUFFIX, CREATE_PARAMS, NULLABLE,
+// CASE_SENSITIVE, SEARCHABLE, UNSIGNED_ATTRIBUTE, FIXED_PREC_SCALE, AUTO_UNIQUE_VALUE,
+// LOCAL_TYPE_NAME, MINIMUM_SCALE, MAXIMUM_SCALE, SQL_DATA_TYPE, SQL_DATETIME_SUB,
+// NUM_PREC_RADIX, INTERVAL_PRECISION.
+impl MongoTypesInfo {
+    // Create a new MongoStatement listing the BSON types mapped to the given SQL type, or all the
+    // BSON types for SQL_ALL_TYPES. The types are ordered by SQL type and then by how closely
+    // they map to it.
+    pub fn list_types_info(sql_type: SqlDataType) -> MongoRows {
+        // ODBC 2.x applications use the ODBC 2.x code for timestamps.
+        let sql_type = match sql_type {
+            SqlDataType::EXT_TIMESTAMP => SqlDataType::TIMESTAMP,
+            _ => sql_type,
+        };
+        let mut types: Vec<BsonTypeInfo> = BsonTypeInfo::ALL
+            .iter()
+            .filter(|type_info| sql_type == SQL_ALL_TYPES || type_info.sql_type == sql_type)
+            .copied()
+            .collect();
+        // The sort is stable, so types mapped to the same SQL type keep their relative order.
+        types.sort_by_key(|type_info| type_info.sql_type.0);
+        MongoRows::new(
+            types.iter().map(type_info_row).collect(),
+            fixed_resultset_metadata(&[
+                ("TYPE_NAME", BsonTypeInfo::STRING, false),
+                ("DATA_TYPE", BsonTypeInfo::SMALLINT, false),
+                ("COLUMN_SIZE", BsonTypeInfo::INT, true),
+                ("LITERAL_PREFIX", BsonTypeInfo::STRING, true),
+                ("LITERAL_SUFFIX", BsonTypeInfo::STRING, true),
+                ("CREATE_PARAMS", BsonTypeInfo::STRING, true),
+                ("NULLABLE", BsonTypeInfo::SMALLINT, false),
+                ("CASE_SENSITIVE", BsonTypeInfo::SMALLINT, false),
+                ("SEARCHABLE", BsonTypeInfo::SMALLINT, false),
+                ("UNSIGNED_ATTRIBUTE", BsonTypeInfo::SMALLINT, true),
+                ("FIXED_PREC_SCALE", BsonTypeInfo::SMALLINT, false),
+                ("AUTO_UNIQUE_VALUE", BsonTypeInfo::SMALLINT, true),
+                ("LOCAL_TYPE_NAME", BsonTypeInfo::STRING, true),
+                ("MINIMUM_SCALE", BsonTypeInfo::SMALLINT, true),
+                ("MAXIMUM_SCALE", BsonTypeInfo::SMALLINT, true),
+                ("SQL_DATA_TYPE", BsonTypeInfo::SMALLINT, false),
+                ("SQL_DATETIME_SUB", BsonTypeInfo::SMALLINT, true),
+                ("NUM_PREC_RADIX", BsonTypeInfo::INT, true),
+                ("INTERVAL_PRECISION", BsonTypeInfo::SMALLINT, true),
+            ]),
+        )
+    }
+}
+
+// Build the SQLGetTypeInfo row describing the given type.
+fn type_info_row(type_info: &BsonTypeInfo) -> Vec<Bson> {
+    let (sql_data_type, datetime_sub) = type_info.verbose_type();
+    let (min_scale, max_scale) = match type_info.sql_type {
+        // The scale of a decimal depends on the value.
+        SqlDataType::DECIMAL => (
+            Bson::Int32(0),
+            Bson::Int32(type_info.column_size.unwrap_or_default() as i32),
+        ),
+        _ => (opt_int(type_info.scale), opt_int(type_info.scale)),
+    };
+    let numeric_attribute = |value: bool| {
+        if type_info.is_numeric() {
+            Bson::Int32(value as i32)
+        } else {
+            Bson::Null
+        }
+    };
+    vec![
+        Bson::String(type_info.type_name.to_string()),
+        Bson::Int32(type_info.sql_type.0 as i32),
+        opt_int(type_info.column_size),
+        opt_str(type_info.literal_prefix),
+        opt_str(type_info.literal_suffix),
+        // CREATE_PARAMS
+        Bson::Null,
+        Bson::Int32(Nullability::NULLABLE.0 as i32),
+        Bson::Int32(type_info.is_case_sensitive as i32),
+        Bson::Int32(type_info.searchable as i32),
+        numeric_attribute(type_info.is_unsigned),
+        // FIXED_PREC_SCALE
+        Bson::Int32(0),
+        // AUTO_UNIQUE_VALUE
+        numeric_attribute(false),
+        // LOCAL_TYPE_NAME
+        Bson::Null,
+        min_scale,
+        max_scale,
+        Bson::Int32(sql_data_type.0 as i32),
+        opt_int(datetime_sub.map(|datetime_sub| datetime_sub as u16)),
+        if type_info.is_numeric() {
+            Bson::Int32(10)
+        } else {
+            Bson::Null
+        },
+        // INTERVAL_PRECISION
+        Bson::Null,
+    ]
+}
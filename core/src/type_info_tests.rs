@@ -0,0 +1,108 @@
+use crate::rows::MongoRows;
+use crate::stmt::MongoStatement;
+use crate::type_info::{MongoTypesInfo, SQL_ALL_TYPES};
+use bson::Bson;
+use odbc_sys::SqlDataType;
+
+// Collect the TYPE_NAME and DATA_TYPE of all the rows of the given statement.
+fn type_names(mut types_info: MongoRows) -> Vec<(String, i32)> {
+    let mut names = vec![];
+    while types_info.next().unwrap() {
+        match (
+            types_info.get_value(1).unwrap(),
+            types_info.get_value(2).unwrap(),
+        ) {
+            (Some(Bson::String(name)), Some(Bson::Int32(data_type))) => {
+                names.push((name.clone(), *data_type))
+            }
+            row => panic!("unexpected row {:?}", row),
+        }
+    }
+    names
+}
+
+#[test]
+fn all_types() {
+    let names = type_names(MongoTypesInfo::list_types_info(SQL_ALL_TYPES));
+    assert_eq!(22, names.len());
+    // The types are ordered by DATA_TYPE.
+    assert!(names.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert_eq!(("bool".to_string(), -7), names[0]);
+}
+
+#[test]
+fn filtered_types() {
+    assert_eq!(
+        vec![("int".to_string(), 4)],
+        type_names(MongoTypesInfo::list_types_info(SqlDataType::INTEGER))
+    );
+    assert_eq!(
+        vec![("date".to_string(), 93), ("timestamp".to_string(), 93)],
+        type_names(MongoTypesInfo::list_types_info(SqlDataType::EXT_TIMESTAMP))
+    );
+    assert!(type_names(MongoTypesInfo::list_types_info(SqlDataType::EXT_GUID)).is_empty());
+}
+
+#[test]
+fn type_info_row() {
+    let mut types_info = MongoTypesInfo::list_types_info(SqlDataType::VARCHAR);
+    assert!(types_info.get_value(1).is_err());
+    assert!(types_info.next().unwrap());
+    let row: Vec<Option<&Bson>> = (1..=19)
+        .map(|col| types_info.get_value(col).unwrap())
+        .collect();
+    assert_eq!(
+        vec![
+            Some(&Bson::String("string".to_string())),
+            Some(&Bson::Int32(12)),
+            Some(&Bson::Int32(16 * 1024 * 1024)),
+            Some(&Bson::String("'".to_string())),
+            Some(&Bson::String("'".to_string())),
+            Some(&Bson::Null),
+            Some(&Bson::Int32(1)),
+            Some(&Bson::Int32(1)),
+            Some(&Bson::Int32(3)),
+            Some(&Bson::Null),
+            Some(&Bson::Int32(0)),
+            Some(&Bson::Null),
+            Some(&Bson::Null),
+            Some(&Bson::Null),
+            Some(&Bson::Null),
+            Some(&Bson::Int32(12)),
+            Some(&Bson::Null),
+            Some(&Bson::Null),
+            Some(&Bson::Null),
+        ],
+        row
+    );
+    assert!(types_info.get_value(20).is_err());
+}
+
+#[test]
+fn resultset_metadata() {
+    let types_info = MongoTypesInfo::list_types_info(SQL_ALL_TYPES);
+    assert_eq!(19, types_info._get_col_count());
+    let data_type = types_info._get_col_metadata(2).unwrap();
+    assert_eq!("DATA_TYPE", data_type.col_name);
+    assert_eq!(SqlDataType::SMALLINT, data_type.sql_type);
+    assert!(!data_type.is_nullable);
+    let interval_precision = types_info._get_col_metadata(19).unwrap();
+    assert_eq!("INTERVAL_PRECISION", interval_precision.col_name);
+    assert!(interval_precision.is_nullable);
+    assert!(types_info._get_col_metadata(0).is_err());
+    assert!(types_info._get_col_metadata(20).is_err());
+}
+
+#[test]
+fn approximate_numeric_scale() {
+    // The scale of a double is not applicable, so MINIMUM_SCALE and MAXIMUM_SCALE are NULL.
+    let mut types_info = MongoTypesInfo::list_types_info(SqlDataType::DOUBLE);
+    assert!(types_info.next().unwrap());
+    assert_eq!(
+        Some(&Bson::String("double".to_string())),
+        types_info.get_value(1).unwrap()
+    );
+    assert_eq!(Some(&Bson::Null), types_info.get_value(14).unwrap());
+    assert_eq!(Some(&Bson::Null), types_info.get_value(15).unwrap());
+    assert_eq!(Some(&Bson::Int32(10)), types_info.get_value(18).unwrap());
+}
@@ -0,0 +1,84 @@
+use bson::Bson;
+
+// The escape character of the search patterns, as reported by SQL_SEARCH_PATTERN_ESCAPE.
+pub const SEARCH_PATTERN_ESCAPE: char = '\\';
+
+// Check whether the given name matches the given ODBC search pattern. In a search pattern, '%'
+// matches any sequence of zero or more characters and '_' matches any single character. The
+// escape character makes the following '%', '_' or escape character match itself.
+pub(crate) fn is_match(name: &str, pattern: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let pattern = parse_pattern(pattern);
+    // matches[i] is true if the first i characters of the name match the pattern elements
+    // processed so far.
+    let mut matches = vec![false; name.len() + 1];
+    matches[0] = true;
+    for element in pattern {
+        let mut next = vec![false; name.len() + 1];
+        match element {
+            PatternElement::AnySequence => {
+                let mut matched = false;
+                for (next, matches) in next.iter_mut().zip(&matches) {
+                    matched = matched || *matches;
+                    *next = matched;
+                }
+            }
+            PatternElement::AnyChar => next[1..].copy_from_slice(&matches[..name.len()]),
+            PatternElement::Char(c) => {
+                for (i, name_char) in name.iter().enumerate() {
+                    next[i + 1] = matches[i] && *name_char == c;
+                }
+            }
+        }
+        matches = next;
+    }
+    matches[name.len()]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PatternElement {
+    // '%'
+    AnySequence,
+    // '_'
+    AnyChar,
+    Char(char),
+}
+
+// Split the search pattern into its elements, resolving the escaped characters. A trailing
+// escape character matches itself.
+fn parse_pattern(pattern: &str) -> Vec<PatternElement> {
+    let mut elements = vec![];
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        elements.push(match c {
+            '%' => PatternElement::AnySequence,
+            '_' => PatternElement::AnyChar,
+            SEARCH_PATTERN_ESCAPE => PatternElement::Char(chars.next().unwrap_or(c)),
+            c => PatternElement::Char(c),
+        });
+    }
+    elements
+}
+
+// Convert the optional integer to a BSON value, null if there is no value.
+pub(crate) fn opt_int(value: Option<impl Into<u32>>) -> Bson {
+    value.map_or(Bson::Null, |value| Bson::Int32(value.into() as i32))
+}
+
+// Convert the optional string to a BSON value, null if there is no value.
+pub(crate) fn opt_str(value: Option<&str>) -> Bson {
+    value.map_or(Bson::Null, |value| Bson::String(value.to_string()))
+}
+
+// Escape the search pattern characters of the given name, so that it can be used as a search
+// pattern matching only itself.
+pub fn escape_pattern(name: &str) -> String {
+    let mut pattern = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '%' | '_' | SEARCH_PATTERN_ESCAPE) {
+            pattern.push(SEARCH_PATTERN_ESCAPE);
+        }
+        pattern.push(c);
+    }
+    pattern
+}
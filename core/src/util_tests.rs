@@ -0,0 +1,36 @@
+use crate::util::{escape_pattern, is_match};
+
+#[test]
+fn wildcards() {
+    assert!(is_match("foo", "%"));
+    assert!(is_match("", "%"));
+    assert!(is_match("foo", "f%"));
+    assert!(is_match("foo", "%o"));
+    assert!(is_match("foo", "f%o%"));
+    assert!(!is_match("foo", "b%"));
+    assert!(is_match("foo", "f_o"));
+    assert!(!is_match("fo", "f_o"));
+    assert!(is_match("foo", "___"));
+    assert!(!is_match("foo", "__"));
+    assert!(is_match("", ""));
+    assert!(!is_match("foo", ""));
+    assert!(!is_match("Foo", "foo"));
+}
+
+#[test]
+fn escaped_characters() {
+    assert!(is_match("foo_bar", "foo\\_bar"));
+    assert!(!is_match("fooxbar", "foo\\_bar"));
+    assert!(is_match("100%", "100\\%"));
+    assert!(!is_match("1000", "100\\%"));
+    assert!(is_match("a\\b", "a\\\\b"));
+    assert!(is_match("a\\", "a\\"));
+}
+
+#[test]
+fn escape() {
+    for name in ["foo", "foo_bar", "100%", "a\\b", "%_\\"] {
+        assert!(is_match(name, &escape_pattern(name)));
+    }
+    assert!(!is_match("fooxbar", &escape_pattern("foo_bar")));
+}
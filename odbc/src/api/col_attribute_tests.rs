@@ -0,0 +1,302 @@
+use crate::{
+    api::test_fixtures::{free_handles, new_statement, sql_state},
+    handles::definitions::{MongoHandle, StatementState},
+    SQLColAttribute, SQLColAttributeW, SQLDescribeCol, SQLDescribeColW, SQLFetch, SQLGetData,
+    SQLGetTypeInfo, SQLNumResultCols,
+};
+use mongo_odbc_core::Searchable;
+use odbc_sys::{
+    CDataType, Desc, Len, Nullability, Pointer, SmallInt, SqlDataType, SqlReturn, ULen, WChar,
+};
+use std::ptr::null_mut;
+
+// Allocate a statement holding the SQLGetTypeInfo result set for all the types.
+fn type_info_statement() -> *mut MongoHandle {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType(0))
+    );
+    stmt_handle
+}
+
+#[test]
+fn num_result_cols() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let mut column_count: SmallInt = 0;
+    // There is no result set before the statement is executed.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLNumResultCols(stmt_handle as *mut _, &mut column_count)
+    );
+    assert_eq!("HY010", sql_state(stmt_handle));
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType(0))
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLNumResultCols(stmt_handle as *mut _, &mut column_count)
+    );
+    assert_eq!(19, column_count);
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn describe_col() {
+    let stmt_handle = type_info_statement();
+    // Describe the column with a name buffer of buffer_length characters, returning the name
+    // written to the buffer and the other outputs.
+    let describe_col = |col_number, buffer_length: SmallInt| {
+        let mut name = [0 as WChar; 16];
+        let mut name_length: SmallInt = 0;
+        let mut data_type = SqlDataType::UNKNOWN_TYPE;
+        let mut col_size: ULen = 0;
+        let mut decimal_digits: SmallInt = -1;
+        let mut nullable = Nullability::UNKNOWN;
+        let sql_return = SQLDescribeColW(
+            stmt_handle as *mut _,
+            col_number,
+            name.as_mut_ptr(),
+            buffer_length,
+            &mut name_length,
+            &mut data_type,
+            &mut col_size,
+            &mut decimal_digits,
+            &mut nullable,
+        );
+        let name = String::from_utf16_lossy(&name);
+        (
+            sql_return,
+            name.trim_end_matches('\0').to_string(),
+            name_length,
+            data_type,
+            col_size,
+            decimal_digits,
+            nullable,
+        )
+    };
+
+    assert_eq!(
+        (
+            SqlReturn::SUCCESS,
+            "DATA_TYPE".to_string(),
+            9,
+            SqlDataType::SMALLINT,
+            5,
+            0,
+            Nullability::NO_NULLS
+        ),
+        describe_col(2, 16)
+    );
+    assert_eq!(
+        (
+            SqlReturn::SUCCESS,
+            "LITERAL_PREFIX".to_string(),
+            14,
+            SqlDataType::VARCHAR,
+            16 * 1024 * 1024,
+            0,
+            Nullability::NULLABLE
+        ),
+        describe_col(4, 16)
+    );
+
+    // The name is truncated to the buffer, and its length is the full length.
+    let (sql_return, name, name_length, ..) = describe_col(1, 5);
+    assert_eq!(
+        (SqlReturn::SUCCESS_WITH_INFO, "TYPE".to_string(), 9),
+        (sql_return, name, name_length)
+    );
+    assert_eq!("01004", sql_state(stmt_handle));
+
+    // Bookmarks are not supported.
+    assert_eq!(SqlReturn::ERROR, describe_col(0, 16).0);
+    assert_eq!("07009", sql_state(stmt_handle));
+    assert_eq!(SqlReturn::ERROR, describe_col(20, 16).0);
+    assert_eq!("07009", sql_state(stmt_handle));
+
+    let mut name_length: SmallInt = 0;
+    let mut narrow_name = [0u8; 16];
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLDescribeCol(
+            stmt_handle as *mut _,
+            3,
+            narrow_name.as_mut_ptr(),
+            16,
+            &mut name_length,
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            null_mut(),
+        )
+    );
+    assert_eq!(b"COLUMN_SIZE\0", &narrow_name[..12]);
+    assert_eq!(11, name_length);
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn col_attribute() {
+    let stmt_handle = type_info_statement();
+    let numeric_attribute = |col_number, field_identifier| {
+        let mut value: Len = -1;
+        let sql_return = SQLColAttributeW(
+            stmt_handle as *mut _,
+            col_number,
+            field_identifier,
+            null_mut(),
+            0,
+            null_mut(),
+            &mut value,
+        );
+        (sql_return, value)
+    };
+    let string_attribute = |col_number, field_identifier, buffer_length| {
+        let mut value = [0 as WChar; 16];
+        let mut string_length: SmallInt = 0;
+        let sql_return = SQLColAttributeW(
+            stmt_handle as *mut _,
+            col_number,
+            field_identifier,
+            value.as_mut_ptr() as Pointer,
+            buffer_length,
+            &mut string_length,
+            null_mut(),
+        );
+        let value = String::from_utf16_lossy(&value);
+        (
+            sql_return,
+            value.trim_end_matches('\0').to_string(),
+            string_length,
+        )
+    };
+
+    // SQL_DESC_COUNT ignores the column number.
+    assert_eq!((SqlReturn::SUCCESS, 19), numeric_attribute(0, Desc::Count));
+    assert_eq!(
+        (SqlReturn::SUCCESS, SqlDataType::SMALLINT.0 as Len),
+        numeric_attribute(2, Desc::ConciseType)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 5),
+        numeric_attribute(2, Desc::Precision)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 2),
+        numeric_attribute(2, Desc::OctetLength)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 6),
+        numeric_attribute(2, Desc::DisplaySize)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_attribute(2, Desc::Unsigned)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 10),
+        numeric_attribute(2, Desc::NumPrecRadix)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_attribute(1, Desc::NumPrecRadix)
+    );
+    // The numeric types can't be used with LIKE, the character types can.
+    assert_eq!(
+        (SqlReturn::SUCCESS, Searchable::Basic as Len),
+        numeric_attribute(2, Desc::Searchable)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, Searchable::All as Len),
+        numeric_attribute(1, Desc::Searchable)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 1),
+        numeric_attribute(1, Desc::CaseSensitive)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_attribute(1, Desc::Updatable)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, Nullability::NO_NULLS.0 as Len),
+        numeric_attribute(1, Desc::Nullable)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, Nullability::NULLABLE.0 as Len),
+        numeric_attribute(3, Desc::Nullable)
+    );
+
+    // The string lengths are in bytes.
+    assert_eq!(
+        (SqlReturn::SUCCESS, "DATA_TYPE".to_string(), 18),
+        string_attribute(2, Desc::Name, 32)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, "DATA_TYPE".to_string(), 18),
+        string_attribute(2, Desc::Label, 32)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, "int".to_string(), 6),
+        string_attribute(2, Desc::TypeName, 32)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, "'".to_string(), 2),
+        string_attribute(1, Desc::LiteralPrefix, 32)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS_WITH_INFO, "DAT".to_string(), 18),
+        string_attribute(2, Desc::Name, 8)
+    );
+    assert_eq!("01004", sql_state(stmt_handle));
+
+    // Only the implementation row descriptor fields are column attributes.
+    assert_eq!(SqlReturn::ERROR, numeric_attribute(1, Desc::ArraySize).0);
+    assert_eq!("HY091", sql_state(stmt_handle));
+    assert_eq!(SqlReturn::ERROR, numeric_attribute(0, Desc::Name).0);
+    assert_eq!("07009", sql_state(stmt_handle));
+
+    let mut narrow_value = [0u8; 16];
+    let mut string_length: SmallInt = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLColAttribute(
+            stmt_handle as *mut _,
+            1,
+            Desc::BaseColumnName,
+            narrow_value.as_mut_ptr() as Pointer,
+            16,
+            &mut string_length,
+            null_mut(),
+        )
+    );
+    assert_eq!(b"TYPE_NAME\0", &narrow_value[..10]);
+    assert_eq!(9, string_length);
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn default_c_type_from_metadata() {
+    let stmt_handle = type_info_statement();
+    let mut data_type = [0i16; 2];
+    let mut indicator: Len = 0;
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt_handle as *mut _));
+    // DATA_TYPE is a SMALLINT column, so its default C type is SQL_C_SSHORT.
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetData(
+            stmt_handle as *mut _,
+            2,
+            CDataType::Default,
+            data_type.as_mut_ptr() as Pointer,
+            0,
+            &mut indicator,
+        )
+    );
+    assert_eq!([SqlDataType::EXT_BIT.0, 0], data_type);
+    assert_eq!(2, indicator);
+    free_handles(&[stmt_handle]);
+}
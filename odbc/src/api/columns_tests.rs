@@ -0,0 +1,55 @@
+use crate::{
+    api::test_fixtures::{free_handles, new_statement, sql_state, statement},
+    handles::definitions::StatementState,
+    SQLColumnsW,
+};
+use odbc_sys::{SqlReturn, NTS};
+use std::ptr::{null, null_mut};
+
+#[test]
+fn columns_without_connection() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let table_name: Vec<u16> = "foo%\0".encode_utf16().collect();
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLColumnsW(
+            stmt_handle as *mut _,
+            null(),
+            0,
+            null(),
+            0,
+            table_name.as_ptr(),
+            NTS as i16,
+            null(),
+            0,
+        )
+    );
+    assert_eq!("08003", sql_state(stmt_handle));
+    assert!(statement(stmt_handle)
+        .read()
+        .unwrap()
+        .mongo_statement
+        .is_none());
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn columns_with_open_cursor() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::ExecutedHasResultSet);
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLColumnsW(
+            stmt_handle as *mut _,
+            null(),
+            0,
+            null(),
+            0,
+            null(),
+            0,
+            null(),
+            0
+        )
+    );
+    assert_eq!("24000", sql_state(stmt_handle));
+    free_handles(&[stmt_handle]);
+}
@@ -0,0 +1,510 @@
+use crate::api::errors::ODBCError;
+use bson::{spec::BinarySubtype, Bson};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use mongo_odbc_core::BsonTypeInfo;
+use odbc_sys::{
+    CDataType, Date, Guid, Len, Pointer, SmallInt, SqlDataType, Time, Timestamp, NULL_DATA,
+};
+use std::{
+    mem::{size_of, size_of_val},
+    ptr::copy_nonoverlapping,
+};
+
+pub type Result<T> = std::result::Result<T, ODBCError>;
+
+// The formats accepted when converting a string to a date, a time or a timestamp.
+const TIMESTAMP_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.fZ"];
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+// The format used when converting a date to a string.
+const TIMESTAMP_OUTPUT_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+// The C types values can be converted to.
+const SUPPORTED_C_TYPES: [CDataType; 22] = [
+    CDataType::Char,
+    CDataType::WChar,
+    CDataType::Binary,
+    CDataType::Bit,
+    CDataType::Double,
+    CDataType::Float,
+    CDataType::STinyInt,
+    CDataType::UTinyInt,
+    CDataType::SShort,
+    CDataType::UShort,
+    CDataType::SLong,
+    CDataType::ULong,
+    CDataType::SBigInt,
+    CDataType::UBigInt,
+    CDataType::Date,
+    CDataType::TypeDate,
+    CDataType::Time,
+    CDataType::TypeTime,
+    CDataType::TimeStamp,
+    CDataType::TypeTimestamp,
+    CDataType::Guid,
+    CDataType::Default,
+];
+
+/// is_supported_c_type returns whether values can be converted to the given C type.
+pub fn is_supported_c_type(target_type: CDataType) -> bool {
+    SUPPORTED_C_TYPES.contains(&target_type)
+}
+
+/// c_type_from_code returns the supported C type whose SQL_C_* code is [`code`], as found in the
+/// type fields of descriptors.
+pub fn c_type_from_code(code: SmallInt) -> Option<CDataType> {
+    SUPPORTED_C_TYPES
+        .into_iter()
+        .find(|c_type| *c_type as SmallInt == code)
+}
+
+/// c_type_size returns the size in bytes of the values of the given fixed length C type, or None
+/// for variable length types.
+pub fn c_type_size(target_type: CDataType) -> Option<usize> {
+    match target_type {
+        CDataType::Bit | CDataType::STinyInt | CDataType::UTinyInt => Some(size_of::<u8>()),
+        CDataType::SShort | CDataType::UShort => Some(size_of::<u16>()),
+        CDataType::SLong | CDataType::ULong => Some(size_of::<u32>()),
+        CDataType::SBigInt | CDataType::UBigInt => Some(size_of::<u64>()),
+        CDataType::Float => Some(size_of::<f32>()),
+        CDataType::Double => Some(size_of::<f64>()),
+        CDataType::Date | CDataType::TypeDate => Some(size_of::<Date>()),
+        CDataType::Time | CDataType::TypeTime => Some(size_of::<Time>()),
+        CDataType::TimeStamp | CDataType::TypeTimestamp => Some(size_of::<Timestamp>()),
+        CDataType::Guid => Some(size_of::<Guid>()),
+        _ => None,
+    }
+}
+
+/// format_cell converts [`value`] to [`target_type`] and writes it to the [`target_buffer`] of
+/// [`buffer_length`] bytes. The length of the value, or SQL_NULL_DATA for null values, is written
+/// to [`length_or_indicator`].
+/// Character and binary values can be retrieved in several chunks: [`offset`] is the number of
+/// characters or bytes of the value already returned by previous calls, and only the remaining
+/// part of the value is written and reported in the length.
+/// Callers resolve SQL_C_DEFAULT from the column metadata with [`default_c_type`]; a value passed
+/// with SQL_C_DEFAULT is converted to the default C type of its own BSON type.
+/// Returns the offset to resume from if the remaining part of the value didn't fit in the buffer,
+/// and the warning raised by the conversion if the value had to be truncated.
+pub fn format_cell(
+    value: Option<&Bson>,
+    target_type: CDataType,
+    target_buffer: Pointer,
+    buffer_length: Len,
+    length_or_indicator: *mut Len,
+    offset: usize,
+) -> Result<(Option<usize>, Option<ODBCError>)> {
+    let value = match value {
+        None | Some(Bson::Null) | Some(Bson::Undefined) => {
+            if length_or_indicator.is_null() {
+                return Err(ODBCError::IndicatorVariableRequiredButNotSupplied);
+            }
+            unsafe { *length_or_indicator = NULL_DATA };
+            return Ok((None, None));
+        }
+        Some(value) => value,
+    };
+    let warning = match target_type {
+        CDataType::Default => {
+            return format_cell(
+                Some(value),
+                default_c_type(
+                    BsonTypeInfo::from_type_name(bson_type_name(value))
+                        .map_or(SqlDataType::VARCHAR, |type_info| type_info.sql_type),
+                ),
+                target_buffer,
+                buffer_length,
+                length_or_indicator,
+                offset,
+            )
+        }
+        CDataType::Char => {
+            return Ok(set_output_data(
+                to_string(value).as_bytes(),
+                target_buffer as *mut u8,
+                buffer_length,
+                true,
+                length_or_indicator,
+                offset,
+            ))
+        }
+        CDataType::WChar => {
+            return Ok(set_output_data(
+                &to_string(value).encode_utf16().collect::<Vec<u16>>(),
+                target_buffer as *mut u16,
+                buffer_length,
+                true,
+                length_or_indicator,
+                offset,
+            ))
+        }
+        CDataType::Binary => {
+            return Ok(set_output_data(
+                &to_bytes(value),
+                target_buffer as *mut u8,
+                buffer_length,
+                false,
+                length_or_indicator,
+                offset,
+            ))
+        }
+        CDataType::Double => {
+            set_fixed_data(
+                to_f64(value, "SQL_C_DOUBLE")?,
+                target_buffer,
+                length_or_indicator,
+            );
+            None
+        }
+        CDataType::Float => {
+            let double = to_f64(value, "SQL_C_FLOAT")?;
+            if double.is_finite() && double.abs() > f32::MAX as f64 {
+                return Err(ODBCError::NumericValueOutOfRange(double.to_string()));
+            }
+            set_fixed_data(double as f32, target_buffer, length_or_indicator);
+            None
+        }
+        CDataType::Bit => {
+            let (bit, warning) = to_bit(value)?;
+            set_fixed_data(bit, target_buffer, length_or_indicator);
+            warning
+        }
+        CDataType::STinyInt => set_integer::<i8>(value, target_buffer, length_or_indicator)?,
+        CDataType::UTinyInt => set_integer::<u8>(value, target_buffer, length_or_indicator)?,
+        CDataType::SShort => set_integer::<i16>(value, target_buffer, length_or_indicator)?,
+        CDataType::UShort => set_integer::<u16>(value, target_buffer, length_or_indicator)?,
+        CDataType::SLong => set_integer::<i32>(value, target_buffer, length_or_indicator)?,
+        CDataType::ULong => set_integer::<u32>(value, target_buffer, length_or_indicator)?,
+        CDataType::SBigInt => set_integer::<i64>(value, target_buffer, length_or_indicator)?,
+        CDataType::UBigInt => set_integer::<u64>(value, target_buffer, length_or_indicator)?,
+        CDataType::Date | CDataType::TypeDate => {
+            let datetime = to_datetime(value, "SQL_C_TYPE_DATE")?;
+            set_fixed_data(
+                Date {
+                    year: datetime.year() as i16,
+                    month: datetime.month() as u16,
+                    day: datetime.day() as u16,
+                },
+                target_buffer,
+                length_or_indicator,
+            );
+            // Converting a timestamp with a time part to a date truncates it.
+            (datetime.num_seconds_from_midnight() != 0 || datetime.nanosecond() != 0)
+                .then(|| ODBCError::FractionalTruncation(datetime.to_string()))
+        }
+        CDataType::Time | CDataType::TypeTime => {
+            let time = match value {
+                Bson::String(s) => NaiveTime::parse_from_str(s.trim(), TIME_FORMAT)
+                    .or_else(|_| to_datetime(value, "SQL_C_TYPE_TIME").map(|dt| dt.time()))?,
+                _ => to_datetime(value, "SQL_C_TYPE_TIME")?.time(),
+            };
+            set_fixed_data(
+                Time {
+                    hour: time.hour() as u16,
+                    minute: time.minute() as u16,
+                    second: time.second() as u16,
+                },
+                target_buffer,
+                length_or_indicator,
+            );
+            (time.nanosecond() != 0).then(|| ODBCError::FractionalTruncation(time.to_string()))
+        }
+        CDataType::TimeStamp | CDataType::TypeTimestamp => {
+            let datetime = to_datetime(value, "SQL_C_TYPE_TIMESTAMP")?;
+            set_fixed_data(
+                Timestamp {
+                    year: datetime.year() as i16,
+                    month: datetime.month() as u16,
+                    day: datetime.day() as u16,
+                    hour: datetime.hour() as u16,
+                    minute: datetime.minute() as u16,
+                    second: datetime.second() as u16,
+                    fraction: datetime.nanosecond(),
+                },
+                target_buffer,
+                length_or_indicator,
+            );
+            None
+        }
+        CDataType::Guid => {
+            set_fixed_data(to_guid(value)?, target_buffer, length_or_indicator);
+            None
+        }
+        _ => return Err(ODBCError::InvalidTargetType(target_type)),
+    };
+    // Fixed length values are always returned whole.
+    Ok((None, warning))
+}
+
+/// default_c_type returns the SQL_C_DEFAULT type of a column of the given [`sql_type`].
+pub fn default_c_type(sql_type: SqlDataType) -> CDataType {
+    match sql_type {
+        SqlDataType::DOUBLE => CDataType::Double,
+        SqlDataType::SMALLINT => CDataType::SShort,
+        SqlDataType::INTEGER => CDataType::SLong,
+        SqlDataType::EXT_BIG_INT => CDataType::SBigInt,
+        SqlDataType::EXT_BIT => CDataType::Bit,
+        SqlDataType::EXT_BINARY => CDataType::Binary,
+        SqlDataType::TIMESTAMP => CDataType::TypeTimestamp,
+        _ => CDataType::Char,
+    }
+}
+
+// bson_type_name returns the name of the BSON type of the value, used in error messages.
+fn bson_type_name(value: &Bson) -> &'static str {
+    match value {
+        Bson::Double(_) => "double",
+        Bson::String(_) => "string",
+        Bson::Array(_) => "array",
+        Bson::Document(_) => "object",
+        Bson::Boolean(_) => "bool",
+        Bson::Null => "null",
+        Bson::RegularExpression(_) => "regex",
+        Bson::JavaScriptCode(_) => "javascript",
+        Bson::JavaScriptCodeWithScope(_) => "javascriptWithScope",
+        Bson::Int32(_) => "int",
+        Bson::Int64(_) => "long",
+        Bson::Timestamp(_) => "timestamp",
+        Bson::Binary(_) => "binData",
+        Bson::ObjectId(_) => "objectId",
+        Bson::DateTime(_) => "date",
+        Bson::Symbol(_) => "symbol",
+        Bson::Decimal128(_) => "decimal",
+        Bson::Undefined => "undefined",
+        Bson::MaxKey => "maxKey",
+        Bson::MinKey => "minKey",
+        Bson::DbPointer(_) => "dbPointer",
+    }
+}
+
+// to_string returns the character representation of the value.
+fn to_string(value: &Bson) -> String {
+    match value {
+        Bson::String(s) | Bson::Symbol(s) | Bson::JavaScriptCode(s) => s.clone(),
+        Bson::Double(d) => d.to_string(),
+        Bson::Int32(i) => i.to_string(),
+        Bson::Int64(i) => i.to_string(),
+        Bson::Boolean(b) => (*b as u8).to_string(),
+        Bson::Decimal128(d) => d.to_string(),
+        Bson::ObjectId(oid) => oid.to_hex(),
+        Bson::Binary(binary) => to_hex(&binary.bytes),
+        Bson::DateTime(_) | Bson::Timestamp(_) => match to_datetime(value, "SQL_C_CHAR") {
+            Ok(datetime) => datetime.format(TIMESTAMP_OUTPUT_FORMAT).to_string(),
+            Err(_) => value.clone().into_relaxed_extjson().to_string(),
+        },
+        _ => value.clone().into_relaxed_extjson().to_string(),
+    }
+}
+
+// to_bytes returns the binary representation of the value.
+fn to_bytes(value: &Bson) -> Vec<u8> {
+    match value {
+        Bson::Binary(binary) => binary.bytes.clone(),
+        Bson::ObjectId(oid) => oid.bytes().to_vec(),
+        _ => to_string(value).into_bytes(),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// to_f64 returns the numeric value of the value, for conversions to [`target_type`].
+fn to_f64(value: &Bson, target_type: &'static str) -> Result<f64> {
+    match value {
+        Bson::Double(d) => Ok(*d),
+        Bson::Int32(i) => Ok(*i as f64),
+        Bson::Int64(i) => Ok(*i as f64),
+        Bson::Boolean(b) => Ok(*b as u8 as f64),
+        Bson::Decimal128(d) => d
+            .to_string()
+            .parse()
+            .map_err(|_| ODBCError::InvalidCharacterValue(target_type)),
+        Bson::String(s) => s
+            .trim()
+            .parse()
+            .map_err(|_| ODBCError::InvalidCharacterValue(target_type)),
+        _ => Err(ODBCError::RestrictedDataType(
+            bson_type_name(value),
+            target_type,
+        )),
+    }
+}
+
+// to_bit converts the value to a SQL_C_BIT. Only 0 and 1 are valid bits, the values strictly
+// between 0 and 2 are truncated.
+fn to_bit(value: &Bson) -> Result<(u8, Option<ODBCError>)> {
+    if let Bson::Boolean(b) = value {
+        return Ok((*b as u8, None));
+    }
+    let double = to_f64(value, "SQL_C_BIT")?;
+    if double == 0.0 || double == 1.0 {
+        Ok((double as u8, None))
+    } else if double > 0.0 && double < 2.0 {
+        Ok((
+            double as u8,
+            Some(ODBCError::FractionalTruncation(double.to_string())),
+        ))
+    } else {
+        Err(ODBCError::NumericValueOutOfRange(double.to_string()))
+    }
+}
+
+// set_integer converts the value to the integer type T and writes it to the target buffer.
+// Fractional parts are truncated with a warning, and values which don't fit in T are rejected.
+fn set_integer<T: TryFrom<i128> + Copy>(
+    value: &Bson,
+    target_buffer: Pointer,
+    length_or_indicator: *mut Len,
+) -> Result<Option<ODBCError>> {
+    let (integer, warning) = match value {
+        Bson::Int32(i) => (*i as i128, None),
+        Bson::Int64(i) => (*i as i128, None),
+        Bson::Boolean(b) => (*b as i128, None),
+        // Integer strings are parsed exactly, others go through their floating point value.
+        _ => match value.as_str().and_then(|s| s.trim().parse::<i128>().ok()) {
+            Some(i) => (i, None),
+            None => {
+                let double = to_f64(value, "an integer type")?;
+                if !double.is_finite() || double.abs() >= i128::MAX as f64 {
+                    return Err(ODBCError::NumericValueOutOfRange(double.to_string()));
+                }
+                (
+                    double.trunc() as i128,
+                    (double.fract() != 0.0)
+                        .then(|| ODBCError::FractionalTruncation(double.to_string())),
+                )
+            }
+        },
+    };
+    let integer =
+        T::try_from(integer).map_err(|_| ODBCError::NumericValueOutOfRange(integer.to_string()))?;
+    set_fixed_data(integer, target_buffer, length_or_indicator);
+    Ok(warning)
+}
+
+// to_datetime converts the value to a date and time, for conversions to [`target_type`].
+fn to_datetime(value: &Bson, target_type: &'static str) -> Result<NaiveDateTime> {
+    let out_of_range = || ODBCError::NumericValueOutOfRange(value.to_string());
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .ok_or_else(out_of_range)?;
+    match value {
+        Bson::DateTime(datetime) => epoch
+            .checked_add_signed(Duration::milliseconds(datetime.timestamp_millis()))
+            .ok_or_else(out_of_range),
+        Bson::Timestamp(timestamp) => epoch
+            .checked_add_signed(Duration::seconds(timestamp.time as i64))
+            .ok_or_else(out_of_range),
+        Bson::String(s) => {
+            let s = s.trim();
+            TIMESTAMP_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+                .or_else(|| {
+                    NaiveDate::parse_from_str(s, DATE_FORMAT)
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })
+                .ok_or(ODBCError::InvalidCharacterValue(target_type))
+        }
+        _ => Err(ODBCError::RestrictedDataType(
+            bson_type_name(value),
+            target_type,
+        )),
+    }
+}
+
+// to_guid converts a UUID binary or its string representation to a SQL_C_GUID.
+fn to_guid(value: &Bson) -> Result<Guid> {
+    let bytes = match value {
+        Bson::Binary(binary) if binary.subtype == BinarySubtype::Uuid => binary.bytes.clone(),
+        Bson::String(s) => {
+            let hex: String = s.trim().chars().filter(|c| *c != '-').collect();
+            if hex.len() != 32 {
+                return Err(ODBCError::InvalidCharacterValue("SQL_C_GUID"));
+            }
+            (0..32)
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+                .collect::<std::result::Result<Vec<u8>, _>>()
+                .map_err(|_| ODBCError::InvalidCharacterValue("SQL_C_GUID"))?
+        }
+        _ => {
+            return Err(ODBCError::RestrictedDataType(
+                bson_type_name(value),
+                "SQL_C_GUID",
+            ))
+        }
+    };
+    if bytes.len() != 16 {
+        return Err(ODBCError::RestrictedDataType(
+            bson_type_name(value),
+            "SQL_C_GUID",
+        ));
+    }
+    let mut d4 = [0; 8];
+    d4.copy_from_slice(&bytes[8..16]);
+    Ok(Guid {
+        d1: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        d2: u16::from_be_bytes([bytes[4], bytes[5]]),
+        d3: u16::from_be_bytes([bytes[6], bytes[7]]),
+        d4,
+    })
+}
+
+// set_fixed_data writes a fixed length value to the target buffer and its size to the
+// length/indicator buffer.
+fn set_fixed_data<T>(data: T, target_buffer: Pointer, length_or_indicator: *mut Len) {
+    unsafe {
+        if !target_buffer.is_null() {
+            (target_buffer as *mut T).write_unaligned(data);
+        }
+        if !length_or_indicator.is_null() {
+            *length_or_indicator = size_of::<T>() as Len;
+        }
+    }
+}
+
+// set_output_data writes as many characters or bytes of [`data`] as possible, starting at
+// [`offset`], to the target buffer of [`buffer_length`] bytes, followed by a null terminator for
+// character data. The length in bytes of the data from the offset is written to the
+// length/indicator buffer.
+// Returns the offset of the data which didn't fit in the buffer and a 01004 warning if the data
+// was truncated.
+fn set_output_data<T: Copy + Default>(
+    data: &[T],
+    target_buffer: *mut T,
+    buffer_length: Len,
+    null_terminated: bool,
+    length_or_indicator: *mut Len,
+    offset: usize,
+) -> (Option<usize>, Option<ODBCError>) {
+    let data = &data[offset.min(data.len())..];
+    if !length_or_indicator.is_null() {
+        unsafe { *length_or_indicator = size_of_val(data) as Len };
+    }
+    let buffer_len = buffer_length.max(0) as usize / size_of::<T>();
+    let capacity = if null_terminated {
+        buffer_len.saturating_sub(1)
+    } else {
+        buffer_len
+    };
+    let num_chars = data.len().min(capacity);
+    if !target_buffer.is_null() {
+        unsafe {
+            copy_nonoverlapping(data.as_ptr(), target_buffer, num_chars);
+            if null_terminated && buffer_len > 0 {
+                *target_buffer.add(num_chars) = T::default();
+            }
+        }
+    }
+    if num_chars < data.len() {
+        (
+            Some(offset + num_chars),
+            Some(ODBCError::OutStringTruncated(buffer_length as usize)),
+        )
+    } else {
+        (None, None)
+    }
+}
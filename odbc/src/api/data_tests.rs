@@ -0,0 +1,315 @@
+use crate::api::data::format_cell;
+use bson::{doc, oid::ObjectId, spec::BinarySubtype, Binary, Bson, DateTime};
+use odbc_sys::{CDataType, Date, Guid, Len, Pointer, Time, Timestamp, NULL_DATA};
+use std::ptr::null_mut;
+
+// Convert the value to the fixed length C type T.
+fn convert<T: Default>(value: &Bson, target_type: CDataType) -> (T, Option<String>) {
+    let mut target = T::default();
+    let mut indicator: Len = 0;
+    let warning = format_cell(
+        Some(value),
+        target_type,
+        &mut target as *mut T as Pointer,
+        0,
+        &mut indicator,
+        0,
+    )
+    .unwrap();
+    assert!(warning.0.is_none());
+    let warning = warning.1;
+    assert_eq!(std::mem::size_of::<T>() as Len, indicator);
+    (target, warning.map(|w| w.get_sql_state().to_string()))
+}
+
+// Convert the value to the C type, expecting an error with the given SQL state.
+fn convert_error(value: &Bson, target_type: CDataType, sql_state: &str) {
+    let mut target = [0u8; 64];
+    let mut indicator: Len = 0;
+    let error = format_cell(
+        Some(value),
+        target_type,
+        target.as_mut_ptr() as Pointer,
+        target.len() as Len,
+        &mut indicator,
+        0,
+    )
+    .unwrap_err();
+    assert_eq!(sql_state, error.get_sql_state());
+}
+
+#[test]
+fn null_values() {
+    let mut indicator: Len = 0;
+    for value in [None, Some(&Bson::Null), Some(&Bson::Undefined)] {
+        let (offset, warning) =
+            format_cell(value, CDataType::SLong, null_mut(), 0, &mut indicator, 0).unwrap();
+        assert!(offset.is_none() && warning.is_none());
+        assert_eq!(NULL_DATA, indicator);
+    }
+    assert_eq!(
+        "22002",
+        format_cell(None, CDataType::SLong, null_mut(), 0, null_mut(), 0)
+            .unwrap_err()
+            .get_sql_state()
+    );
+}
+
+#[test]
+fn char_data() {
+    let mut buffer = [0u8; 8];
+    let mut indicator: Len = 0;
+    let mut char_data = |value: &Bson, buffer_length: usize| {
+        let (_, warning) = format_cell(
+            Some(value),
+            CDataType::Char,
+            buffer.as_mut_ptr() as Pointer,
+            buffer_length as Len,
+            &mut indicator,
+            0,
+        )
+        .unwrap();
+        let len = buffer.iter().position(|b| *b == 0).unwrap();
+        (
+            String::from_utf8(buffer[..len].to_vec()).unwrap(),
+            indicator,
+            warning.map(|w| w.get_sql_state().to_string()),
+        )
+    };
+    assert_eq!(
+        ("hello".to_string(), 5, None),
+        char_data(&Bson::String("hello".to_string()), 8)
+    );
+    assert_eq!(
+        ("hel".to_string(), 5, Some("01004".to_string())),
+        char_data(&Bson::String("hello".to_string()), 4)
+    );
+    assert_eq!(("42".to_string(), 2, None), char_data(&Bson::Int64(42), 8));
+    assert_eq!(
+        ("1".to_string(), 1, None),
+        char_data(&Bson::Boolean(true), 8)
+    );
+    assert_eq!(
+        ("{\"a\":1".to_string(), 7, Some("01004".to_string())),
+        char_data(&Bson::Document(doc! {"a": 1}), 7)
+    );
+}
+
+#[test]
+fn wchar_data() {
+    let mut buffer = [0u16; 8];
+    let mut indicator: Len = 0;
+    let (offset, warning) = format_cell(
+        Some(&Bson::DateTime(DateTime::from_millis(1_000_123))),
+        CDataType::WChar,
+        buffer.as_mut_ptr() as Pointer,
+        (buffer.len() * 2) as Len,
+        &mut indicator,
+        0,
+    )
+    .unwrap();
+    assert_eq!(Some(7), offset);
+    assert_eq!("01004", warning.unwrap().get_sql_state());
+    assert_eq!(2 * "1970-01-01 00:16:40.123".len() as Len, indicator);
+    assert_eq!(
+        "1970-01",
+        String::from_utf16(&buffer[..buffer.iter().position(|c| *c == 0).unwrap()]).unwrap()
+    );
+}
+
+#[test]
+fn binary_data() {
+    let mut buffer = [0u8; 4];
+    let mut indicator: Len = 0;
+    let value = Bson::Binary(Binary {
+        subtype: BinarySubtype::Generic,
+        bytes: vec![1, 2, 3, 4, 5],
+    });
+    let (offset, warning) = format_cell(
+        Some(&value),
+        CDataType::Binary,
+        buffer.as_mut_ptr() as Pointer,
+        buffer.len() as Len,
+        &mut indicator,
+        0,
+    )
+    .unwrap();
+    assert_eq!(Some(4), offset);
+    assert_eq!("01004", warning.unwrap().get_sql_state());
+    assert_eq!(5, indicator);
+    assert_eq!([1, 2, 3, 4], buffer);
+
+    // The next chunk starts at the offset.
+    let (offset, warning) = format_cell(
+        Some(&value),
+        CDataType::Binary,
+        buffer.as_mut_ptr() as Pointer,
+        buffer.len() as Len,
+        &mut indicator,
+        4,
+    )
+    .unwrap();
+    assert!(offset.is_none() && warning.is_none());
+    assert_eq!(1, indicator);
+    assert_eq!(5, buffer[0]);
+
+    let oid = ObjectId::new();
+    let mut buffer = [0u8; 12];
+    assert!(format_cell(
+        Some(&Bson::ObjectId(oid)),
+        CDataType::Binary,
+        buffer.as_mut_ptr() as Pointer,
+        buffer.len() as Len,
+        &mut indicator,
+        0,
+    )
+    .unwrap()
+    .1
+    .is_none());
+    assert_eq!(oid.bytes(), buffer);
+}
+
+#[test]
+fn numeric_data() {
+    assert_eq!(
+        (42i32, None),
+        convert::<i32>(&Bson::Int64(42), CDataType::SLong)
+    );
+    assert_eq!(
+        (-3i16, Some("01S07".to_string())),
+        convert::<i16>(&Bson::Double(-3.7), CDataType::SShort)
+    );
+    assert_eq!(
+        (255u8, None),
+        convert::<u8>(&Bson::String(" 255 ".to_string()), CDataType::UTinyInt)
+    );
+    assert_eq!(
+        (u64::MAX, None),
+        convert::<u64>(&Bson::String(u64::MAX.to_string()), CDataType::UBigInt)
+    );
+    assert_eq!(
+        (1i8, None),
+        convert::<i8>(&Bson::Boolean(true), CDataType::STinyInt)
+    );
+    assert_eq!(
+        (1.5f64, None),
+        convert::<f64>(&Bson::String("1.5".to_string()), CDataType::Double)
+    );
+    assert_eq!(
+        (2.0f32, None),
+        convert::<f32>(&Bson::Int32(2), CDataType::Float)
+    );
+    assert_eq!((1u8, None), convert::<u8>(&Bson::Int32(1), CDataType::Bit));
+    assert_eq!(
+        (7i64, None),
+        convert::<i64>(&Bson::Int64(7), CDataType::Default)
+    );
+    assert_eq!(
+        (0u8, Some("01S07".to_string())),
+        convert::<u8>(&Bson::Double(0.5), CDataType::Bit)
+    );
+
+    convert_error(&Bson::Int32(256), CDataType::UTinyInt, "22003");
+    convert_error(&Bson::Int32(-1), CDataType::ULong, "22003");
+    convert_error(&Bson::Int32(2), CDataType::Bit, "22003");
+    convert_error(&Bson::String("foo".to_string()), CDataType::SLong, "22018");
+    convert_error(&Bson::ObjectId(ObjectId::new()), CDataType::Double, "07006");
+}
+
+#[test]
+fn datetime_data() {
+    // 2022-03-04 05:06:07.890
+    let value = Bson::DateTime(DateTime::from_millis(1_646_370_367_890));
+    assert_eq!(
+        (
+            Timestamp {
+                year: 2022,
+                month: 3,
+                day: 4,
+                hour: 5,
+                minute: 6,
+                second: 7,
+                fraction: 890_000_000,
+            },
+            None
+        ),
+        convert::<Timestamp>(&value, CDataType::TypeTimestamp)
+    );
+    assert_eq!(
+        (
+            Date {
+                year: 2022,
+                month: 3,
+                day: 4
+            },
+            Some("01S07".to_string())
+        ),
+        convert::<Date>(&value, CDataType::TypeDate)
+    );
+    assert_eq!(
+        (
+            Time {
+                hour: 5,
+                minute: 6,
+                second: 7
+            },
+            Some("01S07".to_string())
+        ),
+        convert::<Time>(&value, CDataType::TypeTime)
+    );
+    assert_eq!(
+        (
+            Date {
+                year: 2021,
+                month: 12,
+                day: 31
+            },
+            None
+        ),
+        convert::<Date>(&Bson::String("2021-12-31".to_string()), CDataType::TypeDate)
+    );
+    assert_eq!(
+        (
+            Time {
+                hour: 23,
+                minute: 59,
+                second: 58
+            },
+            None
+        ),
+        convert::<Time>(&Bson::String("23:59:58".to_string()), CDataType::TypeTime)
+    );
+
+    convert_error(
+        &Bson::String("foo".to_string()),
+        CDataType::TypeTimestamp,
+        "22018",
+    );
+    convert_error(&Bson::Int32(1), CDataType::TypeDate, "07006");
+}
+
+#[test]
+fn guid_data() {
+    let expected = Guid {
+        d1: 0x0011_2233,
+        d2: 0x4455,
+        d3: 0x6677,
+        d4: [0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff],
+    };
+    let (guid, _) = convert::<Guid>(
+        &Bson::String("00112233-4455-6677-8899-aabbccddeeff".to_string()),
+        CDataType::Guid,
+    );
+    assert_eq!(expected, guid);
+    let (guid, _) = convert::<Guid>(
+        &Bson::Binary(Binary {
+            subtype: BinarySubtype::Uuid,
+            bytes: (0..16).map(|i| i * 0x11).collect(),
+        }),
+        CDataType::Guid,
+    );
+    assert_eq!(expected, guid);
+
+    convert_error(&Bson::String("0011".to_string()), CDataType::Guid, "22018");
+    convert_error(&Bson::Int32(1), CDataType::Guid, "07006");
+}
@@ -1,4 +1,5 @@
 use num_derive::FromPrimitive;
+use odbc_sys::Desc;
 
 #[macro_export]
 macro_rules! map {
@@ -108,6 +109,14 @@ pub enum RetrieveData {
 #[derive(Clone, Copy, Debug)]
 pub enum RowOperationPtr {}
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowStatus {
+    Success = 0,
+    NoRow = 3,
+    Error = 5,
+    SuccessWithInfo = 6,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum SimulateCursor {
     NonUnique = 0,
@@ -121,3 +130,339 @@ pub enum UseBookmarks {
 
 #[derive(Clone, Copy, Debug)]
 pub enum AsyncStmtEvent {}
+
+// The options of SQLFreeStmt. odbc_sys::FreeStmtOption doesn't have the deprecated SQL_DROP,
+// which applications still pass.
+#[derive(Clone, Copy, Debug, FromPrimitive, PartialEq, Eq)]
+pub enum FreeStmtOption {
+    Close = 0,
+    Drop = 1,
+    Unbind = 2,
+    ResetParams = 3,
+}
+
+// Catalog functions
+
+// The search pattern matching all the names, and the value of SQL_ALL_CATALOGS, SQL_ALL_SCHEMAS
+// and SQL_ALL_TABLE_TYPES.
+pub const SQL_ALL: &str = "%";
+
+#[derive(Clone, Copy, Debug, FromPrimitive)]
+pub enum IdentifierType {
+    BestRowid = 1,
+    Rowver = 2,
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, PartialEq, Eq)]
+pub enum IndexUniqueness {
+    Unique = 0,
+    All = 1,
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, PartialEq, Eq)]
+pub enum StatisticsAccuracy {
+    Quick = 0,
+    Ensure = 1,
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive)]
+pub enum RowIdScope {
+    Currow = 0,
+    Transaction = 1,
+    Session = 2,
+}
+
+// Column attributes
+
+// SQL_CODE_TIMESTAMP, the datetime interval code of the timestamp types.
+pub const SQL_CODE_TIMESTAMP: odbc_sys::Len = 3;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Updatable {
+    ReadOnly = 0,
+    Write = 1,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Unnamed {
+    Named = 0,
+    Unnamed = 1,
+}
+
+// Descriptor fields
+
+// SQL_DESC_ALLOC_TYPE, whether a descriptor was allocated implicitly with its statement or
+// explicitly by the application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocType {
+    Auto = 1,
+    User = 2,
+}
+
+// The descriptor fields, by which the SQLSMALLINT field identifiers of the descriptor functions
+// are matched.
+pub const DESC_FIELDS: [Desc; 42] = [
+    Desc::Count,
+    Desc::Type,
+    Desc::Length,
+    Desc::OctetLengthPtr,
+    Desc::Precision,
+    Desc::Scale,
+    Desc::DatetimeIntervalCode,
+    Desc::Nullable,
+    Desc::IndicatorPtr,
+    Desc::DataPtr,
+    Desc::Name,
+    Desc::Unnamed,
+    Desc::OctetLength,
+    Desc::AllocType,
+    Desc::ArraySize,
+    Desc::ArrayStatusPtr,
+    Desc::AutoUniqueValue,
+    Desc::BaseColumnName,
+    Desc::BaseTableName,
+    Desc::BindOffsetPtr,
+    Desc::BindType,
+    Desc::CaseSensitive,
+    Desc::CatalogName,
+    Desc::ConciseType,
+    Desc::DatetimeIntervalPrecision,
+    Desc::DisplaySize,
+    Desc::FixedPrecScale,
+    Desc::Label,
+    Desc::LiteralPrefix,
+    Desc::LiteralSuffix,
+    Desc::LocalTypeName,
+    Desc::MaximumScale,
+    Desc::MinimumScale,
+    Desc::NumPrecRadix,
+    Desc::ParameterType,
+    Desc::RowsProcessedPtr,
+    Desc::SchemaName,
+    Desc::Searchable,
+    Desc::TypeName,
+    Desc::TableName,
+    Desc::Unsigned,
+    Desc::Updatable,
+];
+
+// Information types
+
+// The information types of SQLGetInfo. odbc_sys::InfoType only has a few of them, and an
+// application can pass any value.
+#[derive(Clone, Copy, Debug, FromPrimitive, PartialEq, Eq)]
+pub enum InfoType {
+    MaxDriverConnections = 0,
+    MaxConcurrentActivities = 1,
+    DataSourceName = 2,
+    DriverName = 6,
+    DriverVer = 7,
+    RowUpdates = 11,
+    ServerName = 13,
+    SearchPatternEscape = 14,
+    DatabaseName = 16,
+    DbmsName = 17,
+    DbmsVer = 18,
+    AccessibleTables = 19,
+    AccessibleProcedures = 20,
+    Procedures = 21,
+    ConcatNullBehavior = 22,
+    CursorCommitBehavior = 23,
+    CursorRollbackBehavior = 24,
+    DataSourceReadOnly = 25,
+    DefaultTxnIsolation = 26,
+    ExpressionsInOrderby = 27,
+    IdentifierCase = 28,
+    IdentifierQuoteChar = 29,
+    MaxColumnNameLen = 30,
+    MaxCursorNameLen = 31,
+    MaxSchemaNameLen = 32,
+    MaxProcedureNameLen = 33,
+    MaxCatalogNameLen = 34,
+    MaxTableNameLen = 35,
+    MultResultSets = 36,
+    MultipleActiveTxn = 37,
+    OuterJoins = 38,
+    SchemaTerm = 39,
+    ProcedureTerm = 40,
+    CatalogNameSeparator = 41,
+    CatalogTerm = 42,
+    ScrollOptions = 44,
+    TableTerm = 45,
+    TxnCapable = 46,
+    UserName = 47,
+    ConvertFunctions = 48,
+    NumericFunctions = 49,
+    StringFunctions = 50,
+    SystemFunctions = 51,
+    TimedateFunctions = 52,
+    ConvertBigint = 53,
+    ConvertBinary = 54,
+    ConvertBit = 55,
+    ConvertChar = 56,
+    ConvertDate = 57,
+    ConvertDecimal = 58,
+    ConvertDouble = 59,
+    ConvertFloat = 60,
+    ConvertInteger = 61,
+    ConvertLongvarchar = 62,
+    ConvertNumeric = 63,
+    ConvertReal = 64,
+    ConvertSmallint = 65,
+    ConvertTime = 66,
+    ConvertTimestamp = 67,
+    ConvertTinyint = 68,
+    ConvertVarbinary = 69,
+    ConvertVarchar = 70,
+    ConvertLongvarbinary = 71,
+    TxnIsolationOption = 72,
+    Integrity = 73,
+    CorrelationName = 74,
+    NonNullableColumns = 75,
+    DriverOdbcVer = 77,
+    LockTypes = 78,
+    PosOperations = 79,
+    PositionedStatements = 80,
+    GetdataExtensions = 81,
+    BookmarkPersistence = 82,
+    StaticSensitivity = 83,
+    FileUsage = 84,
+    NullCollation = 85,
+    AlterTable = 86,
+    ColumnAlias = 87,
+    GroupBy = 88,
+    Keywords = 89,
+    OrderByColumnsInSelect = 90,
+    SchemaUsage = 91,
+    CatalogUsage = 92,
+    QuotedIdentifierCase = 93,
+    SpecialCharacters = 94,
+    Subqueries = 95,
+    Union = 96,
+    MaxColumnsInGroupBy = 97,
+    MaxColumnsInIndex = 98,
+    MaxColumnsInOrderBy = 99,
+    MaxColumnsInSelect = 100,
+    MaxColumnsInTable = 101,
+    MaxIndexSize = 102,
+    MaxRowSizeIncludesLong = 103,
+    MaxRowSize = 104,
+    MaxStatementLen = 105,
+    MaxTablesInSelect = 106,
+    MaxUserNameLen = 107,
+    MaxCharLiteralLen = 108,
+    TimedateAddIntervals = 109,
+    TimedateDiffIntervals = 110,
+    NeedLongDataLen = 111,
+    MaxBinaryLiteralLen = 112,
+    LikeEscapeClause = 113,
+    CatalogLocation = 114,
+    OjCapabilities = 115,
+    ActiveEnvironments = 116,
+    AlterDomain = 117,
+    SqlConformance = 118,
+    DatetimeLiterals = 119,
+    BatchRowCount = 120,
+    BatchSupport = 121,
+    ConvertWchar = 122,
+    ConvertIntervalDayTime = 123,
+    ConvertIntervalYearMonth = 124,
+    ConvertWlongvarchar = 125,
+    ConvertWvarchar = 126,
+    CreateAssertion = 127,
+    CreateCharacterSet = 128,
+    CreateCollation = 129,
+    CreateDomain = 130,
+    CreateSchema = 131,
+    CreateTable = 132,
+    CreateTranslation = 133,
+    CreateView = 134,
+    DropAssertion = 136,
+    DropCharacterSet = 137,
+    DropCollation = 138,
+    DropDomain = 139,
+    DropSchema = 140,
+    DropTable = 141,
+    DropTranslation = 142,
+    DropView = 143,
+    DynamicCursorAttributes1 = 144,
+    DynamicCursorAttributes2 = 145,
+    ForwardOnlyCursorAttributes1 = 146,
+    ForwardOnlyCursorAttributes2 = 147,
+    IndexKeywords = 148,
+    InfoSchemaViews = 149,
+    KeysetCursorAttributes1 = 150,
+    KeysetCursorAttributes2 = 151,
+    OdbcInterfaceConformance = 152,
+    ParamArrayRowCounts = 153,
+    ParamArraySelects = 154,
+    Sql92DatetimeFunctions = 155,
+    Sql92ForeignKeyDeleteRule = 156,
+    Sql92ForeignKeyUpdateRule = 157,
+    Sql92Grant = 158,
+    Sql92NumericValueFunctions = 159,
+    Sql92Predicates = 160,
+    Sql92RelationalJoinOperators = 161,
+    Sql92Revoke = 162,
+    Sql92RowValueConstructor = 163,
+    Sql92StringFunctions = 164,
+    Sql92ValueExpressions = 165,
+    StandardCliConformance = 166,
+    StaticCursorAttributes1 = 167,
+    StaticCursorAttributes2 = 168,
+    AggregateFunctions = 169,
+    DdlIndex = 170,
+    InsertStatement = 172,
+    ConvertGuid = 173,
+    XopenCliYear = 10000,
+    CursorSensitivity = 10001,
+    DescribeParameter = 10002,
+    CatalogName = 10003,
+    CollationSeq = 10004,
+    MaxIdentifierLen = 10005,
+    AsyncMode = 10021,
+    MaxAsyncConcurrentStatements = 10022,
+    AsyncDbcFunctions = 10023,
+    DriverAwarePoolingSupported = 10024,
+    AsyncNotification = 10025,
+}
+
+// Diagnostics
+
+// The fields of the header and records of the diagnostics of SQLGetDiagField.
+// odbc_sys::HeaderDiagnosticIdentifier can't be converted from the identifier an application
+// passes.
+#[derive(Clone, Copy, Debug, FromPrimitive, PartialEq, Eq)]
+pub enum DiagIdentifier {
+    ReturnCode = 1,
+    Number = 2,
+    RowCount = 3,
+    SqlState = 4,
+    Native = 5,
+    MessageText = 6,
+    DynamicFunction = 7,
+    ClassOrigin = 8,
+    SubclassOrigin = 9,
+    ConnectionName = 10,
+    ServerName = 11,
+    DynamicFunctionCode = 12,
+    CursorRowCount = -1249,
+    RowNumber = -1248,
+    ColumnNumber = -1247,
+}
+
+// The SQL statements reported by SQL_DIAG_DYNAMIC_FUNCTION and SQL_DIAG_DYNAMIC_FUNCTION_CODE.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicFunction {
+    UnknownStatement = 0,
+    SelectCursor = 85,
+}
+
+impl DynamicFunction {
+    pub fn name(self) -> &'static str {
+        match self {
+            DynamicFunction::UnknownStatement => "",
+            DynamicFunction::SelectCursor => "SELECT CURSOR",
+        }
+    }
+}
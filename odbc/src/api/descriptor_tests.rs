@@ -0,0 +1,445 @@
+use crate::{
+    api::{
+        definitions::AllocType,
+        test_fixtures::{allocate, connection, free_handles, new_connection, new_env, sql_state},
+    },
+    handles::definitions::*,
+    SQLBindCol, SQLCopyDesc, SQLDisconnect, SQLFetch, SQLFreeHandle, SQLGetDescField,
+    SQLGetDescRec, SQLGetDescRecW, SQLGetStmtAttr, SQLGetTypeInfo, SQLNumResultCols,
+    SQLSetDescField, SQLSetDescRec, SQLSetStmtAttr,
+};
+use odbc_sys::{
+    CDataType, Desc, HDesc, Handle, HandleType, Len, Nullability, Pointer, SmallInt, SqlDataType,
+    SqlReturn, StatementAttribute, ULen,
+};
+use std::ptr::null_mut;
+
+fn stmt_desc(stmt_handle: *mut MongoHandle, attribute: StatementAttribute) -> *mut MongoHandle {
+    let mut desc: Pointer = null_mut();
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetStmtAttr(
+            stmt_handle as *mut _,
+            attribute,
+            &mut desc as *mut Pointer as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+    desc as *mut _
+}
+
+fn numeric_field<T: Default>(
+    desc_handle: *mut MongoHandle,
+    record_number: SmallInt,
+    field: Desc,
+) -> (SqlReturn, T) {
+    let mut value = T::default();
+    let sql_return = SQLGetDescField(
+        desc_handle as HDesc,
+        record_number,
+        field as SmallInt,
+        &mut value as *mut T as Pointer,
+        0,
+        null_mut(),
+    );
+    (sql_return, value)
+}
+
+fn set_field(
+    desc_handle: *mut MongoHandle,
+    record_number: SmallInt,
+    field: Desc,
+    value: Pointer,
+) -> SqlReturn {
+    SQLSetDescField(
+        desc_handle as HDesc,
+        record_number,
+        field as SmallInt,
+        value,
+        0,
+    )
+}
+
+#[test]
+fn implicit_descriptors() {
+    let env_handle: *mut _ = new_env();
+    let conn_handle: *mut _ = new_connection(env_handle, ConnectionState::Connected);
+    let stmt_handle = allocate(HandleType::Stmt, conn_handle);
+    let ard = stmt_desc(stmt_handle, StatementAttribute::AppRowDesc);
+    let ird = stmt_desc(stmt_handle, StatementAttribute::ImpRowDesc);
+    assert_eq!(
+        (SqlReturn::SUCCESS, AllocType::Auto as SmallInt),
+        numeric_field::<SmallInt>(ard, 0, Desc::AllocType)
+    );
+
+    // The implicit descriptors are freed with their statement.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLFreeHandle(HandleType::Desc, ard as Handle)
+    );
+    assert_eq!("HY017", sql_state(ard));
+    // And the implementation descriptors can't be replaced.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLSetStmtAttr(
+            stmt_handle as *mut _,
+            StatementAttribute::ImpRowDesc,
+            ard as Pointer,
+            0
+        )
+    );
+    assert_eq!("HY017", sql_state(stmt_handle));
+
+    // The row attributes of the statement are fields of its row descriptors.
+    let mut rows_fetched: ULen = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLSetStmtAttr(
+            stmt_handle as *mut _,
+            StatementAttribute::RowArraySize,
+            5 as Pointer,
+            0
+        )
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLSetStmtAttr(
+            stmt_handle as *mut _,
+            StatementAttribute::RowsFetchedPtr,
+            &mut rows_fetched as *mut ULen as Pointer,
+            0
+        )
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 5),
+        numeric_field::<ULen>(ard, 0, Desc::ArraySize)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, &mut rows_fetched as *mut ULen as usize),
+        numeric_field::<usize>(ird, 0, Desc::RowsProcessedPtr)
+    );
+
+    // SQLBindCol sets the records of the application row descriptor.
+    let mut data_type: i16 = 0;
+    let mut data_type_len: Len = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            2,
+            CDataType::SShort,
+            &mut data_type as *mut i16 as Pointer,
+            0,
+            &mut data_type_len,
+        )
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 2),
+        numeric_field::<SmallInt>(ard, 0, Desc::Count)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, CDataType::SShort as SmallInt),
+        numeric_field::<SmallInt>(ard, 2, Desc::ConciseType)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, &mut data_type as *mut i16 as usize),
+        numeric_field::<usize>(ard, 2, Desc::DataPtr)
+    );
+    assert_eq!(
+        SqlReturn::NO_DATA,
+        numeric_field::<SmallInt>(ard, 3, Desc::Type).0
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            2,
+            CDataType::SShort,
+            null_mut(),
+            0,
+            null_mut(),
+        )
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_field::<SmallInt>(ard, 0, Desc::Count)
+    );
+
+    // The implementation row descriptor describes the result set.
+    assert_eq!(
+        SqlReturn::ERROR,
+        numeric_field::<SmallInt>(ird, 0, Desc::Count).0
+    );
+    assert_eq!("HY007", sql_state(ird));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType::VARCHAR)
+    );
+    let mut column_count = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLNumResultCols(stmt_handle as *mut _, &mut column_count)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, column_count),
+        numeric_field::<SmallInt>(ird, 0, Desc::Count)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, SqlDataType::SMALLINT.0),
+        numeric_field::<SmallInt>(ird, 2, Desc::ConciseType)
+    );
+    let mut name = [0u16; 10];
+    let mut name_length = 0;
+    let mut desc_type = 0;
+    let mut nullable = Nullability::UNKNOWN;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetDescRecW(
+            ird as HDesc,
+            1,
+            name.as_mut_ptr(),
+            name.len() as SmallInt,
+            &mut name_length,
+            &mut desc_type,
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            &mut nullable,
+        )
+    );
+    assert_eq!("TYPE_NAME", String::from_utf16(&name[..9]).unwrap());
+    assert_eq!(9, name_length);
+    assert_eq!(SqlDataType::VARCHAR.0, desc_type);
+    assert_eq!(Nullability::NO_NULLS, nullable);
+    assert_eq!(
+        SqlReturn::NO_DATA,
+        numeric_field::<SmallInt>(ird, column_count + 1, Desc::Type).0
+    );
+    assert_eq!(
+        SqlReturn::ERROR,
+        set_field(
+            ird,
+            1,
+            Desc::ConciseType,
+            CDataType::Char as SmallInt as Pointer
+        )
+    );
+    assert_eq!("HY016", sql_state(ird));
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Stmt, stmt_handle as Handle)
+    );
+    free_handles(&[conn_handle, env_handle]);
+}
+
+#[test]
+fn explicit_descriptor_binding() {
+    let env_handle: *mut _ = new_env();
+    let conn_handle: *mut _ = new_connection(env_handle, ConnectionState::Connected);
+    let stmt_handle = allocate(HandleType::Stmt, conn_handle);
+    let other_stmt_handle = allocate(HandleType::Stmt, conn_handle);
+    let implicit_ard = stmt_desc(stmt_handle, StatementAttribute::AppRowDesc);
+    let desc = allocate(HandleType::Desc, conn_handle);
+    assert_eq!(
+        (SqlReturn::SUCCESS, AllocType::User as SmallInt),
+        numeric_field::<SmallInt>(desc, 0, Desc::AllocType)
+    );
+
+    // Bind the TYPE_NAME column through the fields of the descriptor's first record.
+    let mut type_name = [0u8; 10];
+    let mut type_name_len: Len = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        set_field(desc, 1, Desc::Type, CDataType::Char as SmallInt as Pointer)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        set_field(desc, 1, Desc::OctetLength, type_name.len() as Pointer)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        set_field(
+            desc,
+            1,
+            Desc::OctetLengthPtr,
+            &mut type_name_len as *mut Len as Pointer
+        )
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        set_field(desc, 1, Desc::DataPtr, type_name.as_mut_ptr() as Pointer)
+    );
+    // A bound record must have a C type.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLSetDescRec(
+            desc as HDesc,
+            2,
+            SqlDataType::EXT_W_LONG_VARCHAR.0,
+            0,
+            0,
+            0,
+            0,
+            type_name.as_mut_ptr() as Pointer,
+            null_mut(),
+            null_mut(),
+        )
+    );
+    assert_eq!("HY021", sql_state(desc));
+
+    // The descriptor can be attached to the statements of its connection, unlike the implicit
+    // descriptors of other statements.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLSetStmtAttr(
+            stmt_handle as *mut _,
+            StatementAttribute::AppRowDesc,
+            stmt_desc(other_stmt_handle, StatementAttribute::AppRowDesc) as Pointer,
+            0
+        )
+    );
+    assert_eq!("HY017", sql_state(stmt_handle));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLSetStmtAttr(
+            stmt_handle as *mut _,
+            StatementAttribute::AppRowDesc,
+            desc as Pointer,
+            0
+        )
+    );
+    assert_eq!(desc, stmt_desc(stmt_handle, StatementAttribute::AppRowDesc));
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType::VARCHAR)
+    );
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt_handle as *mut _));
+    assert_eq!(b"string\0", &type_name[..7]);
+    assert_eq!(6, type_name_len);
+
+    // The statements revert to their implicit descriptor once the descriptor is freed.
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Desc, desc as Handle)
+    );
+    assert_eq!(
+        implicit_ard,
+        stmt_desc(stmt_handle, StatementAttribute::AppRowDesc)
+    );
+
+    for stmt_handle in [stmt_handle, other_stmt_handle] {
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLFreeHandle(HandleType::Stmt, stmt_handle as Handle)
+        );
+    }
+    free_handles(&[conn_handle, env_handle]);
+}
+
+#[test]
+fn copy_desc() {
+    let env_handle: *mut _ = new_env();
+    let conn_handle: *mut _ = new_connection(env_handle, ConnectionState::Connected);
+    let stmt_handle = allocate(HandleType::Stmt, conn_handle);
+    let ird = stmt_desc(stmt_handle, StatementAttribute::ImpRowDesc);
+    let desc = allocate(HandleType::Desc, conn_handle);
+
+    assert_eq!(SqlReturn::ERROR, SQLCopyDesc(desc as HDesc, ird as HDesc));
+    assert_eq!("HY016", sql_state(ird));
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType::VARCHAR)
+    );
+    assert_eq!(SqlReturn::SUCCESS, SQLCopyDesc(ird as HDesc, desc as HDesc));
+    let mut column_count = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLNumResultCols(stmt_handle as *mut _, &mut column_count)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, column_count),
+        numeric_field::<SmallInt>(desc, 0, Desc::Count)
+    );
+
+    // The copied records are unbound and unnamed.
+    let mut name = [0u8; 10];
+    let mut name_length = 0;
+    let mut desc_type = 0;
+    let mut nullable = Nullability::NO_NULLS;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetDescRec(
+            desc as HDesc,
+            2,
+            name.as_mut_ptr(),
+            name.len() as SmallInt,
+            &mut name_length,
+            &mut desc_type,
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            &mut nullable,
+        )
+    );
+    assert_eq!(0, name_length);
+    assert_eq!(SqlDataType::SMALLINT.0, desc_type);
+    assert_eq!(Nullability::UNKNOWN, nullable);
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_field::<usize>(desc, 2, Desc::DataPtr)
+    );
+    assert_eq!(
+        SqlReturn::ERROR,
+        numeric_field::<SmallInt>(desc, 0, Desc::Type).0
+    );
+    assert_eq!("07009", sql_state(desc));
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Desc, desc as Handle)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Stmt, stmt_handle as Handle)
+    );
+    free_handles(&[conn_handle, env_handle]);
+}
+
+#[test]
+fn disconnect_frees_explicit_descriptors() {
+    let env_handle = allocate(HandleType::Env, null_mut());
+    let conn_handle = allocate(HandleType::Dbc, env_handle);
+    connection(conn_handle).write().unwrap().state = ConnectionState::Connected;
+    let desc = allocate(HandleType::Desc, conn_handle);
+    let freed_desc = allocate(HandleType::Desc, conn_handle);
+    let descriptors =
+        |conn_handle: *mut MongoHandle| connection(conn_handle).read().unwrap().descriptors.clone();
+    assert_eq!(2, descriptors(conn_handle).len());
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Desc, freed_desc as Handle)
+    );
+    assert_eq!(
+        vec![desc],
+        descriptors(conn_handle).into_iter().collect::<Vec<_>>()
+    );
+
+    // The descriptor still allocated when the connection is freed goes with it.
+    assert_eq!(SqlReturn::SUCCESS, SQLDisconnect(conn_handle as *mut _));
+    assert!(descriptors(conn_handle).is_empty());
+    assert!(MongoHandle::from_live(desc as Handle, HandleType::Desc).is_none());
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Dbc, conn_handle as Handle)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Env, env_handle as Handle)
+    );
+}
@@ -0,0 +1,66 @@
+use crate::{
+    api::test_fixtures::{allocate, connection, new_env, sql_state},
+    handles::definitions::*,
+    SQLDriverConnect, SQLDriverConnectW, SQLFreeHandle,
+};
+use odbc_sys::{Char, DriverConnectOption, HDbc, HandleType, SqlReturn, NTS};
+use std::ptr::null_mut;
+
+#[test]
+fn negative_buffer_length() {
+    let env_handle = new_env();
+    let conn_handle = allocate(HandleType::Dbc, env_handle);
+    let conn_str = c"SERVER=localhost;USER=user;PWD=pwd";
+    let mut out = [0; 64];
+    let mut out_length = 0;
+
+    // The buffer length is checked before connecting.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLDriverConnect(
+            conn_handle as HDbc,
+            null_mut(),
+            conn_str.as_ptr() as *const Char,
+            NTS as i16,
+            out.as_mut_ptr(),
+            -1,
+            &mut out_length,
+            DriverConnectOption::NoPrompt,
+        )
+    );
+    assert_eq!("HY090", sql_state(conn_handle));
+
+    let conn_wstr: Vec<u16> = "SERVER=localhost;USER=user;PWD=pwd\0"
+        .encode_utf16()
+        .collect();
+    let mut wout = [0; 64];
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLDriverConnectW(
+            conn_handle as HDbc,
+            null_mut(),
+            conn_wstr.as_ptr(),
+            NTS as i16,
+            wout.as_mut_ptr(),
+            -1,
+            &mut out_length,
+            DriverConnectOption::NoPrompt,
+        )
+    );
+    assert_eq!("HY090", sql_state(conn_handle));
+    assert_eq!(0, out[0]);
+    assert_eq!(0, wout[0]);
+    assert_eq!(
+        ConnectionState::Allocated,
+        connection(conn_handle).read().unwrap().state
+    );
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Dbc, conn_handle as *mut _)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Env, env_handle as *mut _)
+    );
+}
@@ -0,0 +1,87 @@
+use crate::api::{
+    errors::ODBCError,
+    odbc_uri::{ODBCUri, PWD, USER},
+};
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+// The system odbc.ini used by unixODBC when ODBCSYSINI is not set.
+const DEFAULT_SYSTEM_ODBC_INI: &str = "/etc/odbc.ini";
+
+/// odbc_ini_paths returns the odbc.ini files to look for DSNs in, in the order in which they
+/// should be searched: the file pointed to by $ODBCINI, the user file ~/.odbc.ini and finally the
+/// system odbc.ini, located in $ODBCSYSINI if it is set.
+pub fn odbc_ini_paths() -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Some(odbc_ini) = env::var_os("ODBCINI") {
+        paths.push(PathBuf::from(odbc_ini));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        paths.push(Path::new(&home).join(".odbc.ini"));
+    }
+    match env::var_os("ODBCSYSINI") {
+        Some(sys_ini_dir) => paths.push(Path::new(&sys_ini_dir).join("odbc.ini")),
+        None => paths.push(PathBuf::from(DEFAULT_SYSTEM_ODBC_INI)),
+    }
+    paths
+}
+
+/// find_dsn returns the attributes of the given DSN from the first of the [`paths`] defining it.
+pub fn find_dsn(paths: &[PathBuf], dsn: &str) -> Option<HashMap<String, String>> {
+    paths.iter().find_map(|path| read_dsn(path, dsn))
+}
+
+/// read_dsn reads the attributes of the given DSN from the odbc.ini file at [`path`]. DSN names
+/// and attribute keywords are case-insensitive, and keywords are returned upper-cased.
+/// Returns None if the file can't be read or doesn't define the DSN.
+pub fn read_dsn(path: &Path, dsn: &str) -> Option<HashMap<String, String>> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut attributes = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            // A DSN section ends where the next one starts.
+            if attributes.is_some() {
+                break;
+            }
+            if line[1..line.len() - 1].trim().eq_ignore_ascii_case(dsn) {
+                attributes = Some(HashMap::new());
+            }
+            continue;
+        }
+        if let (Some(attributes), Some((keyword, value))) = (&mut attributes, line.split_once('='))
+        {
+            attributes.insert(keyword.trim().to_uppercase(), value.trim().to_string());
+        }
+    }
+    attributes
+}
+
+/// dsn_to_odbc_uri builds the connection attributes for the given DSN, looked up in the odbc.ini
+/// files. The user name and password, if not empty, override the values from the DSN.
+pub fn dsn_to_odbc_uri(dsn: &str, user: &str, pwd: &str) -> Result<ODBCUri, ODBCError> {
+    let attributes =
+        find_dsn(&odbc_ini_paths(), dsn).ok_or_else(|| ODBCError::DsnNotFound(dsn.to_string()))?;
+    Ok(odbc_uri_from_dsn(attributes, user, pwd))
+}
+
+/// odbc_uri_from_dsn builds the connection attributes from the given DSN attributes, overriding
+/// the user name and password with the given ones if they are not empty.
+pub fn odbc_uri_from_dsn(attributes: HashMap<String, String>, user: &str, pwd: &str) -> ODBCUri {
+    let mut odbc_uri = ODBCUri::default();
+    attributes
+        .into_iter()
+        .for_each(|(keyword, value)| odbc_uri.set(&keyword, value));
+    if !user.is_empty() {
+        odbc_uri.set(USER, user.to_string());
+    }
+    if !pwd.is_empty() {
+        odbc_uri.set(PWD, pwd.to_string());
+    }
+    odbc_uri
+}
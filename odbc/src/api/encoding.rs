@@ -0,0 +1,61 @@
+/// Charset is the character set of the strings the ANSI functions receive and return, chosen
+/// with the CHARSET keyword of the connection string. The strings of the W functions are always
+/// UTF-16.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Charset {
+    #[default]
+    Utf8,
+    Latin1,
+}
+
+// The character Latin-1 strings use for the characters they can't represent.
+const LATIN1_REPLACEMENT: u8 = b'?';
+
+impl Charset {
+    /// from_name returns the charset called [`name`], matched case-insensitively.
+    pub fn from_name(name: &str) -> Option<Charset> {
+        match name.trim().to_uppercase().as_str() {
+            "UTF-8" | "UTF8" => Some(Charset::Utf8),
+            "ISO-8859-1" | "ISO8859-1" | "LATIN1" | "LATIN-1" => Some(Charset::Latin1),
+            _ => None,
+        }
+    }
+
+    /// decode converts the narrow string [`bytes`] to a String. Invalid UTF-8 sequences are
+    /// replaced with U+FFFD.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Charset::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            Charset::Latin1 => bytes.iter().map(|b| char::from(*b)).collect(),
+        }
+    }
+
+    /// encode converts [`text`] to a narrow string. Latin-1 replaces the characters it can't
+    /// represent with '?'.
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Charset::Utf8 => text.as_bytes().to_vec(),
+            Charset::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c).unwrap_or(LATIN1_REPLACEMENT))
+                .collect(),
+        }
+    }
+
+    /// truncation_length returns the length of the longest prefix of the narrow string
+    /// [`bytes`] which is at most [`max_length`] bytes long and doesn't end in the middle of a
+    /// character.
+    pub fn truncation_length(self, bytes: &[u8], max_length: usize) -> usize {
+        if bytes.len() <= max_length {
+            return bytes.len();
+        }
+        match self {
+            // The continuation bytes of UTF-8 characters are of the form 0b10xxxxxx.
+            Charset::Utf8 => (0..=max_length)
+                .rev()
+                .find(|length| bytes[*length] & 0xC0 != 0x80)
+                .unwrap_or(0),
+            Charset::Latin1 => max_length,
+        }
+    }
+}
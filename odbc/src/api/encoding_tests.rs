@@ -0,0 +1,160 @@
+use crate::{
+    api::{
+        encoding::Charset,
+        errors::ODBCError,
+        test_fixtures::{connection, free_handles, new_connection, new_env, new_statement},
+    },
+    handles::definitions::*,
+    SQLGetDiagRec, SQLGetEnvAttr, SQLGetStmtAttr, SQLSetEnvAttr, SQLSetStmtAttr,
+};
+use odbc_sys::{
+    EnvironmentAttribute, HandleType, Integer, Pointer, SmallInt, SqlReturn, StatementAttribute,
+    ULen,
+};
+use std::ptr::null_mut;
+
+#[test]
+fn charset_names() {
+    assert_eq!(Some(Charset::Utf8), Charset::from_name("utf-8"));
+    assert_eq!(Some(Charset::Utf8), Charset::from_name("UTF8"));
+    assert_eq!(Some(Charset::Latin1), Charset::from_name("ISO-8859-1"));
+    assert_eq!(Some(Charset::Latin1), Charset::from_name("latin1"));
+    assert_eq!(None, Charset::from_name("EBCDIC"));
+}
+
+#[test]
+fn encode_and_decode() {
+    assert_eq!("café".as_bytes(), Charset::Utf8.encode("café"));
+    assert_eq!(b"caf\xE9".to_vec(), Charset::Latin1.encode("café"));
+    // Latin-1 can't represent the characters past U+00FF.
+    assert_eq!(b"caf? ?".to_vec(), Charset::Latin1.encode("caf✐ 𑜲"));
+
+    assert_eq!("café", Charset::Utf8.decode("café".as_bytes()));
+    assert_eq!("café", Charset::Latin1.decode(b"caf\xE9"));
+    assert_eq!("caf\u{FFFD}", Charset::Utf8.decode(b"caf\xE9"));
+}
+
+#[test]
+fn truncation_length() {
+    let bytes = "a✐b".as_bytes();
+    assert_eq!(5, Charset::Utf8.truncation_length(bytes, 10));
+    assert_eq!(4, Charset::Utf8.truncation_length(bytes, 4));
+    // The three bytes of ✐ are kept or dropped together.
+    assert_eq!(1, Charset::Utf8.truncation_length(bytes, 3));
+    assert_eq!(1, Charset::Utf8.truncation_length(bytes, 1));
+    assert_eq!(0, Charset::Utf8.truncation_length(bytes, 0));
+    assert_eq!(2, Charset::Latin1.truncation_length(b"caf\xE9", 2));
+}
+
+fn diag_rec(
+    handle_type: HandleType,
+    handle: *mut MongoHandle,
+    buffer: &mut [u8],
+) -> (SqlReturn, SmallInt) {
+    let mut sql_state = [0u8; 6];
+    let mut native_error = 0;
+    let mut text_length = 0;
+    let sql_return = SQLGetDiagRec(
+        handle_type,
+        handle as *mut _,
+        1,
+        sql_state.as_mut_ptr(),
+        &mut native_error,
+        buffer.as_mut_ptr(),
+        buffer.len() as SmallInt,
+        &mut text_length,
+    );
+    assert_eq!(b"IM002\0", &sql_state);
+    (sql_return, text_length)
+}
+
+#[test]
+fn narrow_diag_rec() {
+    let env_handle: *mut _ = new_env();
+    unsafe { (*env_handle).add_diag_info(ODBCError::DsnNotFound("données".to_string())) };
+
+    const MESSAGE: &str = "[MongoDB][API] Data source name \"données\" not found";
+    let mut message_text = [0u8; 60];
+    assert_eq!(
+        (SqlReturn::SUCCESS, MESSAGE.len() as SmallInt),
+        diag_rec(HandleType::Env, env_handle, &mut message_text)
+    );
+    assert_eq!(MESSAGE.as_bytes(), &message_text[..MESSAGE.len()]);
+
+    // The message is truncated before the 2 bytes of 'é' rather than between them.
+    let mut message_text = [0xFFu8; 39];
+    assert_eq!(
+        (SqlReturn::SUCCESS_WITH_INFO, MESSAGE.len() as SmallInt),
+        diag_rec(HandleType::Env, env_handle, &mut message_text)
+    );
+    assert_eq!(
+        b"[MongoDB][API] Data source name \"donn\0",
+        &message_text[..38]
+    );
+
+    // The statements of a connection use its charset.
+    let conn_handle: *mut _ = new_connection(env_handle, ConnectionState::Allocated);
+    connection(conn_handle).write().unwrap().charset = Charset::Latin1;
+    let stmt_handle: *mut _ = new_statement(conn_handle, StatementState::Allocated);
+    unsafe { (*stmt_handle).add_diag_info(ODBCError::DsnNotFound("données".to_string())) };
+    let mut message_text = [0u8; 60];
+    assert_eq!(
+        (SqlReturn::SUCCESS, MESSAGE.len() as SmallInt - 1),
+        diag_rec(HandleType::Stmt, stmt_handle, &mut message_text)
+    );
+    assert_eq!(
+        b"[MongoDB][API] Data source name \"donn\xE9es\" not found\0",
+        &message_text[..MESSAGE.len()]
+    );
+    free_handles(&[stmt_handle, conn_handle, env_handle]);
+}
+
+#[test]
+fn narrow_attributes() {
+    let env_handle: *mut _ = new_env();
+    let mut odbc_version: Integer = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLSetEnvAttr(
+            env_handle as *mut _,
+            EnvironmentAttribute::OdbcVersion,
+            3 as Pointer,
+            0
+        )
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetEnvAttr(
+            env_handle as *mut _,
+            EnvironmentAttribute::OdbcVersion,
+            &mut odbc_version as *mut Integer as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+    assert_eq!(3, odbc_version);
+
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let mut row_array_size: ULen = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLSetStmtAttr(
+            stmt_handle as *mut _,
+            StatementAttribute::RowArraySize,
+            10 as Pointer,
+            0
+        )
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetStmtAttr(
+            stmt_handle as *mut _,
+            StatementAttribute::RowArraySize,
+            &mut row_array_size as *mut ULen as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+    assert_eq!(10, row_array_size);
+    free_handles(&[stmt_handle, env_handle]);
+}
@@ -1,10 +1,13 @@
 use crate::{
-    api::definitions::*,
-    handles::definitions::{Env, EnvState, MongoHandle},
-    map, SQLGetDiagRecW, SQLGetEnvAttrW, SQLSetEnvAttrW,
+    api::{
+        definitions::*,
+        test_fixtures::{free_handles, new_env},
+    },
+    handles::definitions::MongoHandle,
+    SQLGetDiagRecW, SQLGetEnvAttrW, SQLSetEnvAttrW,
 };
 use odbc_sys::{EnvironmentAttribute, HEnv, HandleType, Integer, Pointer, SqlReturn};
-use std::{collections::BTreeMap, ffi::c_void, mem::size_of, sync::RwLock};
+use std::{collections::BTreeMap, ffi::c_void, mem::size_of};
 
 const OPTIONAL_VALUE_CHANGED: &str = "01S02\0";
 
@@ -70,8 +73,7 @@ fn get_set_env_attr(
 #[test]
 fn test_env_attr() {
     use crate::map;
-    let env_handle: *mut _ =
-        &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+    let env_handle: *mut _ = new_env();
 
     get_set_env_attr(
         env_handle,
@@ -141,13 +143,14 @@ fn test_env_attr() {
             std::ptr::null_mut()
         )
     );
+    free_handles(&[env_handle]);
 }
 
 // optional_value_changed tests functions that return the SQL state
 // 01S02: Optional value changed.
 #[test]
 fn optional_value_changed() {
-    let handle: *mut _ = &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+    let handle: *mut _ = new_env();
     assert_eq!(
         SqlReturn::SUCCESS_WITH_INFO,
         SQLSetEnvAttrW(
@@ -180,4 +183,5 @@ fn optional_value_changed() {
       "[MongoDB][API] Invalid value for attribute SQL_ATTR_CP_MATCH, changed to SQL_CP_STRICT_MATCH\0",
       unsafe { String::from_utf16(&*(message_text as *const [u16; 93])).unwrap() }
     );
+    free_handles(&[handle]);
 }
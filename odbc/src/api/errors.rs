@@ -1,23 +1,220 @@
+use mongodb::error::ErrorKind;
+use odbc_sys::{CDataType, FetchOrientation, Integer, Len, SmallInt, USmallInt};
+
 const VENDOR_IDENTIFIER: &str = "MongoDB";
 
+// Server error codes
+const FAILED_TO_PARSE: i32 = 9;
+const TYPE_MISMATCH: i32 = 14;
+const AUTHENTICATION_FAILED: i32 = 18;
+const NAMESPACE_NOT_FOUND: i32 = 26;
+const MAX_TIME_MS_EXPIRED: i32 = 50;
+const INVALID_PIPELINE_OPERATOR: i32 = 168;
+// The codes of the errors $sql reports for queries which can't be compiled, as listed in the
+// MongoSQL error codes reference. Other codes in the same ranges are not query errors.
+const MONGOSQL_ERRORS: &[i32] = &[
+    // Schema checking errors: the query is invalid for the schema of the data it reads.
+    1001, 1002, 1003, 1004, 1005, 1006, 1007, 1008, 1009, 1010, 1011, 1014, 1016,
+    // Parse error.
+    2001,
+    // Algebrization errors: unknown names, ambiguous references and invalid clauses.
+    3002, 3004, 3005, 3007, 3008, 3009, 3010, 3012, 3013, 3014, 3016, 3019, 3020, 3022, 3023, 3024,
+    3025, 3026, 3027, 3028, 3029, 3030, 3034,
+];
+
+// The row and column numbers of the diagnostics which aren't associated with a row or a column,
+// and of those associated with a row whose number can't be determined.
+pub const SQL_NO_ROW_NUMBER: Len = -1;
+pub const SQL_ROW_NUMBER_UNKNOWN: Len = -2;
+pub const SQL_NO_COLUMN_NUMBER: Integer = -1;
+
+// The documents defining the classes and subclasses of the SQL states.
+const ISO_9075: &str = "ISO 9075";
+const ODBC_3_0: &str = "ODBC 3.0";
+// The SQL states ODBC defines within the classes of the ISO SQL standard.
+const ODBC_SUBCLASSES: &[&str] = &[
+    "01S00", "01S01", "01S02", "01S06", "01S07", "07S01", "08S01", "21S01", "21S02", "25S01",
+    "25S02", "25S03", "42S01", "42S02", "42S11", "42S12", "42S21", "42S22", "HY095", "HY097",
+    "HY098", "HY099", "HY100", "HY101", "HY105", "HY107", "HY109", "HY110", "HY111", "HYT00",
+    "HYT01",
+];
+
 // SQL states
 pub const HYC00: &str = "HYC00";
 pub const HY024: &str = "HY024";
+pub const HY000: &str = "HY000";
+pub const HYT00: &str = "HYT00";
+pub const HYT01: &str = "HYT01";
+pub const HY003: &str = "HY003";
+pub const HY007: &str = "HY007";
+pub const HY009: &str = "HY009";
+pub const HY010: &str = "HY010";
+pub const HY011: &str = "HY011";
+pub const HY016: &str = "HY016";
+pub const HY017: &str = "HY017";
+pub const HY021: &str = "HY021";
+pub const HY090: &str = "HY090";
+pub const HY091: &str = "HY091";
+pub const HY092: &str = "HY092";
+pub const HY095: &str = "HY095";
+pub const HY096: &str = "HY096";
+pub const HY097: &str = "HY097";
+pub const HY098: &str = "HY098";
+pub const HY100: &str = "HY100";
+pub const HY101: &str = "HY101";
+pub const HY106: &str = "HY106";
 pub const _01S02: &str = "01S02";
+pub const _01004: &str = "01004";
+pub const _01S07: &str = "01S07";
+pub const _07006: &str = "07006";
+pub const _07009: &str = "07009";
+pub const _08001: &str = "08001";
+pub const _08002: &str = "08002";
+pub const _08003: &str = "08003";
+pub const _08S01: &str = "08S01";
+pub const _22002: &str = "22002";
+pub const _22003: &str = "22003";
+pub const _22018: &str = "22018";
+pub const _24000: &str = "24000";
+pub const _25000: &str = "25000";
+pub const _28000: &str = "28000";
+pub const _42000: &str = "42000";
+pub const _42S02: &str = "42S02";
+pub const IM002: &str = "IM002";
 
 #[derive(Debug)]
 pub enum ODBCError {
     Unimplemented(&'static str),
+    // A panic caught at the boundary of the exported function [`0`], with its message [`1`].
+    Panic(&'static str, String),
     InvalidAttrValue(&'static str),
     OptionValueChanged(&'static str, &'static str),
+    InvalidConnectionString(String),
+    DsnNotFound(String),
+    OutStringTruncated(usize),
+    ConnectionInUse,
+    ConnectionNotOpen,
+    InvalidTransactionState,
+    FunctionSequenceError(&'static str),
+    InvalidCursorState,
+    CursorNotOpen,
+    CursorNotPositioned,
+    AttributeCannotBeSetNow(&'static str),
+    InvalidDescriptorIndex(Integer),
+    InvalidTargetType(CDataType),
+    InvalidBufferLength(isize),
+    InvalidFieldIdentifier(SmallInt),
+    StatementNotExecuted,
+    CannotModifyIrd,
+    InvalidUseOfAutomaticDescriptor,
+    // The handle attached to a statement as its descriptor [`0`] is not a descriptor.
+    InvalidAttachedDescriptor(&'static str),
+    InconsistentDescriptorInformation(String),
+    FunctionTypeOutOfRange(USmallInt),
+    InvalidOptionIdentifier(SmallInt),
+    InvalidInfoType(USmallInt),
+    InvalidUseOfNullPointer(&'static str),
+    ColumnTypeOutOfRange(SmallInt),
+    ScopeOutOfRange(SmallInt),
+    UniquenessOptionOutOfRange(SmallInt),
+    AccuracyOptionOutOfRange(SmallInt),
+    FetchTypeOutOfRange(FetchOrientation),
+    RestrictedDataType(&'static str, &'static str),
+    InvalidCharacterValue(&'static str),
+    NumericValueOutOfRange(String),
+    FractionalTruncation(String),
+    IndicatorVariableRequiredButNotSupplied,
+    Core(mongo_odbc_core::Error),
+    // An error raised for the value of the column [`column_number`] of the row [`row_number`] of
+    // the rowset, numbered from 1. The row number is None when it can't be determined.
+    InColumn {
+        error: Box<ODBCError>,
+        row_number: Option<usize>,
+        column_number: USmallInt,
+    },
 }
 
 impl ODBCError {
+    /// in_column attaches the row and column the error was raised for.
+    pub fn in_column(self, row_number: Option<usize>, column_number: USmallInt) -> ODBCError {
+        ODBCError::InColumn {
+            error: Box::new(self),
+            row_number,
+            column_number,
+        }
+    }
+
+    pub fn get_row_number(&self) -> Len {
+        match self {
+            ODBCError::InColumn {
+                row_number: Some(row_number),
+                ..
+            } => *row_number as Len,
+            ODBCError::InColumn { .. } => SQL_ROW_NUMBER_UNKNOWN,
+            _ => SQL_NO_ROW_NUMBER,
+        }
+    }
+
+    pub fn get_column_number(&self) -> Integer {
+        match self {
+            ODBCError::InColumn { column_number, .. } => *column_number as Integer,
+            _ => SQL_NO_COLUMN_NUMBER,
+        }
+    }
+
     pub fn get_sql_state(&self) -> &str {
         match self {
             ODBCError::Unimplemented(_) => HYC00,
+            ODBCError::Panic(_, _) => HY000,
             ODBCError::InvalidAttrValue(_) => HY024,
             ODBCError::OptionValueChanged(_, _) => _01S02,
+            ODBCError::InvalidConnectionString(_) => _08001,
+            ODBCError::DsnNotFound(_) => IM002,
+            ODBCError::OutStringTruncated(_) => _01004,
+            ODBCError::ConnectionInUse => _08002,
+            ODBCError::ConnectionNotOpen => _08003,
+            ODBCError::InvalidTransactionState => _25000,
+            ODBCError::FunctionSequenceError(_) => HY010,
+            ODBCError::InvalidCursorState
+            | ODBCError::CursorNotOpen
+            | ODBCError::CursorNotPositioned => _24000,
+            ODBCError::AttributeCannotBeSetNow(_) => HY011,
+            ODBCError::InvalidDescriptorIndex(_) => _07009,
+            ODBCError::InvalidTargetType(_) => HY003,
+            ODBCError::InvalidBufferLength(_) => HY090,
+            ODBCError::InvalidFieldIdentifier(_) => HY091,
+            ODBCError::StatementNotExecuted => HY007,
+            ODBCError::CannotModifyIrd => HY016,
+            ODBCError::InvalidUseOfAutomaticDescriptor => HY017,
+            ODBCError::InvalidAttachedDescriptor(_) => HY000,
+            ODBCError::InconsistentDescriptorInformation(_) => HY021,
+            ODBCError::FunctionTypeOutOfRange(_) => HY095,
+            ODBCError::InvalidOptionIdentifier(_) => HY092,
+            ODBCError::InvalidInfoType(_) => HY096,
+            ODBCError::InvalidUseOfNullPointer(_) => HY009,
+            ODBCError::ColumnTypeOutOfRange(_) => HY097,
+            ODBCError::ScopeOutOfRange(_) => HY098,
+            ODBCError::UniquenessOptionOutOfRange(_) => HY100,
+            ODBCError::AccuracyOptionOutOfRange(_) => HY101,
+            ODBCError::FetchTypeOutOfRange(_) => HY106,
+            ODBCError::RestrictedDataType(_, _) => _07006,
+            ODBCError::InvalidCharacterValue(_) => _22018,
+            ODBCError::NumericValueOutOfRange(_) => _22003,
+            ODBCError::FractionalTruncation(_) => _01S07,
+            ODBCError::IndicatorVariableRequiredButNotSupplied => _22002,
+            ODBCError::Core(core_error) => match core_error {
+                mongo_odbc_core::Error::InvalidUriFormat(_)
+                | mongo_odbc_core::Error::ServerUnreachable(_) => _08001,
+                mongo_odbc_core::Error::AuthenticationFailed(_) => _28000,
+                mongo_odbc_core::Error::LoginTimeout(_) => HYT00,
+                mongo_odbc_core::Error::InvalidCursorState => _24000,
+                mongo_odbc_core::Error::ColIndexOutOfBounds(_) => _07009,
+                mongo_odbc_core::Error::NoDatabase
+                | mongo_odbc_core::Error::InvalidResultSetJsonSchema(_)
+                | mongo_odbc_core::Error::InvalidCursorReply(_) => HY000,
+                mongo_odbc_core::Error::MongoDriver(error) => driver_error_sql_state(error),
+            },
+            ODBCError::InColumn { error, .. } => error.get_sql_state(),
         }
     }
     pub fn get_error_message(&self) -> String {
@@ -26,6 +223,10 @@ impl ODBCError {
                 "[{}][API] The feature {} is not implemented",
                 VENDOR_IDENTIFIER, fn_name
             ),
+            ODBCError::Panic(fn_name, message) => format!(
+                "[{}][API] Internal error in {}: {}",
+                VENDOR_IDENTIFIER, fn_name, message
+            ),
             ODBCError::InvalidAttrValue(attr) => format!(
                 "[{}][API] Invalid value for attribute {}",
                 VENDOR_IDENTIFIER, attr
@@ -34,6 +235,154 @@ impl ODBCError {
                 "[{}][API] Invalid value for attribute {}, changed to {}",
                 VENDOR_IDENTIFIER, attr, value
             ),
+            ODBCError::InvalidConnectionString(reason) => format!(
+                "[{}][API] Invalid connection string: {}",
+                VENDOR_IDENTIFIER, reason
+            ),
+            ODBCError::DsnNotFound(dsn) => format!(
+                "[{}][API] Data source name \"{}\" not found",
+                VENDOR_IDENTIFIER, dsn
+            ),
+            ODBCError::OutStringTruncated(buffer_length) => format!(
+                "[{}][API] Buffer size \"{}\" not large enough for string",
+                VENDOR_IDENTIFIER, buffer_length
+            ),
+            ODBCError::ConnectionInUse => format!(
+                "[{}][API] The connection is already established",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::ConnectionNotOpen => {
+                format!("[{}][API] The connection is not open", VENDOR_IDENTIFIER)
+            }
+            ODBCError::InvalidTransactionState => format!(
+                "[{}][API] A transaction is in progress on the connection",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::FunctionSequenceError(fn_name) => format!(
+                "[{}][API] Function sequence error: {} can't be called in the current state",
+                VENDOR_IDENTIFIER, fn_name
+            ),
+            ODBCError::InvalidCursorState => format!(
+                "[{}][API] Invalid cursor state: a cursor is already open on the statement",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::CursorNotOpen => format!(
+                "[{}][API] Invalid cursor state: no cursor is open on the statement",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::CursorNotPositioned => format!(
+                "[{}][API] Invalid cursor state: the cursor is not positioned on a row",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::AttributeCannotBeSetNow(attr) => format!(
+                "[{}][API] Attribute {} can't be set in the current state",
+                VENDOR_IDENTIFIER, attr
+            ),
+            ODBCError::InvalidDescriptorIndex(index) => format!(
+                "[{}][API] Invalid descriptor index {}",
+                VENDOR_IDENTIFIER, index
+            ),
+            ODBCError::InvalidTargetType(target_type) => format!(
+                "[{}][API] Invalid target type {:?}",
+                VENDOR_IDENTIFIER, target_type
+            ),
+            ODBCError::InvalidBufferLength(buffer_length) => format!(
+                "[{}][API] Invalid buffer length {}",
+                VENDOR_IDENTIFIER, buffer_length
+            ),
+            ODBCError::InvalidFieldIdentifier(field_identifier) => format!(
+                "[{}][API] Invalid descriptor field identifier {}",
+                VENDOR_IDENTIFIER, field_identifier
+            ),
+            ODBCError::StatementNotExecuted => format!(
+                "[{}][API] Associated statement is not prepared: the statement has no result set",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::CannotModifyIrd => format!(
+                "[{}][API] Cannot modify an implementation row descriptor",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::InvalidUseOfAutomaticDescriptor => format!(
+                "[{}][API] Invalid use of an automatically allocated descriptor handle",
+                VENDOR_IDENTIFIER
+            ),
+            ODBCError::InvalidAttachedDescriptor(attribute) => format!(
+                "[{}][API] The handle attached to the statement as {} is not a descriptor",
+                VENDOR_IDENTIFIER, attribute
+            ),
+            ODBCError::InconsistentDescriptorInformation(reason) => format!(
+                "[{}][API] Inconsistent descriptor information: {}",
+                VENDOR_IDENTIFIER, reason
+            ),
+            ODBCError::FunctionTypeOutOfRange(function_id) => format!(
+                "[{}][API] Function type {} out of range",
+                VENDOR_IDENTIFIER, function_id
+            ),
+            ODBCError::InvalidOptionIdentifier(option) => format!(
+                "[{}][API] Invalid option identifier {}",
+                VENDOR_IDENTIFIER, option
+            ),
+            ODBCError::InvalidInfoType(info_type) => format!(
+                "[{}][API] Information type {} out of range",
+                VENDOR_IDENTIFIER, info_type
+            ),
+            ODBCError::InvalidUseOfNullPointer(argument) => format!(
+                "[{}][API] Invalid use of null pointer: {} can't be null",
+                VENDOR_IDENTIFIER, argument
+            ),
+            ODBCError::ColumnTypeOutOfRange(identifier_type) => format!(
+                "[{}][API] Column type {} out of range",
+                VENDOR_IDENTIFIER, identifier_type
+            ),
+            ODBCError::ScopeOutOfRange(scope) => format!(
+                "[{}][API] Scope {} out of range",
+                VENDOR_IDENTIFIER, scope
+            ),
+            ODBCError::UniquenessOptionOutOfRange(unique) => format!(
+                "[{}][API] Uniqueness option {} out of range",
+                VENDOR_IDENTIFIER, unique
+            ),
+            ODBCError::AccuracyOptionOutOfRange(reserved) => format!(
+                "[{}][API] Accuracy option {} out of range",
+                VENDOR_IDENTIFIER, reserved
+            ),
+            ODBCError::FetchTypeOutOfRange(fetch_orientation) => format!(
+                "[{}][API] Fetch type {:?} out of range, only forward only cursors are supported",
+                VENDOR_IDENTIFIER, fetch_orientation
+            ),
+            ODBCError::RestrictedDataType(bson_type, target_type) => format!(
+                "[{}][API] Restricted data type attribute violation: a {} value can't be converted to {}",
+                VENDOR_IDENTIFIER, bson_type, target_type
+            ),
+            ODBCError::InvalidCharacterValue(target_type) => format!(
+                "[{}][API] Invalid character value for a conversion to {}",
+                VENDOR_IDENTIFIER, target_type
+            ),
+            ODBCError::NumericValueOutOfRange(value) => format!(
+                "[{}][API] Numeric value out of range: {}",
+                VENDOR_IDENTIFIER, value
+            ),
+            ODBCError::FractionalTruncation(value) => format!(
+                "[{}][API] Fractional truncation of the value {}",
+                VENDOR_IDENTIFIER, value
+            ),
+            ODBCError::IndicatorVariableRequiredButNotSupplied => format!(
+                "[{}][API] Indicator variable required but not supplied",
+                VENDOR_IDENTIFIER
+            ),
+            // The message of an error reported by the server is the server's message.
+            ODBCError::Core(mongo_odbc_core::Error::MongoDriver(error)) => {
+                match error.kind.as_ref() {
+                    ErrorKind::Command(command_error) => {
+                        format!("[{}][Core] {}", VENDOR_IDENTIFIER, command_error.message)
+                    }
+                    _ => format!("[{}][Core] {}", VENDOR_IDENTIFIER, error),
+                }
+            }
+            ODBCError::Core(core_error) => {
+                format!("[{}][Core] {}", VENDOR_IDENTIFIER, core_error)
+            }
+            ODBCError::InColumn { error, .. } => error.get_error_message(),
         }
     }
     pub fn get_native_err_code(&self) -> i32 {
@@ -42,8 +391,99 @@ impl ODBCError {
             // and so the driver returns 0 since it doesn't have a native error
             // code to propagate.
             ODBCError::Unimplemented(_)
+            | ODBCError::Panic(_, _)
             | ODBCError::InvalidAttrValue(_)
-            | ODBCError::OptionValueChanged(_, _) => 0,
+            | ODBCError::OptionValueChanged(_, _)
+            | ODBCError::InvalidConnectionString(_)
+            | ODBCError::DsnNotFound(_)
+            | ODBCError::OutStringTruncated(_)
+            | ODBCError::ConnectionInUse
+            | ODBCError::ConnectionNotOpen
+            | ODBCError::InvalidTransactionState
+            | ODBCError::FunctionSequenceError(_)
+            | ODBCError::InvalidCursorState
+            | ODBCError::CursorNotOpen
+            | ODBCError::CursorNotPositioned
+            | ODBCError::AttributeCannotBeSetNow(_)
+            | ODBCError::InvalidDescriptorIndex(_)
+            | ODBCError::InvalidTargetType(_)
+            | ODBCError::InvalidBufferLength(_)
+            | ODBCError::InvalidFieldIdentifier(_)
+            | ODBCError::StatementNotExecuted
+            | ODBCError::CannotModifyIrd
+            | ODBCError::InvalidUseOfAutomaticDescriptor
+            | ODBCError::InvalidAttachedDescriptor(_)
+            | ODBCError::InconsistentDescriptorInformation(_)
+            | ODBCError::FunctionTypeOutOfRange(_)
+            | ODBCError::InvalidOptionIdentifier(_)
+            | ODBCError::InvalidInfoType(_)
+            | ODBCError::InvalidUseOfNullPointer(_)
+            | ODBCError::ColumnTypeOutOfRange(_)
+            | ODBCError::ScopeOutOfRange(_)
+            | ODBCError::UniquenessOptionOutOfRange(_)
+            | ODBCError::AccuracyOptionOutOfRange(_)
+            | ODBCError::FetchTypeOutOfRange(_)
+            | ODBCError::RestrictedDataType(_, _)
+            | ODBCError::InvalidCharacterValue(_)
+            | ODBCError::NumericValueOutOfRange(_)
+            | ODBCError::FractionalTruncation(_)
+            | ODBCError::IndicatorVariableRequiredButNotSupplied => 0,
+            // The native error code of an error reported by the server is the server's error
+            // code.
+            ODBCError::Core(
+                mongo_odbc_core::Error::MongoDriver(error)
+                | mongo_odbc_core::Error::AuthenticationFailed(error)
+                | mongo_odbc_core::Error::LoginTimeout(error)
+                | mongo_odbc_core::Error::ServerUnreachable(error),
+            ) => match error.kind.as_ref() {
+                ErrorKind::Command(command_error) => command_error.code,
+                _ => 0,
+            },
+            ODBCError::Core(_) => 0,
+            ODBCError::InColumn { error, .. } => error.get_native_err_code(),
         }
     }
+
+    /// get_class_origin returns the document defining the class of the error's SQL state: ODBC
+    /// for the IM class, the ISO SQL standard for the others.
+    pub fn get_class_origin(&self) -> &'static str {
+        if self.get_sql_state().starts_with("IM") {
+            ODBC_3_0
+        } else {
+            ISO_9075
+        }
+    }
+
+    /// get_subclass_origin returns the document defining the error's SQL state.
+    pub fn get_subclass_origin(&self) -> &'static str {
+        let sql_state = self.get_sql_state();
+        if sql_state.starts_with("IM") || ODBC_SUBCLASSES.contains(&sql_state) {
+            ODBC_3_0
+        } else {
+            ISO_9075
+        }
+    }
+}
+
+// driver_error_sql_state returns the SQL state of an error of the MongoDB driver, from the
+// server's error code when the server reported it.
+fn driver_error_sql_state(error: &mongodb::error::Error) -> &'static str {
+    match error.kind.as_ref() {
+        ErrorKind::Authentication { .. } => _28000,
+        ErrorKind::Command(command_error) => match command_error.code {
+            AUTHENTICATION_FAILED => _28000,
+            MAX_TIME_MS_EXPIRED => HYT00,
+            NAMESPACE_NOT_FOUND => _42S02,
+            FAILED_TO_PARSE | TYPE_MISMATCH | INVALID_PIPELINE_OPERATOR => _42000,
+            code if MONGOSQL_ERRORS.contains(&code) => _42000,
+            _ => HY000,
+        },
+        // The server selection only fails once no server was found before the timeout.
+        ErrorKind::ServerSelection { .. } => HYT01,
+        ErrorKind::Io(io_error) if io_error.kind() == std::io::ErrorKind::TimedOut => HYT01,
+        ErrorKind::Io(_)
+        | ErrorKind::ConnectionPoolCleared { .. }
+        | ErrorKind::DnsResolve { .. } => _08S01,
+        _ => HY000,
+    }
 }
@@ -0,0 +1,64 @@
+use crate::api::errors::ODBCError;
+use bson::doc;
+use mongodb::error::{CommandError, Error, ErrorKind};
+
+fn command_error(code: i32, code_name: &str, message: &str) -> Error {
+    let command_error: CommandError = bson::from_document(doc! {
+        "code": code,
+        "codeName": code_name,
+        "errmsg": message,
+    })
+    .unwrap();
+    Error::from(ErrorKind::Command(command_error))
+}
+
+fn driver_error(error: Error) -> ODBCError {
+    ODBCError::Core(mongo_odbc_core::Error::MongoDriver(error))
+}
+
+#[test]
+fn server_errors() {
+    // (code, code name, expected SQL state)
+    for (code, code_name, expected) in [
+        (18, "AuthenticationFailed", "28000"),
+        (50, "MaxTimeMSExpired", "HYT00"),
+        (26, "NamespaceNotFound", "42S02"),
+        (9, "FailedToParse", "42000"),
+        (2001, "", "42000"),
+        (1001, "", "42000"),
+        (3008, "", "42000"),
+        // Codes which are not documented MongoSQL query errors are not syntax errors.
+        (1500, "", "HY000"),
+        (4000, "", "HY000"),
+        (11000, "DuplicateKey", "HY000"),
+    ] {
+        let error = driver_error(command_error(code, code_name, "server message"));
+        assert_eq!(expected, error.get_sql_state(), "{}", code_name);
+        assert_eq!(code, error.get_native_err_code());
+        assert_eq!("[MongoDB][Core] server message", error.get_error_message());
+    }
+}
+
+#[test]
+fn network_errors() {
+    let timeout = driver_error(Error::from(std::io::Error::from(
+        std::io::ErrorKind::TimedOut,
+    )));
+    assert_eq!("HYT01", timeout.get_sql_state());
+    assert_eq!(0, timeout.get_native_err_code());
+    let reset = driver_error(Error::from(std::io::Error::from(
+        std::io::ErrorKind::ConnectionReset,
+    )));
+    assert_eq!("08S01", reset.get_sql_state());
+}
+
+#[test]
+fn connection_errors() {
+    let error = ODBCError::Core(mongo_odbc_core::Error::AuthenticationFailed(command_error(
+        18,
+        "AuthenticationFailed",
+        "Authentication failed.",
+    )));
+    assert_eq!("28000", error.get_sql_state());
+    assert_eq!(18, error.get_native_err_code());
+}
@@ -0,0 +1,36 @@
+use crate::{
+    api::test_fixtures::{free_handles, new_statement, sql_state, statement},
+    handles::definitions::StatementState,
+    SQLExecDirectW,
+};
+use odbc_sys::{SqlReturn, NTS};
+use std::ptr::null_mut;
+
+#[test]
+fn exec_direct_without_connection() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let query: Vec<u16> = "select * from foo\0".encode_utf16().collect();
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLExecDirectW(stmt_handle as *mut _, query.as_ptr(), NTS as i32)
+    );
+    assert_eq!("08003", sql_state(stmt_handle));
+    {
+        let stmt_contents = statement(stmt_handle).read().unwrap();
+        assert_eq!(StatementState::Allocated, stmt_contents.state);
+        assert!(stmt_contents.mongo_statement.is_none());
+    }
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn exec_direct_with_open_cursor() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::ExecutedHasResultSet);
+    let query: Vec<u16> = "select * from foo".encode_utf16().collect();
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLExecDirectW(stmt_handle as *mut _, query.as_ptr(), query.len() as i32)
+    );
+    assert_eq!("24000", sql_state(stmt_handle));
+    free_handles(&[stmt_handle]);
+}
@@ -0,0 +1,345 @@
+use crate::{
+    api::{
+        definitions::RowStatus,
+        test_fixtures::{free_handles, new_statement, sql_state, statement, with_errors},
+    },
+    handles::definitions::StatementState,
+    SQLBindCol, SQLFetch, SQLFetchScroll, SQLGetData, SQLGetTypeInfo, SQLSetStmtAttrW,
+};
+use odbc_sys::{
+    CDataType, FetchOrientation, Len, Pointer, SqlDataType, SqlReturn, StatementAttribute, ULen,
+    USmallInt, NULL_DATA,
+};
+use std::{mem::size_of, ptr::null_mut};
+
+#[test]
+fn bind_col_and_fetch() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let mut type_name = [0u8; 4];
+    let mut type_name_len: Len = 0;
+    let mut data_type: i16 = 0;
+    let mut data_type_len: Len = 0;
+    let mut column_size: i32 = 0;
+    let mut column_size_ind: Len = 0;
+    // Fetching without a result set is a function sequence error.
+    assert_eq!(SqlReturn::ERROR, SQLFetch(stmt_handle as *mut _));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType::VARCHAR)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            1,
+            CDataType::Char,
+            type_name.as_mut_ptr() as Pointer,
+            type_name.len() as Len,
+            &mut type_name_len,
+        )
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            2,
+            CDataType::SShort,
+            &mut data_type as *mut i16 as Pointer,
+            0,
+            &mut data_type_len,
+        )
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            3,
+            CDataType::SLong,
+            &mut column_size as *mut i32 as Pointer,
+            0,
+            &mut column_size_ind,
+        )
+    );
+    // Bookmark columns are not supported.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            0,
+            CDataType::SLong,
+            &mut column_size as *mut i32 as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+
+    // The first VARCHAR type is string, which is truncated.
+    assert_eq!(
+        SqlReturn::SUCCESS_WITH_INFO,
+        SQLFetch(stmt_handle as *mut _)
+    );
+    assert_eq!(
+        StatementState::CursorFetchSet,
+        statement(stmt_handle).read().unwrap().state
+    );
+    assert_eq!(1, with_errors(stmt_handle, Vec::len));
+    assert_eq!("01004", sql_state(stmt_handle));
+    assert_eq!(b"str\0", &type_name);
+    assert_eq!(6, type_name_len);
+    assert_eq!(12, data_type);
+    assert_eq!(2, data_type_len);
+    assert_eq!(16 * 1024 * 1024, column_size);
+    assert_eq!(4, column_size_ind);
+
+    // Unbind the name and fetch the remaining rows.
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            1,
+            CDataType::Char,
+            null_mut(),
+            0,
+            null_mut(),
+        )
+    );
+    let mut count = 1;
+    while SQLFetch(stmt_handle as *mut _) == SqlReturn::SUCCESS {
+        assert_eq!(12, data_type);
+        count += 1;
+    }
+    assert_eq!(SqlReturn::NO_DATA, SQLFetch(stmt_handle as *mut _));
+    assert_eq!(13, count);
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn get_data_in_chunks() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let mut buffer = [0u8; 4];
+    let mut indicator: Len = 0;
+    let mut get_char_data = |col: u16| {
+        let sql_return = SQLGetData(
+            stmt_handle as *mut _,
+            col,
+            CDataType::Char,
+            buffer.as_mut_ptr() as Pointer,
+            buffer.len() as Len,
+            &mut indicator,
+        );
+        let len = buffer.iter().position(|b| *b == 0).unwrap_or(0);
+        (
+            sql_return,
+            String::from_utf8(buffer[..len].to_vec()).unwrap(),
+            indicator,
+        )
+    };
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType::VARCHAR)
+    );
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt_handle as *mut _));
+
+    // TYPE_NAME is returned in chunks until it is used up.
+    assert_eq!(
+        (SqlReturn::SUCCESS_WITH_INFO, "str".to_string(), 6),
+        get_char_data(1)
+    );
+    assert_eq!((SqlReturn::SUCCESS, "ing".to_string(), 3), get_char_data(1));
+    assert_eq!(SqlReturn::NO_DATA, get_char_data(1).0);
+    // CREATE_PARAMS is null.
+    assert_eq!(NULL_DATA, get_char_data(6).2);
+    assert_eq!(SqlReturn::NO_DATA, get_char_data(6).0);
+
+    let get_data_type = |target_type: CDataType| {
+        let mut data_type: i32 = 0;
+        let sql_return = SQLGetData(
+            stmt_handle as *mut _,
+            2,
+            target_type,
+            &mut data_type as *mut i32 as Pointer,
+            0,
+            null_mut(),
+        );
+        (sql_return, data_type)
+    };
+    // SQL_C_DEFAULT converts DATA_TYPE to SQL_C_SLONG.
+    assert_eq!((SqlReturn::SUCCESS, 12), get_data_type(CDataType::Default));
+    assert_eq!(SqlReturn::NO_DATA, get_data_type(CDataType::Default).0);
+
+    // SQL_ARD_TYPE requires the column to be bound.
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt_handle as *mut _));
+    assert_eq!(SqlReturn::ERROR, get_data_type(CDataType::Ard).0);
+    assert_eq!("HY003", sql_state(stmt_handle));
+    let mut bound: i32 = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            2,
+            CDataType::SLong,
+            &mut bound as *mut i32 as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+    assert_eq!((SqlReturn::SUCCESS, 12), get_data_type(CDataType::Ard));
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn column_wise_block_fetch() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let mut data_types = [0i16; 10];
+    let mut data_type_lens: [Len; 10] = [0; 10];
+    let mut row_statuses: [USmallInt; 10] = [0; 10];
+    let mut rows_fetched: ULen = 0;
+    let set_attr = |attr: StatementAttribute, value: Pointer| {
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLSetStmtAttrW(stmt_handle as *mut _, attr, value, 0)
+        )
+    };
+    set_attr(StatementAttribute::RowArraySize, 10 as Pointer);
+    set_attr(
+        StatementAttribute::RowStatusPtr,
+        row_statuses.as_mut_ptr() as Pointer,
+    );
+    set_attr(
+        StatementAttribute::RowsFetchedPtr,
+        &mut rows_fetched as *mut ULen as Pointer,
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType(0))
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            2,
+            CDataType::SShort,
+            data_types.as_mut_ptr() as Pointer,
+            0,
+            data_type_lens.as_mut_ptr(),
+        )
+    );
+
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt_handle as *mut _));
+    assert_eq!(10, rows_fetched);
+    assert_eq!([-7, -5, -2, 0, 3, 4, 8, 12, 12, 12], data_types);
+    assert_eq!([2; 10], data_type_lens);
+    assert_eq!([RowStatus::Success as USmallInt; 10], row_statuses);
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFetchScroll(stmt_handle as *mut _, FetchOrientation::Next, 0)
+    );
+    assert_eq!(10, rows_fetched);
+    // Only forward only cursors are supported.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLFetchScroll(stmt_handle as *mut _, FetchOrientation::First, 0)
+    );
+
+    // The last rowset is partial.
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt_handle as *mut _));
+    assert_eq!(2, rows_fetched);
+    assert_eq!([93, 93], data_types[..2]);
+    assert_eq!([RowStatus::Success as USmallInt; 2], row_statuses[..2]);
+    assert_eq!([RowStatus::NoRow as USmallInt; 8], row_statuses[2..]);
+
+    assert_eq!(SqlReturn::NO_DATA, SQLFetch(stmt_handle as *mut _));
+    assert_eq!(0, rows_fetched);
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn row_wise_block_fetch() {
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    struct Row {
+        data_type: i16,
+        data_type_len: Len,
+        column_size: i32,
+    }
+
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let mut rows = [Row::default(); 5];
+    let mut row_statuses: [USmallInt; 4] = [0; 4];
+    // The buffers are bound to the first row, and offset to write to the following ones.
+    let mut bind_offset: ULen = size_of::<Row>();
+    let set_attr = |attr: StatementAttribute, value: Pointer| {
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLSetStmtAttrW(stmt_handle as *mut _, attr, value, 0)
+        )
+    };
+    set_attr(StatementAttribute::RowArraySize, 4 as Pointer);
+    set_attr(StatementAttribute::RowBindType, size_of::<Row>() as Pointer);
+    set_attr(
+        StatementAttribute::RowBindOffsetPtr,
+        &mut bind_offset as *mut ULen as Pointer,
+    );
+    set_attr(
+        StatementAttribute::RowStatusPtr,
+        row_statuses.as_mut_ptr() as Pointer,
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType(0))
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            2,
+            CDataType::SShort,
+            &mut rows[0].data_type as *mut i16 as Pointer,
+            0,
+            &mut rows[0].data_type_len,
+        )
+    );
+    // COLUMN_SIZE is bound without an indicator, so its null values fail.
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            3,
+            CDataType::SLong,
+            &mut rows[0].column_size as *mut i32 as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+
+    assert_eq!(
+        SqlReturn::SUCCESS_WITH_INFO,
+        SQLFetch(stmt_handle as *mut _)
+    );
+    assert_eq!(0, rows[0].data_type);
+    assert_eq!(
+        vec![
+            (-7, 2, 1),
+            (-5, 2, 19),
+            (-2, 2, 16 * 1024 * 1024),
+            (0, 2, 0)
+        ],
+        rows[1..]
+            .iter()
+            .map(|row| (row.data_type, row.data_type_len, row.column_size))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        [
+            RowStatus::Success as USmallInt,
+            RowStatus::Success as USmallInt,
+            RowStatus::Success as USmallInt,
+            RowStatus::Error as USmallInt
+        ],
+        row_statuses
+    );
+    assert_eq!(1, with_errors(stmt_handle, Vec::len));
+    assert_eq!("22002", sql_state(stmt_handle));
+    free_handles(&[stmt_handle]);
+}
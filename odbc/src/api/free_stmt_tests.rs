@@ -0,0 +1,181 @@
+use crate::{
+    api::test_fixtures::{
+        allocate, connection, free_handles, new_statement, open_connection, sql_state, statement,
+    },
+    handles::definitions::*,
+    SQLBindCol, SQLCloseCursor, SQLFetch, SQLFreeStmt, SQLGetTypeInfo,
+};
+use mongo_odbc_core::MongoTypesInfo;
+use odbc_sys::{CDataType, Handle, HandleType, Len, Pointer, SmallInt, SqlDataType, SqlReturn};
+use std::ptr::null_mut;
+
+const SQL_CLOSE: SmallInt = 0;
+const SQL_DROP: SmallInt = 1;
+const SQL_UNBIND: SmallInt = 2;
+const SQL_RESET_PARAMS: SmallInt = 3;
+
+#[test]
+fn close_cursor() {
+    let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+    let stmt = stmt_handle as *mut _;
+    assert_eq!(SqlReturn::ERROR, SQLCloseCursor(stmt));
+    assert_eq!("24000", sql_state(stmt_handle));
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt, SqlDataType::UNKNOWN_TYPE)
+    );
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt));
+    assert_eq!(SqlReturn::SUCCESS, SQLCloseCursor(stmt));
+    {
+        let stmt_contents = statement(stmt_handle).read().unwrap();
+        assert_eq!(StatementState::Allocated, stmt_contents.state);
+        assert!(stmt_contents.mongo_statement.is_none());
+    }
+    assert_eq!(SqlReturn::ERROR, SQLCloseCursor(stmt));
+    assert_eq!("24000", sql_state(stmt_handle));
+
+    // A prepared statement keeps its result set metadata.
+    {
+        let mut stmt_contents = statement(stmt_handle).write().unwrap();
+        stmt_contents.is_prepared = true;
+        stmt_contents.mongo_statement = Some(Box::new(MongoTypesInfo::list_types_info(
+            SqlDataType::UNKNOWN_TYPE,
+        )));
+        stmt_contents.state = StatementState::CursorFetchSet;
+    }
+    assert_eq!(SqlReturn::SUCCESS, SQLCloseCursor(stmt));
+    {
+        let stmt_contents = statement(stmt_handle).read().unwrap();
+        assert_eq!(StatementState::PreparedHasResultSet, stmt_contents.state);
+        assert!(stmt_contents.mongo_statement.is_some());
+    }
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn free_stmt_close() {
+    let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+    let stmt = stmt_handle as *mut _;
+    // Closing a statement without a cursor has no effect.
+    assert_eq!(SqlReturn::SUCCESS, SQLFreeStmt(stmt, SQL_CLOSE));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt, SqlDataType::UNKNOWN_TYPE)
+    );
+    assert_eq!(SqlReturn::SUCCESS, SQLFreeStmt(stmt, SQL_CLOSE));
+    assert_eq!(
+        StatementState::Allocated,
+        statement(stmt_handle).read().unwrap().state
+    );
+    assert_eq!(SqlReturn::ERROR, SQLFetch(stmt));
+    assert_eq!("HY010", sql_state(stmt_handle));
+    // The statement can be reused once closed.
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt, SqlDataType::UNKNOWN_TYPE)
+    );
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn free_stmt_unbind_and_reset_params() {
+    let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+    let stmt = stmt_handle as *mut _;
+    let mut value = 0i64;
+    let mut indicator: Len = 0;
+    for col_number in [1, 3] {
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLBindCol(
+                stmt,
+                col_number,
+                CDataType::SBigInt,
+                &mut value as *mut i64 as Pointer,
+                0,
+                &mut indicator,
+            )
+        );
+    }
+    statement(stmt_handle)
+        .read()
+        .unwrap()
+        .apd()
+        .unwrap()
+        .write()
+        .unwrap()
+        .record_mut(2)
+        .data_ptr = &mut value as *mut i64 as Pointer;
+
+    assert_eq!(SqlReturn::SUCCESS, SQLFreeStmt(stmt, SQL_UNBIND));
+    {
+        let stmt_contents = statement(stmt_handle).read().unwrap();
+        assert!(stmt_contents
+            .ard()
+            .unwrap()
+            .read()
+            .unwrap()
+            .records
+            .is_empty());
+        assert_eq!(
+            2,
+            stmt_contents.apd().unwrap().read().unwrap().records.len()
+        );
+    }
+    assert_eq!(SqlReturn::SUCCESS, SQLFreeStmt(stmt, SQL_RESET_PARAMS));
+    assert!(statement(stmt_handle)
+        .read()
+        .unwrap()
+        .apd()
+        .unwrap()
+        .read()
+        .unwrap()
+        .records
+        .is_empty());
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn free_stmt_invalid_attached_descriptor() {
+    let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+    let stmt = stmt_handle as *mut _;
+    let implicit_app_row_desc = {
+        let mut stmt_contents = statement(stmt_handle).write().unwrap();
+        let implicit_app_row_desc = stmt_contents.attributes.app_row_desc;
+        stmt_contents.attributes.app_row_desc = stmt_handle;
+        implicit_app_row_desc
+    };
+    assert_eq!(SqlReturn::ERROR, SQLFreeStmt(stmt, SQL_UNBIND));
+    assert_eq!("HY000", sql_state(stmt_handle));
+
+    statement(stmt_handle)
+        .write()
+        .unwrap()
+        .attributes
+        .app_row_desc = implicit_app_row_desc;
+    assert_eq!(SqlReturn::SUCCESS, SQLFreeStmt(stmt, SQL_UNBIND));
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn free_stmt_drop() {
+    let env_handle = allocate(HandleType::Env, null_mut());
+    let conn_handle = open_connection(env_handle);
+    let stmt = allocate(HandleType::Stmt, conn_handle) as Handle;
+
+    assert_eq!(SqlReturn::ERROR, SQLFreeStmt(stmt as *mut _, 7));
+    assert_eq!("HY092", sql_state(stmt as *mut _));
+    // SQL_DROP frees the statement like SQLFreeHandle.
+    assert_eq!(SqlReturn::SUCCESS, SQLFreeStmt(stmt as *mut _, SQL_DROP));
+    assert!(MongoHandle::from_live(stmt, HandleType::Stmt).is_none());
+    assert!(connection(conn_handle)
+        .read()
+        .unwrap()
+        .statements
+        .is_empty());
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLFreeStmt(stmt as *mut _, SQL_CLOSE)
+    );
+    free_handles(&[conn_handle, env_handle]);
+}
@@ -1,19 +1,41 @@
 use crate::{
     api::{
+        data::{c_type_from_code, default_c_type, format_cell, is_supported_c_type},
         definitions::*,
+        dsn::dsn_to_odbc_uri,
+        encoding::Charset,
         errors::ODBCError,
-        functions::util::{set_str_length, unsupported_function},
+        functions::util::{
+            input_text_argument, input_text_to_string, input_wtext_argument, input_wtext_to_string,
+            panic_safe_exec, set_output_array, set_output_string, set_output_value,
+            set_output_wstring, set_str_length, unsupported_function,
+        },
+        info::{odbc_version, static_info, InfoValue, DBMS_NAME},
+        odbc_uri::{ODBCUri, CHARSET, DSN, PWD, SERVER, USER},
+        supported_functions::{
+            find_function, odbc2_functions_array, odbc3_functions_bitmap, SQL_API_ALL_FUNCTIONS,
+            SQL_API_ODBC3_ALL_FUNCTIONS,
+        },
     },
     handles::definitions::*,
 };
+use mongo_odbc_core::{
+    escape_pattern, MongoCollections, MongoConnection, MongoDatabases, MongoFields,
+    MongoPrimaryKeys, MongoQuery, MongoSpecialColumns, MongoStatement, MongoStatistics,
+    MongoTableTypes, MongoTypesInfo,
+};
 use num_traits::FromPrimitive;
 use odbc_sys::{
     BulkOperation, CDataType, Char, CompletionType, ConnectionAttribute, Desc, DriverConnectOption,
     EnvironmentAttribute, FetchOrientation, HDbc, HDesc, HEnv, HStmt, HWnd, Handle, HandleType,
-    InfoType, Integer, Len, Nullability, ParamType, Pointer, RetCode, SmallInt, SqlDataType,
-    SqlReturn, StatementAttribute, ULen, USmallInt, WChar,
+    Integer, Len, Nullability, ParamType, Pointer, RetCode, SmallInt, SqlDataType, SqlReturn,
+    StatementAttribute, ULen, USmallInt, WChar,
+};
+use std::{
+    mem::size_of,
+    ptr::null_mut,
+    sync::{PoisonError, RwLock},
 };
-use std::{mem::size_of, sync::RwLock};
 
 #[no_mangle]
 pub extern "C" fn SQLAllocHandle(
@@ -21,9 +43,32 @@ pub extern "C" fn SQLAllocHandle(
     input_handle: Handle,
     output_handle: *mut Handle,
 ) -> SqlReturn {
-    match sql_alloc_handle(handle_type, input_handle as *mut _, output_handle) {
+    let alloc = || match sql_alloc_handle(handle_type, input_handle as *mut _, output_handle) {
         Ok(_) => SqlReturn::SUCCESS,
         Err(_) => SqlReturn::INVALID_HANDLE,
+    };
+    match handle_type {
+        // An environment is allocated without an input handle to check or report errors on.
+        HandleType::Env => alloc(),
+        HandleType::Dbc => panic_safe_exec("SQLAllocHandle", input_handle, HandleType::Env, alloc),
+        // Statements and descriptors can only be allocated on an open connection.
+        _ => panic_safe_exec("SQLAllocHandle", input_handle, HandleType::Dbc, || {
+            let conn_handle = MongoHandleRef::from(input_handle);
+            conn_handle.clear_diagnostics();
+            let conn_state = conn_handle
+                .as_connection()
+                .unwrap()
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .state;
+            match conn_state {
+                ConnectionState::Allocated | ConnectionState::_ConnectionFunctionNeedsDataEnv => {
+                    conn_handle.add_diag_info(ODBCError::ConnectionNotOpen);
+                    SqlReturn::ERROR
+                }
+                _ => alloc(),
+            }
+        }),
     }
 }
 
@@ -35,9 +80,8 @@ fn sql_alloc_handle(
     match handle_type {
         HandleType::Env => {
             let env = RwLock::new(Env::with_state(EnvState::Allocated));
-            let mh = Box::new(MongoHandle::Env(env));
             unsafe {
-                *output_handle = Box::into_raw(mh) as *mut _;
+                *output_handle = MongoHandle::Env(env).into_raw() as *mut _;
             }
             Ok(())
         }
@@ -52,9 +96,8 @@ fn sql_alloc_handle(
                 input_handle,
                 ConnectionState::Allocated,
             ));
-            let mut env_contents = (*env).write().unwrap();
-            let mh = Box::new(MongoHandle::Connection(conn));
-            let mh_ptr = Box::into_raw(mh);
+            let mut env_contents = (*env).write().unwrap_or_else(PoisonError::into_inner);
+            let mh_ptr = MongoHandle::Connection(conn).into_raw();
             env_contents.connections.insert(mh_ptr);
             env_contents.state = EnvState::ConnectionAllocated;
             unsafe { *output_handle = mh_ptr as *mut _ }
@@ -71,30 +114,113 @@ fn sql_alloc_handle(
                 input_handle,
                 StatementState::Allocated,
             ));
-            let mut conn_contents = (*conn).write().unwrap();
-            let mh = Box::new(MongoHandle::Statement(stmt));
-            let mh_ptr = Box::into_raw(mh);
+            let mut conn_contents = (*conn).write().unwrap_or_else(PoisonError::into_inner);
+            let mh_ptr = MongoHandle::Statement(stmt).into_raw();
+            // The implicit descriptors refer back to their statement, which only has an address
+            // once boxed.
+            let stmt_contents = unsafe {
+                (*mh_ptr)
+                    .as_statement()
+                    .unwrap()
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+            };
+            for desc in stmt_contents
+                .implicit_descriptors()
+                .into_iter()
+                .filter_map(|desc| unsafe { (*desc).as_descriptor() })
+            {
+                desc.write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .statement = mh_ptr;
+            }
             conn_contents.statements.insert(mh_ptr);
             conn_contents.state = ConnectionState::StatementAllocated;
             unsafe { *output_handle = mh_ptr as *mut _ }
             Ok(())
         }
         HandleType::Desc => {
-            unimplemented!();
+            // input handle cannot be NULL
+            if input_handle.is_null() {
+                return Err(());
+            }
+            // input handle must be a Connection
+            let conn = unsafe { (*input_handle).as_connection().ok_or(())? };
+            let mh_ptr =
+                Descriptor::allocate(input_handle, DescriptorKind::Application, AllocType::User);
+            conn.write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .descriptors
+                .insert(mh_ptr);
+            unsafe { *output_handle = mh_ptr as *mut _ }
+            Ok(())
         }
     }
 }
 
 #[no_mangle]
 pub extern "C" fn SQLBindCol(
-    _hstmt: HStmt,
-    _col_number: USmallInt,
-    _target_type: CDataType,
-    _target_value: Pointer,
-    _buffer_length: Len,
-    _length_or_indicatior: *mut Len,
+    hstmt: HStmt,
+    col_number: USmallInt,
+    target_type: CDataType,
+    target_value: Pointer,
+    buffer_length: Len,
+    length_or_indicatior: *mut Len,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec("SQLBindCol", hstmt as Handle, HandleType::Stmt, || {
+        let stmt_handle = MongoHandleRef::from(hstmt);
+        stmt_handle.clear_diagnostics();
+        let stmt = match stmt_handle.as_statement() {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(stmt) => stmt,
+        };
+        match sql_bind_col(
+            stmt,
+            col_number,
+            BoundColInfo {
+                target_type,
+                target_buffer: target_value,
+                buffer_length,
+                length_or_indicator: length_or_indicatior,
+            },
+        ) {
+            Ok(_) => SqlReturn::SUCCESS,
+            Err(error) => {
+                stmt_handle.add_diag_info(error);
+                SqlReturn::ERROR
+            }
+        }
+    })
+}
+
+/// sql_bind_col binds the given buffers to the column [`col_number`], or unbinds the column if
+/// the target buffer is null, by setting the record of the column in the application row
+/// descriptor. Bookmark columns are not supported.
+fn sql_bind_col(
+    stmt: &RwLock<Statement>,
+    col_number: USmallInt,
+    bound_col: BoundColInfo,
+) -> Result<(), ODBCError> {
+    let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+    let mut ard = stmt_contents
+        .ard()?
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if col_number == 0 {
+        return Err(ODBCError::InvalidDescriptorIndex(col_number.into()));
+    }
+    if bound_col.target_buffer.is_null() {
+        ard.unbind(col_number);
+        return Ok(());
+    }
+    if !is_supported_c_type(bound_col.target_type) {
+        return Err(ODBCError::InvalidTargetType(bound_col.target_type));
+    }
+    if bound_col.buffer_length < 0 {
+        return Err(ODBCError::InvalidBufferLength(bound_col.buffer_length));
+    }
+    ard.record_mut(col_number).bind(&bound_col);
+    Ok(())
 }
 
 #[no_mangle]
@@ -110,7 +236,12 @@ pub extern "C" fn SQLBindParameter(
     _buffer_length: Len,
     _str_len_or_ind_ptr: *mut Len,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(hstmt), "SQLBindParameter")
+    panic_safe_exec(
+        "SQLBindParameter",
+        hstmt as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(hstmt), "SQLBindParameter"),
+    )
 }
 
 #[no_mangle]
@@ -122,19 +253,29 @@ pub extern "C" fn SQLBrowseConnect(
     _buffer_length: SmallInt,
     _out_buffer_length: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(connection_handle), "SQLBrowseConnect")
+    panic_safe_exec(
+        "SQLBrowseConnect",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || unsupported_function(MongoHandleRef::from(connection_handle), "SQLBrowseConnect"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLBrowseConnectW(
-    _connection_handle: HDbc,
+    connection_handle: HDbc,
     _in_connection_string: *const WChar,
     _string_length: SmallInt,
     _out_connection_string: *mut WChar,
     _buffer_length: SmallInt,
     _out_buffer_length: *mut SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLBrowseConnectW",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || unsupported_function(MongoHandleRef::from(connection_handle), "SQLBrowseConnectW"),
+    )
 }
 
 #[no_mangle]
@@ -142,48 +283,271 @@ pub extern "C" fn SQLBulkOperations(
     statement_handle: HStmt,
     _operation: BulkOperation,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLBulkOperations")
+    panic_safe_exec(
+        "SQLBulkOperations",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLBulkOperations"),
+    )
 }
 
 #[no_mangle]
-pub extern "C" fn SQLCancel(_statement_handle: HStmt) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLCancel(statement_handle: HStmt) -> SqlReturn {
+    panic_safe_exec(
+        "SQLCancel",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLCancel"),
+    )
 }
 
 #[no_mangle]
-pub extern "C" fn SQLCancelHandle(_handle_type: HandleType, _handle: Handle) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLCancelHandle(handle_type: HandleType, handle: Handle) -> SqlReturn {
+    panic_safe_exec("SQLCancelHandle", handle, handle_type, || {
+        unsupported_function(MongoHandleRef::from(handle), "SQLCancelHandle")
+    })
 }
 
 #[no_mangle]
-pub extern "C" fn SQLCloseCursor(_statement_handle: HStmt) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLCloseCursor(statement_handle: HStmt) -> SqlReturn {
+    panic_safe_exec(
+        "SQLCloseCursor",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            match sql_close_cursor(stmt) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_close_cursor closes the cursor of the statement and discards its pending results, which
+/// kills the cursor on the server. Unlike SQLFreeStmt with SQL_CLOSE, it fails with 24000 when
+/// no cursor is open.
+fn sql_close_cursor(stmt: &RwLock<Statement>) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::CloseCursor)?;
+    stmt_contents.close_cursor();
+    Ok(())
 }
 
 #[no_mangle]
 pub extern "C" fn SQLColAttribute(
     statement_handle: HStmt,
-    _column_number: USmallInt,
-    _field_identifier: Desc,
-    _character_attribute_ptr: Pointer,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
-    _numeric_attribute_ptr: *mut Len,
+    column_number: USmallInt,
+    field_identifier: Desc,
+    character_attribute_ptr: Pointer,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
+    numeric_attribute_ptr: *mut Len,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLColAttribute")
+    panic_safe_exec(
+        "SQLColAttribute",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let charset = stmt_handle.charset();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            match sql_col_attribute(stmt, column_number, field_identifier) {
+                Ok(ColumnAttribute::Character(value)) => {
+                    let sql_return = set_output_string(
+                        &value,
+                        character_attribute_ptr as *mut Char,
+                        buffer_length.max(0) as usize,
+                        string_length_ptr,
+                        charset,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        stmt_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Ok(ColumnAttribute::Numeric(value)) => {
+                    set_output_value(numeric_attribute_ptr, value);
+                    SqlReturn::SUCCESS
+                }
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLColAttributeW(
-    _statement_handle: HStmt,
-    _column_number: USmallInt,
-    _field_identifier: Desc,
-    _character_attribute_ptr: Pointer,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
-    _numeric_attribute_ptr: *mut Len,
+    statement_handle: HStmt,
+    column_number: USmallInt,
+    field_identifier: Desc,
+    character_attribute_ptr: Pointer,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
+    numeric_attribute_ptr: *mut Len,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLColAttributeW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            match sql_col_attribute(stmt, column_number, field_identifier) {
+                Ok(ColumnAttribute::Character(value)) => {
+                    // The buffer length and the string length are in bytes, not in characters.
+                    let mut string_length = 0;
+                    let sql_return = set_output_wstring(
+                        &value,
+                        character_attribute_ptr as *mut WChar,
+                        buffer_length.max(0) as usize / size_of::<WChar>(),
+                        &mut string_length,
+                    );
+                    set_output_value(
+                        string_length_ptr,
+                        string_length * size_of::<WChar>() as SmallInt,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        stmt_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Ok(ColumnAttribute::Numeric(value)) => {
+                    set_output_value(numeric_attribute_ptr, value);
+                    SqlReturn::SUCCESS
+                }
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// ColumnAttribute is the value of a column attribute: character attributes are returned in
+/// the CharacterAttributePtr buffer and numeric attributes in NumericAttributePtr.
+enum ColumnAttribute {
+    Character(String),
+    Numeric(Len),
+}
+
+/// sql_col_attribute returns the [`field_identifier`] attribute of the column [`column_number`]
+/// of the statement's result set, which is the field of the column's record in the
+/// implementation row descriptor.
+fn sql_col_attribute(
+    stmt: &RwLock<Statement>,
+    column_number: USmallInt,
+    field_identifier: Desc,
+) -> Result<ColumnAttribute, ODBCError> {
+    let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::DescribeResultSet("SQLColAttribute"))?;
+    let mongo_statement = stmt_contents
+        .mongo_statement
+        .as_ref()
+        .ok_or(ODBCError::FunctionSequenceError("SQLColAttribute"))?;
+    ird_field(&**mongo_statement, column_number, field_identifier)
+}
+
+/// ird_field returns the field [`field_identifier`] of the record [`column_number`] of the
+/// implementation row descriptor of a result set, from the column metadata. SQL_DESC_COUNT is
+/// the number of columns of the result set and ignores the column number.
+/// Bookmarks are not supported, so column 0 is an invalid index.
+fn ird_field(
+    mongo_statement: &dyn MongoStatement,
+    column_number: USmallInt,
+    field_identifier: Desc,
+) -> Result<ColumnAttribute, ODBCError> {
+    if field_identifier == Desc::Count {
+        return Ok(ColumnAttribute::Numeric(
+            mongo_statement._get_col_count() as Len
+        ));
+    }
+    if column_number == 0 {
+        return Err(ODBCError::InvalidDescriptorIndex(column_number.into()));
+    }
+    let col = mongo_statement
+        ._get_col_metadata(column_number)
+        .map_err(ODBCError::Core)?;
+    let string = |value: &str| Ok(ColumnAttribute::Character(value.to_string()));
+    let numeric = |value: Len| Ok(ColumnAttribute::Numeric(value));
+    match field_identifier {
+        Desc::AutoUniqueValue => numeric(SqlBool::False as Len),
+        Desc::BaseColumnName => string(&col.base_col_name),
+        Desc::BaseTableName => string(&col.base_table_name),
+        Desc::CaseSensitive => numeric(col.is_case_sensitive as Len),
+        Desc::CatalogName => string(&col.catalog_name),
+        Desc::ConciseType => numeric(col.sql_type.0 as Len),
+        Desc::DisplaySize => numeric(col.display_size as Len),
+        Desc::FixedPrecScale => numeric(col.fixed_prec_scale as Len),
+        Desc::Label => string(&col.label),
+        Desc::Length => numeric(col.length as Len),
+        Desc::LiteralPrefix => string(col.literal_prefix.unwrap_or_default()),
+        Desc::LiteralSuffix => string(col.literal_suffix.unwrap_or_default()),
+        Desc::LocalTypeName => string(""),
+        Desc::Name => string(&col.col_name),
+        Desc::Nullable => numeric(if col.is_nullable {
+            Nullability::NULLABLE.0 as Len
+        } else {
+            Nullability::NO_NULLS.0 as Len
+        }),
+        Desc::NumPrecRadix => numeric(if col.is_numeric { 10 } else { 0 }),
+        Desc::OctetLength => numeric(col.octet_length as Len),
+        Desc::Precision => numeric(col.precision as Len),
+        Desc::Scale => numeric(col.scale as Len),
+        Desc::SchemaName => string(""),
+        Desc::Searchable => numeric(col.searchable as Len),
+        Desc::TableName => string(&col.table_name),
+        // The verbose type of the datetime types is SQL_DATETIME, and their concise type is
+        // given by the datetime interval code.
+        Desc::Type => numeric(match col.sql_type {
+            SqlDataType::TIMESTAMP => SqlDataType::DATETIME.0 as Len,
+            sql_type => sql_type.0 as Len,
+        }),
+        Desc::DatetimeIntervalCode => numeric(match col.sql_type {
+            SqlDataType::TIMESTAMP => SQL_CODE_TIMESTAMP,
+            _ => 0,
+        }),
+        Desc::TypeName => string(&col.type_name),
+        Desc::Unnamed => numeric(if col.col_name.is_empty() {
+            Unnamed::Unnamed as Len
+        } else {
+            Unnamed::Named as Len
+        }),
+        Desc::Unsigned => numeric(col.is_unsigned as Len),
+        Desc::Updatable => numeric(if col.is_updatable {
+            Updatable::Write as Len
+        } else {
+            Updatable::ReadOnly as Len
+        }),
+        // The other fields are not fields of the implementation row descriptor.
+        _ => Err(ODBCError::InvalidFieldIdentifier(
+            field_identifier as SmallInt,
+        )),
+    }
 }
 
 #[no_mangle]
@@ -198,15 +562,22 @@ pub extern "C" fn SQLColumnPrivileges(
     _column_name: *const Char,
     _column_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(
-        MongoHandleRef::from(statement_handle),
+    panic_safe_exec(
         "SQLColumnPrivileges",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            unsupported_function(
+                MongoHandleRef::from(statement_handle),
+                "SQLColumnPrivileges",
+            )
+        },
     )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLColumnPrivilegesW(
-    _statement_handle: HStmt,
+    statement_handle: HStmt,
     _catalog_name: *const WChar,
     _catalog_name_length: SmallInt,
     _schema_name: *const WChar,
@@ -216,77 +587,332 @@ pub extern "C" fn SQLColumnPrivilegesW(
     _column_name: *const WChar,
     _column_name_length: SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLColumnPrivilegesW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            unsupported_function(
+                MongoHandleRef::from(statement_handle),
+                "SQLColumnPrivilegesW",
+            )
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLColumns(
     statement_handle: HStmt,
-    _catalog_name: *const Char,
-    _catalog_name_length: SmallInt,
+    catalog_name: *const Char,
+    catalog_name_length: SmallInt,
     _schema_name: *const Char,
     _schema_name_length: SmallInt,
-    _table_name: *const Char,
-    _table_name_length: SmallInt,
-    _column_name: *const Char,
-    _column_name_length: SmallInt,
+    table_name: *const Char,
+    table_name_length: SmallInt,
+    column_name: *const Char,
+    column_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLColumns")
+    panic_safe_exec(
+        "SQLColumns",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, charset| {
+                let argument = |text, text_length| input_text_argument(text, text_length, charset);
+                sql_columns(
+                    stmt,
+                    argument(catalog_name, catalog_name_length),
+                    argument(table_name, table_name_length),
+                    argument(column_name, column_name_length),
+                )
+            })
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLColumnsW(
-    _statement_handle: HStmt,
-    _catalog_name: *const WChar,
-    _catalog_name_length: SmallInt,
+    statement_handle: HStmt,
+    catalog_name: *const WChar,
+    catalog_name_length: SmallInt,
     _schema_name: *const WChar,
     _schema_name_length: SmallInt,
-    _table_name: *const WChar,
-    _table_name_length: SmallInt,
-    _column_name: *const WChar,
-    _column_name_length: SmallInt,
+    table_name: *const WChar,
+    table_name_length: SmallInt,
+    column_name: *const WChar,
+    column_name_length: SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLColumnsW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, _| {
+                sql_columns(
+                    stmt,
+                    input_wtext_argument(catalog_name, catalog_name_length),
+                    input_wtext_argument(table_name, table_name_length),
+                    input_wtext_argument(column_name, column_name_length),
+                )
+            })
+        },
+    )
+}
+
+/// sql_columns stores on the statement the SQLColumns result set listing the fields of the
+/// collections of the database [`catalog_name`] matching the [`table_name`] search pattern, whose
+/// names match the [`column_name`] search pattern. A [`None`] argument was passed as a null
+/// pointer and doesn't restrict the result. The schema name is ignored since MongoDB has no
+/// schemas.
+fn sql_columns(
+    stmt: &RwLock<Statement>,
+    catalog_name: Option<String>,
+    table_name: Option<String>,
+    column_name: Option<String>,
+) -> Result<(), ODBCError> {
+    // The catalog name is not a search pattern.
+    let db_name_filter = catalog_name.map_or(SQL_ALL.to_string(), |catalog_name| {
+        escape_pattern(&catalog_name)
+    });
+    exec_catalog_function(stmt, "SQLColumns", |mongo_connection, query_timeout| {
+        MongoFields::list_columns(
+            mongo_connection,
+            query_timeout,
+            &db_name_filter,
+            table_name.as_deref().unwrap_or(SQL_ALL),
+            column_name.as_deref().unwrap_or(SQL_ALL),
+        )
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLCompleteAsync(
-    _handle_type: HandleType,
+    handle_type: HandleType,
     handle: Handle,
     _async_ret_code_ptr: *mut RetCode,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(handle), "SQLCompleteAsync")
+    panic_safe_exec("SQLCompleteAsync", handle, handle_type, || {
+        unsupported_function(MongoHandleRef::from(handle), "SQLCompleteAsync")
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLConnect(
     connection_handle: HDbc,
-    _server_name: *const Char,
-    _name_length_1: SmallInt,
-    _user_name: *const Char,
-    _name_length_2: SmallInt,
-    _authentication: *const Char,
-    _name_length_3: SmallInt,
+    server_name: *const Char,
+    name_length_1: SmallInt,
+    user_name: *const Char,
+    name_length_2: SmallInt,
+    authentication: *const Char,
+    name_length_3: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(connection_handle), "SQLConnect")
+    panic_safe_exec(
+        "SQLConnect",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            let charset = conn_handle.charset();
+            let conn = match conn_handle.as_connection() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(conn) => conn,
+            };
+            let dsn = input_text_to_string(server_name, name_length_1 as isize, charset);
+            let user = input_text_to_string(user_name, name_length_2 as isize, charset);
+            let pwd = input_text_to_string(authentication, name_length_3 as isize, charset);
+            match sql_connect(conn, &dsn, &user, &pwd) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLConnectW(
     connection_handle: HDbc,
-    _server_name: *const WChar,
-    _name_length_1: SmallInt,
-    _user_name: *const WChar,
-    _name_length_2: SmallInt,
-    _authentication: *const WChar,
-    _name_length_3: SmallInt,
+    server_name: *const WChar,
+    name_length_1: SmallInt,
+    user_name: *const WChar,
+    name_length_2: SmallInt,
+    authentication: *const WChar,
+    name_length_3: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(connection_handle), "SQLConnectW")
+    panic_safe_exec(
+        "SQLConnectW",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            let conn = match conn_handle.as_connection() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(conn) => conn,
+            };
+            let dsn = input_wtext_to_string(server_name, name_length_1 as isize);
+            let user = input_wtext_to_string(user_name, name_length_2 as isize);
+            let pwd = input_wtext_to_string(authentication, name_length_3 as isize);
+            match sql_connect(conn, &dsn, &user, &pwd) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_connect looks up the given DSN in the odbc.ini files and connects to the server it
+/// describes. The user name and password, if provided, override the ones set in the DSN.
+fn sql_connect(
+    conn: &RwLock<Connection>,
+    dsn: &str,
+    user: &str,
+    pwd: &str,
+) -> Result<(), ODBCError> {
+    let mut odbc_uri = dsn_to_odbc_uri(dsn, user, pwd)?;
+    odbc_uri.set(DSN, dsn.to_string());
+    connect(conn, &odbc_uri)
+}
+
+/// connect creates a new MongoConnection from the given connection attributes and stores it on
+/// the connection. The connection must not already be connected.
+fn connect(conn: &RwLock<Connection>, odbc_uri: &ODBCUri) -> Result<(), ODBCError> {
+    let mut conn_contents = conn.write().unwrap_or_else(PoisonError::into_inner);
+    if matches!(
+        conn_contents.state,
+        ConnectionState::Connected | ConnectionState::StatementAllocated
+    ) {
+        return Err(ODBCError::ConnectionInUse);
+    }
+    let mongo_uri = odbc_uri.to_mongo_uri()?;
+    let charset = match odbc_uri.get(CHARSET) {
+        None => Charset::default(),
+        Some(name) => Charset::from_name(name).ok_or_else(|| {
+            ODBCError::InvalidConnectionString(format!("unsupported charset '{}'", name))
+        })?,
+    };
+    let current_db = conn_contents.attributes.current_db.clone();
+    let mongo_connection = MongoConnection::connect(
+        &mongo_uri,
+        current_db.as_deref(),
+        conn_contents.attributes.connection_timeout,
+        conn_contents.attributes.login_timeout,
+    )
+    .map_err(ODBCError::Core)?;
+    conn_contents.mongo_connection = Some(mongo_connection);
+    conn_contents.data_source = DataSource {
+        name: odbc_uri.get(DSN).unwrap_or_default().to_string(),
+        server: odbc_uri.get(SERVER).unwrap_or_default().to_string(),
+        user: odbc_uri.get(USER).unwrap_or_default().to_string(),
+    };
+    conn_contents.charset = charset;
+    conn_contents.state = ConnectionState::Connected;
+    Ok(())
 }
 
 #[no_mangle]
-pub extern "C" fn SQLCopyDesc(_source_desc_handle: HDesc, _target_desc_handle: HDesc) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLCopyDesc(source_desc_handle: HDesc, target_desc_handle: HDesc) -> SqlReturn {
+    panic_safe_exec(
+        "SQLCopyDesc",
+        target_desc_handle as Handle,
+        HandleType::Desc,
+        || {
+            let target_handle = MongoHandleRef::from(target_desc_handle);
+            target_handle.clear_diagnostics();
+            let source =
+                match MongoHandle::from_live(source_desc_handle as Handle, HandleType::Desc)
+                    .and_then(|source| source.as_descriptor())
+                {
+                    None => return SqlReturn::INVALID_HANDLE,
+                    Some(source) => source,
+                };
+            let target = match target_handle.as_descriptor() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(target) => target,
+            };
+            match sql_copy_desc(source, target) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    target_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_copy_desc copies the header fields, except SQL_DESC_ALLOC_TYPE, and the records of the
+/// [`source`] descriptor to the [`target`] descriptor. The records copied from an implementation
+/// row descriptor describe the columns of its statement's result set.
+fn sql_copy_desc(
+    source: &RwLock<Descriptor>,
+    target: &RwLock<Descriptor>,
+) -> Result<(), ODBCError> {
+    if std::ptr::eq(source, target) {
+        return Ok(());
+    }
+    let source = source.read().unwrap_or_else(PoisonError::into_inner);
+    let mut target = target.write().unwrap_or_else(PoisonError::into_inner);
+    if target.desc_kind == DescriptorKind::ImplementationRow {
+        return Err(ODBCError::CannotModifyIrd);
+    }
+    target.records = match source.desc_kind {
+        DescriptorKind::ImplementationRow => with_ird_result_set(&source, |mongo_statement| {
+            (1..=mongo_statement._get_col_count() as USmallInt)
+                .map(|column_number| ird_record(mongo_statement, column_number))
+                .collect::<Result<Vec<_>, _>>()
+        })?,
+        _ => source.records.clone(),
+    };
+    target.array_size = source.array_size;
+    target.array_status_ptr = source.array_status_ptr;
+    target.bind_offset_ptr = source.bind_offset_ptr;
+    target.bind_type = source.bind_type;
+    target.rows_processed_ptr = source.rows_processed_ptr;
+    Ok(())
+}
+
+/// with_ird_result_set calls [`f`] with the result set described by the implementation row
+/// descriptor [`desc`]. Fails with HY007 if its statement hasn't been executed.
+fn with_ird_result_set<T>(
+    desc: &Descriptor,
+    f: impl FnOnce(&dyn MongoStatement) -> Result<T, ODBCError>,
+) -> Result<T, ODBCError> {
+    let stmt = unsafe { desc.statement.as_ref() }
+        .and_then(MongoHandle::as_statement)
+        .ok_or(ODBCError::StatementNotExecuted)?;
+    let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+    let mongo_statement = stmt_contents
+        .mongo_statement
+        .as_ref()
+        .ok_or(ODBCError::StatementNotExecuted)?;
+    f(&**mongo_statement)
+}
+
+/// ird_record returns the record of the implementation row descriptor describing the column
+/// [`column_number`] of the result set.
+fn ird_record(
+    mongo_statement: &dyn MongoStatement,
+    column_number: USmallInt,
+) -> Result<DescRecord, ODBCError> {
+    let col = mongo_statement
+        ._get_col_metadata(column_number)
+        .map_err(ODBCError::Core)?;
+    let mut record = DescRecord {
+        octet_length: col.octet_length as Len,
+        length: col.length as ULen,
+        precision: col.precision as SmallInt,
+        scale: col.scale as SmallInt,
+        ..Default::default()
+    };
+    record.set_concise_type(col.sql_type.0);
+    Ok(record)
 }
 
 #[no_mangle]
@@ -300,7 +926,12 @@ pub extern "C" fn SQLDataSources(
     _buffer_length_2: SmallInt,
     _name_length_2: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(environment_handle), "SQLDataSources")
+    panic_safe_exec(
+        "SQLDataSources",
+        environment_handle as Handle,
+        HandleType::Env,
+        || unsupported_function(MongoHandleRef::from(environment_handle), "SQLDataSources"),
+    )
 }
 
 #[no_mangle]
@@ -314,37 +945,158 @@ pub extern "C" fn SQLDataSourcesW(
     _buffer_length_2: SmallInt,
     _name_length_2: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(environment_handle), "SQLDataSourcesW")
+    panic_safe_exec(
+        "SQLDataSourcesW",
+        environment_handle as Handle,
+        HandleType::Env,
+        || unsupported_function(MongoHandleRef::from(environment_handle), "SQLDataSourcesW"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLDescribeCol(
     hstmt: HStmt,
-    _col_number: USmallInt,
-    _col_name: *mut Char,
-    _buffer_length: SmallInt,
-    _name_length: *mut SmallInt,
-    _data_type: *mut SqlDataType,
-    _col_size: *mut ULen,
-    _decimal_digits: *mut SmallInt,
-    _nullable: *mut Nullability,
+    col_number: USmallInt,
+    col_name: *mut Char,
+    buffer_length: SmallInt,
+    name_length: *mut SmallInt,
+    data_type: *mut SqlDataType,
+    col_size: *mut ULen,
+    decimal_digits: *mut SmallInt,
+    nullable: *mut Nullability,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(hstmt), "SQLDescribeCol")
+    panic_safe_exec("SQLDescribeCol", hstmt as Handle, HandleType::Stmt, || {
+        let stmt_handle = MongoHandleRef::from(hstmt);
+        stmt_handle.clear_diagnostics();
+        let charset = stmt_handle.charset();
+        let stmt = match stmt_handle.as_statement() {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(stmt) => stmt,
+        };
+        match sql_describe_col(stmt, col_number) {
+            Ok(description) => {
+                description.write(data_type, col_size, decimal_digits, nullable);
+                let sql_return = set_output_string(
+                    &description.name,
+                    col_name,
+                    buffer_length.max(0) as usize,
+                    name_length,
+                    charset,
+                );
+                if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                    stmt_handle.add_diag_info(ODBCError::OutStringTruncated(
+                        buffer_length.max(0) as usize
+                    ));
+                }
+                sql_return
+            }
+            Err(error) => {
+                stmt_handle.add_diag_info(error);
+                SqlReturn::ERROR
+            }
+        }
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLDescribeColW(
-    _hstmt: HStmt,
-    _col_number: USmallInt,
-    _col_name: *mut WChar,
-    _buffer_length: SmallInt,
-    _name_length: *mut SmallInt,
-    _data_type: *mut SqlDataType,
-    _col_size: *mut ULen,
-    _decimal_digits: *mut SmallInt,
-    _nullable: *mut Nullability,
+    hstmt: HStmt,
+    col_number: USmallInt,
+    col_name: *mut WChar,
+    buffer_length: SmallInt,
+    name_length: *mut SmallInt,
+    data_type: *mut SqlDataType,
+    col_size: *mut ULen,
+    decimal_digits: *mut SmallInt,
+    nullable: *mut Nullability,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec("SQLDescribeColW", hstmt as Handle, HandleType::Stmt, || {
+        let stmt_handle = MongoHandleRef::from(hstmt);
+        stmt_handle.clear_diagnostics();
+        let stmt = match stmt_handle.as_statement() {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(stmt) => stmt,
+        };
+        match sql_describe_col(stmt, col_number) {
+            Ok(description) => {
+                description.write(data_type, col_size, decimal_digits, nullable);
+                let sql_return = set_output_wstring(
+                    &description.name,
+                    col_name,
+                    buffer_length.max(0) as usize,
+                    name_length,
+                );
+                if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                    stmt_handle.add_diag_info(ODBCError::OutStringTruncated(
+                        buffer_length.max(0) as usize
+                    ));
+                }
+                sql_return
+            }
+            Err(error) => {
+                stmt_handle.add_diag_info(error);
+                SqlReturn::ERROR
+            }
+        }
+    })
+}
+
+/// ColumnDescription is the description of a result set column returned by SQLDescribeCol.
+struct ColumnDescription {
+    name: String,
+    data_type: SqlDataType,
+    col_size: ULen,
+    decimal_digits: SmallInt,
+    nullable: Nullability,
+}
+
+impl ColumnDescription {
+    /// write writes the non-character fields of the description to the given pointers, skipping
+    /// the null ones.
+    fn write(
+        &self,
+        data_type: *mut SqlDataType,
+        col_size: *mut ULen,
+        decimal_digits: *mut SmallInt,
+        nullable: *mut Nullability,
+    ) {
+        set_output_value(data_type, self.data_type);
+        set_output_value(col_size, self.col_size);
+        set_output_value(decimal_digits, self.decimal_digits);
+        set_output_value(nullable, self.nullable);
+    }
+}
+
+/// sql_describe_col returns the description of the column [`col_number`] of the statement's
+/// result set, from the column metadata.
+/// Bookmarks are not supported, so column 0 is an invalid index.
+fn sql_describe_col(
+    stmt: &RwLock<Statement>,
+    col_number: USmallInt,
+) -> Result<ColumnDescription, ODBCError> {
+    let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::DescribeResultSet("SQLDescribeCol"))?;
+    let mongo_statement = stmt_contents
+        .mongo_statement
+        .as_ref()
+        .ok_or(ODBCError::FunctionSequenceError("SQLDescribeCol"))?;
+    if col_number == 0 {
+        return Err(ODBCError::InvalidDescriptorIndex(col_number.into()));
+    }
+    let col = mongo_statement
+        ._get_col_metadata(col_number)
+        .map_err(ODBCError::Core)?;
+    Ok(ColumnDescription {
+        name: col.col_name.clone(),
+        data_type: col.sql_type,
+        col_size: col.length as ULen,
+        decimal_digits: col.scale as SmallInt,
+        nullable: if col.is_nullable {
+            Nullability::NULLABLE
+        } else {
+            Nullability::NO_NULLS
+        },
+    })
 }
 
 #[no_mangle]
@@ -356,40 +1108,218 @@ pub extern "C" fn SQLDescribeParam(
     _decimal_digits_ptr: *mut SmallInt,
     _nullable_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLDescribeParam")
+    panic_safe_exec(
+        "SQLDescribeParam",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLDescribeParam"),
+    )
 }
 
 #[no_mangle]
-pub extern "C" fn SQLDisconnect(_connection_handle: HDbc) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLDisconnect(connection_handle: HDbc) -> SqlReturn {
+    panic_safe_exec(
+        "SQLDisconnect",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            let conn = match conn_handle.as_connection() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(conn) => conn,
+            };
+            match sql_disconnect(conn) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_disconnect closes the MongoConnection and frees all the statements and the explicitly
+/// allocated descriptors of the connection, which goes back to the allocated state so that it can
+/// be connected again.
+fn sql_disconnect(conn: &RwLock<Connection>) -> Result<(), ODBCError> {
+    let mut conn_contents = conn.write().unwrap_or_else(PoisonError::into_inner);
+    match conn_contents.state {
+        ConnectionState::Allocated | ConnectionState::_ConnectionFunctionNeedsDataEnv => {
+            return Err(ODBCError::ConnectionNotOpen)
+        }
+        ConnectionState::_TransactionInProgress => return Err(ODBCError::InvalidTransactionState),
+        ConnectionState::Connected | ConnectionState::StatementAllocated => {}
+    }
+    // A statement still executing or waiting for data must be completed or cancelled first.
+    let statement_busy = conn_contents.statements.iter().any(|stmt| unsafe {
+        (**stmt).as_statement().is_some_and(|stmt| {
+            matches!(
+                stmt.read().unwrap_or_else(PoisonError::into_inner).state,
+                StatementState::_Executing
+                    | StatementState::_FunctionNeedsDataNoParam
+                    | StatementState::_FunctionNeedsDataNoPut
+                    | StatementState::_FunctionNeedsDataPutCalled
+            )
+        })
+    });
+    if statement_busy {
+        return Err(ODBCError::FunctionSequenceError("SQLDisconnect"));
+    }
+    // Disconnecting implicitly frees all the statements and the explicitly allocated descriptors
+    // of the connection.
+    conn_contents.statements.drain().for_each(|stmt| {
+        MongoHandle::free(stmt);
+    });
+    conn_contents.descriptors.drain().for_each(|desc| {
+        MongoHandle::free(desc);
+    });
+    conn_contents.mongo_connection = None;
+    conn_contents.state = ConnectionState::Allocated;
+    Ok(())
 }
 
 #[no_mangle]
 pub extern "C" fn SQLDriverConnect(
     connection_handle: HDbc,
     _window_handle: HWnd,
-    _in_connection_string: *const Char,
-    _string_length_1: SmallInt,
-    _out_connection_string: *mut Char,
-    _buffer_length: SmallInt,
-    _string_length_2: *mut SmallInt,
-    _drive_completion: DriverConnectOption,
+    in_connection_string: *const Char,
+    string_length_1: SmallInt,
+    out_connection_string: *mut Char,
+    buffer_length: SmallInt,
+    string_length_2: *mut SmallInt,
+    driver_completion: DriverConnectOption,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(connection_handle), "SQLDriverConnect")
+    panic_safe_exec(
+        "SQLDriverConnect",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            let charset = conn_handle.charset();
+            let conn = match conn_handle.as_connection() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(conn) => conn,
+            };
+            if buffer_length < 0 {
+                conn_handle.add_diag_info(ODBCError::InvalidBufferLength(buffer_length as isize));
+                return SqlReturn::ERROR;
+            }
+            let odbc_uri_string =
+                input_text_to_string(in_connection_string, string_length_1 as isize, charset);
+            match sql_driver_connect(conn, &odbc_uri_string, driver_completion) {
+                Ok(normalized_uri) => {
+                    // The connection string can set the charset, which applies to its output.
+                    let sql_return = set_output_string(
+                        &normalized_uri,
+                        out_connection_string,
+                        buffer_length as usize,
+                        string_length_2,
+                        conn_handle.charset(),
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        conn_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLDriverConnectW(
-    _connection_handle: HDbc,
+    connection_handle: HDbc,
     _window_handle: HWnd,
-    _in_connection_string: *const WChar,
-    _string_length_1: SmallInt,
-    _out_connection_string: *mut WChar,
-    _buffer_length: SmallInt,
-    _string_length_2: *mut SmallInt,
-    _driver_completion: DriverConnectOption,
+    in_connection_string: *const WChar,
+    string_length_1: SmallInt,
+    out_connection_string: *mut WChar,
+    buffer_length: SmallInt,
+    string_length_2: *mut SmallInt,
+    driver_completion: DriverConnectOption,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLDriverConnectW",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            let conn = match conn_handle.as_connection() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(conn) => conn,
+            };
+            if buffer_length < 0 {
+                conn_handle.add_diag_info(ODBCError::InvalidBufferLength(buffer_length as isize));
+                return SqlReturn::ERROR;
+            }
+            let odbc_uri_string =
+                input_wtext_to_string(in_connection_string, string_length_1 as isize);
+            match sql_driver_connect(conn, &odbc_uri_string, driver_completion) {
+                Ok(normalized_uri) => {
+                    let sql_return = set_output_wstring(
+                        &normalized_uri,
+                        out_connection_string,
+                        buffer_length as usize,
+                        string_length_2,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        conn_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_driver_connect parses the given connection string, connects to the server it describes
+/// and returns the normalized connection string on success.
+/// If a DSN is specified, its attributes are used for the attributes missing from the connection
+/// string. Prompting the user is not supported, so the connection string must contain all the required
+/// attributes whatever the driver completion option is, and SQL_DRIVER_PROMPT is rejected.
+fn sql_driver_connect(
+    conn: &RwLock<Connection>,
+    odbc_uri_string: &str,
+    driver_completion: DriverConnectOption,
+) -> Result<String, ODBCError> {
+    match driver_completion {
+        DriverConnectOption::Prompt => return Err(ODBCError::Unimplemented("SQL_DRIVER_PROMPT")),
+        DriverConnectOption::NoPrompt
+        | DriverConnectOption::Complete
+        | DriverConnectOption::CompleteRequired => {}
+    }
+    let mut odbc_uri = ODBCUri::new(odbc_uri_string)?;
+    let pwd_from_dsn = odbc_uri.get(PWD).is_none();
+    // The attributes from the connection string take precedence over the ones from the DSN.
+    if let Some(dsn_odbc_uri) = odbc_uri
+        .get(DSN)
+        .map(|dsn| dsn_to_odbc_uri(dsn, "", ""))
+        .transpose()?
+    {
+        odbc_uri.set_defaults(dsn_odbc_uri);
+    }
+    connect(conn, &odbc_uri)?;
+    // The password stored in the DSN is not echoed back, the DSN provides it again when the
+    // returned connection string is used.
+    if pwd_from_dsn {
+        odbc_uri.remove(PWD);
+    }
+    Ok(odbc_uri.to_connection_string())
 }
 
 #[no_mangle]
@@ -403,7 +1333,9 @@ pub extern "C" fn SQLDrivers(
     _drvr_attr_max: SmallInt,
     _out_drvr_attr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(henv), "SQLDrivers")
+    panic_safe_exec("SQLDrivers", henv as Handle, HandleType::Env, || {
+        unsupported_function(MongoHandleRef::from(henv), "SQLDrivers")
+    })
 }
 
 #[no_mangle]
@@ -417,53 +1349,370 @@ pub extern "C" fn SQLDriversW(
     _drvr_attr_max: SmallInt,
     _out_drvr_attr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(henv), "SQLDriversW")
+    panic_safe_exec("SQLDriversW", henv as Handle, HandleType::Env, || {
+        unsupported_function(MongoHandleRef::from(henv), "SQLDriversW")
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLEndTran(
-    _handle_type: HandleType,
-    _handle: Handle,
+    handle_type: HandleType,
+    handle: Handle,
     _completion_type: CompletionType,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec("SQLEndTran", handle, handle_type, || {
+        unsupported_function(MongoHandleRef::from(handle), "SQLEndTran")
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLExecDirect(
     statement_handle: HStmt,
-    _statement_text: *const Char,
-    _text_length: Integer,
+    statement_text: *const Char,
+    text_length: Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLExecDirect")
+    panic_safe_exec(
+        "SQLExecDirect",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let charset = stmt_handle.charset();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            let query = input_text_to_string(statement_text, text_length as isize, charset);
+            match sql_exec_direct(stmt, &query) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLExecDirectW(
     statement_handle: HStmt,
-    _statement_text: *const WChar,
-    _text_length: Integer,
+    statement_text: *const WChar,
+    text_length: Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLExecDirectW")
+    panic_safe_exec(
+        "SQLExecDirectW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            let query = input_wtext_to_string(statement_text, text_length as isize);
+            match sql_exec_direct(stmt, &query) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_exec_direct executes the given MongoSQL query on the current database of the statement's
+/// connection and stores the resulting cursor on the statement. The statement attribute
+/// SQL_ATTR_QUERY_TIMEOUT is used as the maximum execution time of the query.
+fn sql_exec_direct(stmt: &RwLock<Statement>, query: &str) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::ExecDirect("SQLExecDirect"))?;
+    let conn = unsafe { stmt_contents.connection.as_ref() }
+        .and_then(MongoHandle::as_connection)
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    let conn_contents = conn.read().unwrap_or_else(PoisonError::into_inner);
+    let mongo_connection = conn_contents
+        .mongo_connection
+        .as_ref()
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    // A call failing before the query is sent leaves the previous result set untouched, while a
+    // query failing on the server discards it, as the statement transitions require.
+    stmt_contents.discard_result_set();
+    let query_timeout = stmt_contents.query_timeout();
+    stmt_contents.dynamic_function = DynamicFunction::SelectCursor;
+    let mongo_query =
+        MongoQuery::execute(mongo_connection, query_timeout, query).map_err(ODBCError::Core)?;
+    stmt_contents.mongo_statement = Some(Box::new(mongo_query));
+    stmt_contents.state = StatementState::ExecutedHasResultSet;
+    Ok(())
 }
 
 #[no_mangle]
 pub extern "C" fn SQLExecute(statement_handle: HStmt) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLExecute")
+    panic_safe_exec(
+        "SQLExecute",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            match sql_execute(stmt) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_execute runs the query prepared with SQLPrepare and opens a cursor on its result set,
+/// whose metadata is refreshed from the schema returned with the cursor. The statement stays
+/// prepared, so that it can be executed again once its cursor is closed.
+fn sql_execute(stmt: &RwLock<Statement>) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::Execute)?;
+    let conn = unsafe { stmt_contents.connection.as_ref() }
+        .and_then(MongoHandle::as_connection)
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    let conn_contents = conn.read().unwrap_or_else(PoisonError::into_inner);
+    let mongo_connection = conn_contents
+        .mongo_connection
+        .as_ref()
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    let query_timeout = stmt_contents.query_timeout();
+    stmt_contents
+        .mongo_statement
+        .as_mut()
+        .ok_or(ODBCError::FunctionSequenceError("SQLExecute"))?
+        .open_cursor(mongo_connection, query_timeout)
+        .map_err(ODBCError::Core)?;
+    stmt_contents.state = StatementState::ExecutedHasResultSet;
+    Ok(())
 }
 
 #[no_mangle]
-pub extern "C" fn SQLFetch(_statement_handle: HStmt) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLFetch(statement_handle: HStmt) -> SqlReturn {
+    panic_safe_exec(
+        "SQLFetch",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            match sql_fetch(stmt, "SQLFetch") {
+                Ok((sql_return, diagnostics)) => {
+                    diagnostics
+                        .into_iter()
+                        .for_each(|diagnostic| stmt_handle.add_diag_info(diagnostic));
+                    sql_return
+                }
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_fetch fetches the next rowset of SQL_ATTR_ROW_ARRAY_SIZE rows and writes the values of
+/// the bound columns, converted to their target C types, to their buffers, using column-wise or
+/// row-wise binding according to SQL_ATTR_ROW_BIND_TYPE and offsetting the buffers addresses by
+/// the value of SQL_ATTR_ROW_BIND_OFFSET_PTR. The status of each row is written to the row status
+/// array and the number of rows fetched to SQL_ATTR_ROWS_FETCHED_PTR.
+/// Returns SQL_NO_DATA once all the rows have been fetched, along with the diagnostics of the
+/// rows. A rowset where every row failed returns SQL_ERROR. [`function_name`] is SQLFetch or
+/// SQLFetchScroll, for the diagnostics.
+fn sql_fetch(
+    stmt: &RwLock<Statement>,
+    function_name: &'static str,
+) -> Result<(SqlReturn, Vec<ODBCError>), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::Fetch(function_name))?;
+    let stmt_contents = &mut *stmt_contents;
+    // The bindings are described by the application row descriptor, and the row statuses and
+    // the number of rows fetched are returned through the implementation row descriptor.
+    let (row_array_size, bind_offset, row_bind_type, bound_cols) = {
+        let ard = stmt_contents
+            .ard()?
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let bind_offset = if ard.bind_offset_ptr.is_null() {
+            0
+        } else {
+            unsafe { *ard.bind_offset_ptr }
+        };
+        let bound_cols = ard.bound_cols().collect::<Vec<_>>();
+        (
+            ard.array_size.max(1),
+            bind_offset,
+            ard.bind_type,
+            bound_cols,
+        )
+    };
+    let (row_status_ptr, rows_fetched_ptr) = {
+        let ird = stmt_contents
+            .ird()?
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        (ird.array_status_ptr, ird.rows_processed_ptr)
+    };
+    let mongo_statement = stmt_contents
+        .mongo_statement
+        .as_mut()
+        .ok_or(ODBCError::FunctionSequenceError(function_name))?;
+    stmt_contents.get_data_offsets.clear();
+
+    let mut diagnostics = vec![];
+    let mut rows_fetched = 0;
+    let mut rows_in_error = 0;
+    let mut exhausted = false;
+    for row in 0..row_array_size {
+        exhausted = exhausted || !mongo_statement.next().map_err(ODBCError::Core)?;
+        let row_status = if exhausted {
+            RowStatus::NoRow
+        } else {
+            rows_fetched += 1;
+            match fetch_row(
+                &**mongo_statement,
+                &bound_cols,
+                row,
+                row_bind_type,
+                bind_offset,
+            ) {
+                Ok(warnings) if warnings.is_empty() => RowStatus::Success,
+                Ok(warnings) => {
+                    diagnostics.extend(warnings);
+                    RowStatus::SuccessWithInfo
+                }
+                Err(error) => {
+                    diagnostics.push(error);
+                    rows_in_error += 1;
+                    RowStatus::Error
+                }
+            }
+        };
+        if !row_status_ptr.is_null() {
+            unsafe { *row_status_ptr.add(row) = row_status as USmallInt };
+        }
+    }
+    if !rows_fetched_ptr.is_null() {
+        unsafe { *rows_fetched_ptr = rows_fetched };
+    }
+    if rows_fetched == 0 {
+        return Ok((SqlReturn::NO_DATA, vec![]));
+    }
+    stmt_contents.state = StatementState::CursorFetchSet;
+    let sql_return = if rows_in_error == rows_fetched {
+        SqlReturn::ERROR
+    } else if diagnostics.is_empty() {
+        SqlReturn::SUCCESS
+    } else {
+        SqlReturn::SUCCESS_WITH_INFO
+    };
+    Ok((sql_return, diagnostics))
+}
+
+/// fetch_row writes the values of the bound columns of the current row to the buffers of the
+/// [`row`]-th row of the rowset. Returns the warnings raised by the conversions. The warnings and
+/// errors carry the numbers of the row and column they were raised for.
+fn fetch_row(
+    mongo_statement: &dyn MongoStatement,
+    bound_cols: &[(USmallInt, BoundColInfo)],
+    row: usize,
+    row_bind_type: ULen,
+    bind_offset: ULen,
+) -> Result<Vec<ODBCError>, ODBCError> {
+    let mut warnings = vec![];
+    for (col_number, bound_col) in bound_cols {
+        let in_column = |error: ODBCError| error.in_column(Some(row + 1), *col_number);
+        let bound_col = bound_col.for_row(row, row_bind_type, bind_offset);
+        let value = mongo_statement
+            .get_value(*col_number)
+            .map_err(|error| in_column(ODBCError::Core(error)))?;
+        if let (_, Some(warning)) = format_cell(
+            value,
+            resolve_default_c_type(mongo_statement, *col_number, bound_col.target_type)
+                .map_err(in_column)?,
+            bound_col.target_buffer,
+            bound_col.buffer_length,
+            bound_col.length_or_indicator,
+            0,
+        )
+        .map_err(in_column)?
+        {
+            warnings.push(in_column(warning));
+        }
+    }
+    Ok(warnings)
+}
+
+/// resolve_default_c_type returns the C type values of the column [`col_number`] are converted
+/// to for the given [`target_type`]: SQL_C_DEFAULT is the default C type of the column's SQL
+/// type, as reported by its metadata.
+fn resolve_default_c_type(
+    mongo_statement: &dyn MongoStatement,
+    col_number: USmallInt,
+    target_type: CDataType,
+) -> Result<CDataType, ODBCError> {
+    if target_type != CDataType::Default {
+        return Ok(target_type);
+    }
+    let metadata = mongo_statement
+        ._get_col_metadata(col_number)
+        .map_err(ODBCError::Core)?;
+    Ok(default_c_type(metadata.sql_type))
 }
 
 #[no_mangle]
 pub extern "C" fn SQLFetchScroll(
-    _statement_handle: HStmt,
-    _fetch_orientation: FetchOrientation,
+    statement_handle: HStmt,
+    fetch_orientation: FetchOrientation,
     _fetch_offset: Len,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLFetchScroll",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            // Cursors are forward only.
+            if fetch_orientation != FetchOrientation::Next {
+                stmt_handle.add_diag_info(ODBCError::FetchTypeOutOfRange(fetch_orientation));
+                return SqlReturn::ERROR;
+            }
+            match sql_fetch(stmt, "SQLFetchScroll") {
+                Ok((sql_return, diagnostics)) => {
+                    diagnostics
+                        .into_iter()
+                        .for_each(|diagnostic| stmt_handle.add_diag_info(diagnostic));
+                    sql_return
+                }
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
@@ -482,12 +1731,17 @@ pub extern "C" fn SQLForeignKeys(
     _fk_table_name: *const Char,
     _fk_table_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLForeignKeys")
+    panic_safe_exec(
+        "SQLForeignKeys",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLForeignKeys"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLForeignKeysW(
-    _statement_handle: HStmt,
+    statement_handle: HStmt,
     _pk_catalog_name: *const WChar,
     _pk_catalog_name_length: SmallInt,
     _pk_schema_name: *const WChar,
@@ -501,32 +1755,56 @@ pub extern "C" fn SQLForeignKeysW(
     _fk_table_name: *const WChar,
     _fk_table_name_length: SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLForeignKeysW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLForeignKeysW"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLFreeHandle(handle_type: HandleType, handle: Handle) -> SqlReturn {
-    match sql_free_handle(handle_type, handle as *mut _) {
-        Ok(_) => SqlReturn::SUCCESS,
-        Err(_) => SqlReturn::INVALID_HANDLE,
-    }
+    panic_safe_exec("SQLFreeHandle", handle, handle_type, || {
+        MongoHandleRef::from(handle).clear_diagnostics();
+        match sql_free_handle(handle_type, handle as *mut _) {
+            Ok(_) => SqlReturn::SUCCESS,
+            Err(sql_return) => sql_return,
+        }
+    })
 }
 
-fn sql_free_handle(handle_type: HandleType, handle: *mut MongoHandle) -> Result<(), ()> {
+fn sql_free_handle(handle_type: HandleType, handle: *mut MongoHandle) -> Result<(), SqlReturn> {
     match handle_type {
         // By making Boxes to the types and letting them go out of
         // scope, they will be dropped.
+        // An environment can only be freed once its connections are, and a connection once it
+        // is disconnected.
         HandleType::Env => {
-            let _ = unsafe { (*handle).as_env().ok_or(())? };
+            let env = unsafe { (*handle).as_env().ok_or(SqlReturn::INVALID_HANDLE)? };
+            let env_state = env.read().unwrap_or_else(PoisonError::into_inner).state;
+            if env_state != EnvState::Allocated {
+                unsafe {
+                    (*handle).add_diag_info(ODBCError::FunctionSequenceError("SQLFreeHandle"))
+                };
+                return Err(SqlReturn::ERROR);
+            }
         }
         HandleType::Dbc => {
-            let conn = unsafe { (*handle).as_connection().ok_or(())? };
+            let conn = unsafe { (*handle).as_connection().ok_or(SqlReturn::INVALID_HANDLE)? };
+            let conn_state = conn.read().unwrap_or_else(PoisonError::into_inner).state;
+            if conn_state != ConnectionState::Allocated {
+                unsafe {
+                    (*handle).add_diag_info(ODBCError::FunctionSequenceError("SQLFreeHandle"))
+                };
+                return Err(SqlReturn::ERROR);
+            }
             let mut env_contents = unsafe {
-                (*conn.write().unwrap().env)
+                (*conn.write().unwrap_or_else(PoisonError::into_inner).env)
                     .as_env()
-                    .ok_or(())?
+                    .ok_or(SqlReturn::INVALID_HANDLE)?
                     .write()
-                    .unwrap()
+                    .unwrap_or_else(PoisonError::into_inner)
             };
             env_contents.connections.remove(&handle);
             if env_contents.connections.is_empty() {
@@ -534,15 +1812,26 @@ fn sql_free_handle(handle_type: HandleType, handle: *mut MongoHandle) -> Result<
             }
         }
         HandleType::Stmt => {
-            let stmt = unsafe { (*handle).as_statement().ok_or(())? };
+            let stmt = unsafe { (*handle).as_statement().ok_or(SqlReturn::INVALID_HANDLE)? };
+            let state_check = stmt
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .check_state(StatementFunction::Other("SQLFreeHandle"));
+            if let Err(error) = state_check {
+                unsafe { (*handle).add_diag_info(error) };
+                return Err(SqlReturn::ERROR);
+            }
             // Actually reading this value would make ASAN fail, but this
             // is what the ODBC standard expects.
             let mut conn_contents = unsafe {
-                (*stmt.write().unwrap().connection)
+                (*stmt
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .connection)
                     .as_connection()
-                    .ok_or(())?
+                    .ok_or(SqlReturn::INVALID_HANDLE)?
                     .write()
-                    .unwrap()
+                    .unwrap_or_else(PoisonError::into_inner)
             };
             conn_contents.statements.remove(&handle);
             if conn_contents.statements.is_empty() {
@@ -550,18 +1839,111 @@ fn sql_free_handle(handle_type: HandleType, handle: *mut MongoHandle) -> Result<
             }
         }
         HandleType::Desc => {
-            unimplemented!();
+            let desc = unsafe { (*handle).as_descriptor().ok_or(SqlReturn::INVALID_HANDLE)? };
+            let desc_contents = desc.read().unwrap_or_else(PoisonError::into_inner);
+            // The implicitly allocated descriptors are freed with their statement.
+            if desc_contents.alloc_type == AllocType::Auto {
+                drop(desc_contents);
+                unsafe { (*handle).add_diag_info(ODBCError::InvalidUseOfAutomaticDescriptor) };
+                return Err(SqlReturn::ERROR);
+            }
+            // The statements the descriptor is attached to revert to their implicit descriptors.
+            let conn = unsafe {
+                (*desc_contents.connection)
+                    .as_connection()
+                    .ok_or(SqlReturn::INVALID_HANDLE)?
+            };
+            let mut conn_contents = conn.write().unwrap_or_else(PoisonError::into_inner);
+            conn_contents.descriptors.remove(&handle);
+            for stmt in conn_contents
+                .statements
+                .iter()
+                .filter_map(|stmt| unsafe { (**stmt).as_statement() })
+            {
+                let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+                if stmt_contents.attributes.app_row_desc == handle {
+                    stmt_contents.attributes.app_row_desc = stmt_contents.implicit_app_row_desc;
+                }
+                if stmt_contents.attributes.app_param_desc == handle {
+                    stmt_contents.attributes.app_param_desc = stmt_contents.implicit_app_param_desc;
+                }
+            }
         }
     }
-    // create the Box at the end to ensure Drop only occurs when there are no errors due
+    // free the handle at the end to ensure Drop only occurs when there are no errors due
     // to incorrect handle type.
-    let _ = unsafe { Box::from_raw(handle) };
+    MongoHandle::free(handle);
     Ok(())
 }
 
 #[no_mangle]
-pub extern "C" fn SQLFreeStmt(_statement_handle: HStmt, _option: SmallInt) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLFreeStmt(statement_handle: HStmt, option: SmallInt) -> SqlReturn {
+    panic_safe_exec(
+        "SQLFreeStmt",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            let result = match FromPrimitive::from_i16(option) {
+                None => Err(ODBCError::InvalidOptionIdentifier(option)),
+                // SQL_DROP is deprecated in favor of SQLFreeHandle, which it is equivalent to.
+                Some(FreeStmtOption::Drop) => {
+                    return match sql_free_handle(HandleType::Stmt, statement_handle as *mut _) {
+                        Ok(_) => SqlReturn::SUCCESS,
+                        Err(sql_return) => sql_return,
+                    }
+                }
+                Some(FreeStmtOption::Close) => sql_free_stmt(stmt, |stmt_contents| {
+                    stmt_contents.close_cursor();
+                    Ok(())
+                }),
+                Some(FreeStmtOption::Unbind) => sql_free_stmt(stmt, |stmt_contents| {
+                    stmt_contents
+                        .ard()?
+                        .write()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .records
+                        .clear();
+                    Ok(())
+                }),
+                Some(FreeStmtOption::ResetParams) => sql_free_stmt(stmt, |stmt_contents| {
+                    stmt_contents
+                        .apd()?
+                        .write()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .records
+                        .clear();
+                    Ok(())
+                }),
+            };
+            match result {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_free_stmt applies [`free`], the effect of an SQLFreeStmt option other than SQL_DROP, to the
+/// statement once its state allows it. SQL_CLOSE closes the cursor, if open, SQL_UNBIND unbinds all
+/// the columns and SQL_RESET_PARAMS all the parameters. The bindings are released from the
+/// descriptors currently attached to the statement, so an explicitly allocated descriptor loses
+/// them for all the statements sharing it.
+fn sql_free_stmt(
+    stmt: &RwLock<Statement>,
+    free: impl FnOnce(&mut Statement) -> Result<(), ODBCError>,
+) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::Other("SQLFreeStmt"))?;
+    free(&mut stmt_contents)
 }
 
 #[no_mangle]
@@ -572,18 +1954,33 @@ pub extern "C" fn SQLGetConnectAttr(
     _buffer_length: Integer,
     _string_length_ptr: *mut Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(connection_handle), "SQLGetConnectAttr")
+    panic_safe_exec(
+        "SQLGetConnectAttr",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || unsupported_function(MongoHandleRef::from(connection_handle), "SQLGetConnectAttr"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetConnectAttrW(
-    _connection_handle: HDbc,
+    connection_handle: HDbc,
     _attribute: ConnectionAttribute,
     _value_ptr: Pointer,
     _buffer_length: Integer,
     _string_length_ptr: *mut Integer,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetConnectAttrW",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            unsupported_function(
+                MongoHandleRef::from(connection_handle),
+                "SQLGetConnectAttrW",
+            )
+        },
+    )
 }
 
 #[no_mangle]
@@ -593,127 +1990,839 @@ pub extern "C" fn SQLGetCursorName(
     _buffer_length: SmallInt,
     _name_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLGetCursorName")
+    panic_safe_exec(
+        "SQLGetCursorName",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLGetCursorName"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetCursorNameW(
-    _statement_handle: HStmt,
+    statement_handle: HStmt,
     _cursor_name: *mut WChar,
     _buffer_length: SmallInt,
     _name_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetCursorNameW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLGetCursorNameW"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetData(
-    _statement_handle: HStmt,
-    _col_or_param_num: USmallInt,
-    _target_type: CDataType,
-    _target_value_ptr: Pointer,
-    _buffer_length: Len,
-    _str_len_or_ind_ptr: *mut Len,
+    statement_handle: HStmt,
+    col_or_param_num: USmallInt,
+    target_type: CDataType,
+    target_value_ptr: Pointer,
+    buffer_length: Len,
+    str_len_or_ind_ptr: *mut Len,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetData",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            let col = BoundColInfo {
+                target_type,
+                target_buffer: target_value_ptr,
+                buffer_length,
+                length_or_indicator: str_len_or_ind_ptr,
+            };
+            match sql_get_data(stmt, col_or_param_num, col) {
+                Ok((sql_return, warning)) => {
+                    if let Some(warning) = warning {
+                        stmt_handle.add_diag_info(warning);
+                    }
+                    sql_return
+                }
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_get_data writes the value of the column [`col_number`] of the current row, converted to
+/// the target C type, to the given buffers. Long character and binary values can be retrieved in
+/// several chunks by calling sql_get_data repeatedly: each call returns the next part of the
+/// value, with a 01004 warning as long as the value is truncated, and SQL_NO_DATA once the value
+/// has been entirely returned.
+/// SQL_ARD_TYPE uses the type of the column's record in the application row descriptor.
+fn sql_get_data(
+    stmt: &RwLock<Statement>,
+    col_number: USmallInt,
+    mut col: BoundColInfo,
+) -> Result<(SqlReturn, Option<ODBCError>), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::GetData)?;
+    let stmt_contents = &mut *stmt_contents;
+    let mongo_statement = stmt_contents
+        .mongo_statement
+        .as_ref()
+        .ok_or(ODBCError::FunctionSequenceError("SQLGetData"))?;
+    if col_number == 0 {
+        return Err(ODBCError::InvalidDescriptorIndex(col_number.into()));
+    }
+    if col.target_type == CDataType::Ard {
+        col.target_type = stmt_contents
+            .ard()?
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .records
+            .get(col_number as usize - 1)
+            .and_then(|record| c_type_from_code(record.concise_type()))
+            .ok_or(ODBCError::InvalidTargetType(CDataType::Ard))?;
+    }
+    if !is_supported_c_type(col.target_type) {
+        return Err(ODBCError::InvalidTargetType(col.target_type));
+    }
+    if col.buffer_length < 0 {
+        return Err(ODBCError::InvalidBufferLength(col.buffer_length));
+    }
+    let offset = match stmt_contents.get_data_offsets.get(&col_number) {
+        None => 0,
+        Some(Some(offset)) => *offset,
+        Some(None) => return Ok((SqlReturn::NO_DATA, None)),
+    };
+    // The cursor's position within the rowset isn't tracked, so the row number of the
+    // diagnostics is unknown.
+    let in_column = |error: ODBCError| error.in_column(None, col_number);
+    let value = mongo_statement
+        .get_value(col_number)
+        .map_err(|error| in_column(ODBCError::Core(error)))?;
+    let (next_offset, warning) = format_cell(
+        value,
+        resolve_default_c_type(&**mongo_statement, col_number, col.target_type)
+            .map_err(in_column)?,
+        col.target_buffer,
+        col.buffer_length,
+        col.length_or_indicator,
+        offset,
+    )
+    .map_err(in_column)?;
+    let warning = warning.map(in_column);
+    stmt_contents
+        .get_data_offsets
+        .insert(col_number, next_offset);
+    let sql_return = if warning.is_some() {
+        SqlReturn::SUCCESS_WITH_INFO
+    } else {
+        SqlReturn::SUCCESS
+    };
+    Ok((sql_return, warning))
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetDescField(
-    _descriptor_handle: HDesc,
-    _record_number: SmallInt,
-    _field_identifier: SmallInt,
-    _value_ptr: Pointer,
-    _buffer_length: Integer,
-    _string_length_ptr: *mut Integer,
+    descriptor_handle: HDesc,
+    record_number: SmallInt,
+    field_identifier: SmallInt,
+    value_ptr: Pointer,
+    buffer_length: Integer,
+    string_length_ptr: *mut Integer,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetDescField",
+        descriptor_handle as Handle,
+        HandleType::Desc,
+        || {
+            let desc_handle = MongoHandleRef::from(descriptor_handle);
+            desc_handle.clear_diagnostics();
+            let charset = desc_handle.charset();
+            let desc = match desc_handle.as_descriptor() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(desc) => desc,
+            };
+            match sql_get_desc_field(desc, record_number, field_identifier) {
+                Ok(None) => SqlReturn::NO_DATA,
+                Ok(Some(DescValue::String(value))) => {
+                    let mut string_length = 0;
+                    let sql_return = set_output_string(
+                        &value,
+                        value_ptr as *mut Char,
+                        buffer_length.max(0) as usize,
+                        &mut string_length,
+                        charset,
+                    );
+                    set_output_value(string_length_ptr, string_length as Integer);
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        desc_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Ok(Some(value)) => {
+                    match write_numeric_desc_field(value, value_ptr, field_identifier) {
+                        Ok(()) => SqlReturn::SUCCESS,
+                        Err(error) => {
+                            desc_handle.add_diag_info(error);
+                            SqlReturn::ERROR
+                        }
+                    }
+                }
+                Err(error) => {
+                    desc_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetDescFieldW(
-    _descriptor_handle: HDesc,
-    _record_number: SmallInt,
-    _field_identifier: SmallInt,
-    _value_ptr: Pointer,
-    _buffer_length: Integer,
-    _string_length_ptr: *mut Integer,
+    descriptor_handle: HDesc,
+    record_number: SmallInt,
+    field_identifier: SmallInt,
+    value_ptr: Pointer,
+    buffer_length: Integer,
+    string_length_ptr: *mut Integer,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetDescFieldW",
+        descriptor_handle as Handle,
+        HandleType::Desc,
+        || {
+            let desc_handle = MongoHandleRef::from(descriptor_handle);
+            desc_handle.clear_diagnostics();
+            let desc = match desc_handle.as_descriptor() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(desc) => desc,
+            };
+            match sql_get_desc_field(desc, record_number, field_identifier) {
+                Ok(None) => SqlReturn::NO_DATA,
+                Ok(Some(DescValue::String(value))) => {
+                    // The buffer length and the string length are in bytes, not in characters.
+                    let mut string_length = 0;
+                    let sql_return = set_output_wstring(
+                        &value,
+                        value_ptr as *mut WChar,
+                        buffer_length.max(0) as usize / size_of::<WChar>(),
+                        &mut string_length,
+                    );
+                    set_output_value(
+                        string_length_ptr,
+                        string_length as Integer * size_of::<WChar>() as Integer,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        desc_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Ok(Some(value)) => {
+                    match write_numeric_desc_field(value, value_ptr, field_identifier) {
+                        Ok(()) => SqlReturn::SUCCESS,
+                        Err(error) => {
+                            desc_handle.add_diag_info(error);
+                            SqlReturn::ERROR
+                        }
+                    }
+                }
+                Err(error) => {
+                    desc_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// DescValue is the value of a descriptor field, of the type of the field.
+enum DescValue {
+    String(String),
+    SmallInt(SmallInt),
+    Integer(Integer),
+    Len(Len),
+    ULen(ULen),
+    Pointer(Pointer),
+}
+
+impl DescValue {
+    /// as_len returns the value of a numeric field as an SQLLEN, or None for a character field.
+    fn as_len(&self) -> Option<Len> {
+        match self {
+            DescValue::SmallInt(value) => Some(*value as Len),
+            DescValue::Integer(value) => Some(*value as Len),
+            DescValue::Len(value) => Some(*value),
+            DescValue::ULen(value) => Some(*value as Len),
+            DescValue::Pointer(value) => Some(*value as Len),
+            DescValue::String(_) => None,
+        }
+    }
+}
+
+/// write_numeric_desc_field writes the numeric [`value`] of the field [`field_identifier`] to
+/// [`value_ptr`]. Character values are written by the caller, and are rejected as an invalid
+/// field identifier.
+fn write_numeric_desc_field(
+    value: DescValue,
+    value_ptr: Pointer,
+    field_identifier: SmallInt,
+) -> Result<(), ODBCError> {
+    match value {
+        DescValue::SmallInt(value) => set_output_value(value_ptr as *mut SmallInt, value),
+        DescValue::Integer(value) => set_output_value(value_ptr as *mut Integer, value),
+        DescValue::Len(value) => set_output_value(value_ptr as *mut Len, value),
+        DescValue::ULen(value) => set_output_value(value_ptr as *mut ULen, value),
+        DescValue::Pointer(value) => set_output_value(value_ptr as *mut Pointer, value),
+        DescValue::String(_) => return Err(ODBCError::InvalidFieldIdentifier(field_identifier)),
+    }
+    Ok(())
+}
+
+/// desc_field_identifier returns the descriptor field identified by [`field_identifier`].
+fn desc_field_identifier(field_identifier: SmallInt) -> Result<Desc, ODBCError> {
+    DESC_FIELDS
+        .into_iter()
+        .find(|field| *field as SmallInt == field_identifier)
+        .ok_or(ODBCError::InvalidFieldIdentifier(field_identifier))
+}
+
+/// sql_get_desc_field returns the value of the field [`field_identifier`] of the header of
+/// [`desc`], or of its record [`record_number`] for the fields of the records. Returns None for
+/// a record past SQL_DESC_COUNT.
+/// SQL_DESC_ARRAY_SIZE, SQL_DESC_BIND_OFFSET_PTR and SQL_DESC_BIND_TYPE are only defined for
+/// application descriptors, and SQL_DESC_ROWS_PROCESSED_PTR for implementation descriptors.
+fn sql_get_desc_field(
+    desc: &RwLock<Descriptor>,
+    record_number: SmallInt,
+    field_identifier: SmallInt,
+) -> Result<Option<DescValue>, ODBCError> {
+    let field = desc_field_identifier(field_identifier)?;
+    let desc = desc.read().unwrap_or_else(PoisonError::into_inner);
+    let is_application = desc.desc_kind == DescriptorKind::Application;
+    let value = match field {
+        Desc::AllocType => DescValue::SmallInt(desc.alloc_type as SmallInt),
+        Desc::ArraySize if is_application => DescValue::ULen(desc.array_size),
+        Desc::ArrayStatusPtr => DescValue::Pointer(desc.array_status_ptr as Pointer),
+        Desc::BindOffsetPtr if is_application => {
+            DescValue::Pointer(desc.bind_offset_ptr as Pointer)
+        }
+        Desc::BindType if is_application => DescValue::Integer(desc.bind_type as Integer),
+        Desc::RowsProcessedPtr if !is_application => {
+            DescValue::Pointer(desc.rows_processed_ptr as Pointer)
+        }
+        Desc::Count if desc.desc_kind == DescriptorKind::ImplementationRow => {
+            DescValue::SmallInt(with_ird_result_set(&desc, |mongo_statement| {
+                Ok(mongo_statement._get_col_count() as SmallInt)
+            })?)
+        }
+        Desc::Count => DescValue::SmallInt(desc.records.len() as SmallInt),
+        Desc::ArraySize | Desc::BindOffsetPtr | Desc::BindType | Desc::RowsProcessedPtr => {
+            return Err(ODBCError::InvalidFieldIdentifier(field_identifier))
+        }
+        _ => return record_field(&desc, record_number, field),
+    };
+    Ok(Some(value))
+}
+
+/// record_field returns the value of the field [`field`] of the record [`record_number`] of
+/// [`desc`], or None for a record past SQL_DESC_COUNT. The records of an implementation row
+/// descriptor describe the columns of its statement's result set. Bookmarks are not supported,
+/// so record 0 is an invalid index.
+fn record_field(
+    desc: &Descriptor,
+    record_number: SmallInt,
+    field: Desc,
+) -> Result<Option<DescValue>, ODBCError> {
+    if record_number < 1 {
+        return Err(ODBCError::InvalidDescriptorIndex(record_number.into()));
+    }
+    if desc.desc_kind == DescriptorKind::ImplementationRow {
+        return with_ird_result_set(desc, |mongo_statement| {
+            if record_number as u32 > mongo_statement._get_col_count() {
+                return Ok(None);
+            }
+            let value = match ird_field(mongo_statement, record_number as USmallInt, field)? {
+                ColumnAttribute::Character(value) => DescValue::String(value),
+                ColumnAttribute::Numeric(value) => match field {
+                    Desc::DisplaySize | Desc::OctetLength => DescValue::Len(value),
+                    Desc::Length => DescValue::ULen(value as ULen),
+                    Desc::AutoUniqueValue | Desc::CaseSensitive | Desc::NumPrecRadix => {
+                        DescValue::Integer(value as Integer)
+                    }
+                    _ => DescValue::SmallInt(value as SmallInt),
+                },
+            };
+            Ok(Some(value))
+        });
+    }
+    let record = match desc.records.get(record_number as usize - 1) {
+        None => return Ok(None),
+        Some(record) => record,
+    };
+    let value = match field {
+        Desc::Type => DescValue::SmallInt(record.desc_type),
+        Desc::ConciseType => DescValue::SmallInt(record.concise_type()),
+        Desc::DatetimeIntervalCode => DescValue::SmallInt(record.datetime_interval_code),
+        Desc::DataPtr => DescValue::Pointer(record.data_ptr),
+        Desc::IndicatorPtr => DescValue::Pointer(record.indicator_ptr as Pointer),
+        Desc::OctetLengthPtr => DescValue::Pointer(record.octet_length_ptr as Pointer),
+        Desc::OctetLength => DescValue::Len(record.octet_length),
+        Desc::Length => DescValue::ULen(record.length),
+        Desc::Precision => DescValue::SmallInt(record.precision),
+        Desc::Scale => DescValue::SmallInt(record.scale),
+        _ => return Err(ODBCError::InvalidFieldIdentifier(field as SmallInt)),
+    };
+    Ok(Some(value))
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetDescRec(
-    _descriptor_handle: HDesc,
-    _record_number: SmallInt,
-    _name: *mut Char,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
-    _type_ptr: *mut SmallInt,
-    _sub_type_ptr: *mut SmallInt,
-    _length_ptr: *mut Len,
-    _precision_ptr: *mut SmallInt,
-    _scale_ptr: *mut SmallInt,
-    _nullable_ptr: *mut Nullability,
+    descriptor_handle: HDesc,
+    record_number: SmallInt,
+    name: *mut Char,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
+    type_ptr: *mut SmallInt,
+    sub_type_ptr: *mut SmallInt,
+    length_ptr: *mut Len,
+    precision_ptr: *mut SmallInt,
+    scale_ptr: *mut SmallInt,
+    nullable_ptr: *mut Nullability,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetDescRec",
+        descriptor_handle as Handle,
+        HandleType::Desc,
+        || {
+            let desc_handle = MongoHandleRef::from(descriptor_handle);
+            desc_handle.clear_diagnostics();
+            let charset = desc_handle.charset();
+            let desc = match desc_handle.as_descriptor() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(desc) => desc,
+            };
+            match sql_get_desc_rec(desc, record_number) {
+                Ok(None) => SqlReturn::NO_DATA,
+                Ok(Some(record)) => {
+                    record.write(
+                        type_ptr,
+                        sub_type_ptr,
+                        length_ptr,
+                        precision_ptr,
+                        scale_ptr,
+                        nullable_ptr,
+                    );
+                    let sql_return = set_output_string(
+                        &record.name,
+                        name,
+                        buffer_length.max(0) as usize,
+                        string_length_ptr,
+                        charset,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        desc_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Err(error) => {
+                    desc_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetDescRecW(
-    _descriptor_handle: HDesc,
-    _record_number: SmallInt,
-    _name: *mut WChar,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
-    _type_ptr: *mut SmallInt,
-    _sub_type_ptr: *mut SmallInt,
-    _length_ptr: *mut Len,
-    _precision_ptr: *mut SmallInt,
-    _scale_ptr: *mut SmallInt,
-    _nullable_ptr: *mut Nullability,
+    descriptor_handle: HDesc,
+    record_number: SmallInt,
+    name: *mut WChar,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
+    type_ptr: *mut SmallInt,
+    sub_type_ptr: *mut SmallInt,
+    length_ptr: *mut Len,
+    precision_ptr: *mut SmallInt,
+    scale_ptr: *mut SmallInt,
+    nullable_ptr: *mut Nullability,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetDescRecW",
+        descriptor_handle as Handle,
+        HandleType::Desc,
+        || {
+            let desc_handle = MongoHandleRef::from(descriptor_handle);
+            desc_handle.clear_diagnostics();
+            let desc = match desc_handle.as_descriptor() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(desc) => desc,
+            };
+            match sql_get_desc_rec(desc, record_number) {
+                Ok(None) => SqlReturn::NO_DATA,
+                Ok(Some(record)) => {
+                    record.write(
+                        type_ptr,
+                        sub_type_ptr,
+                        length_ptr,
+                        precision_ptr,
+                        scale_ptr,
+                        nullable_ptr,
+                    );
+                    // The buffer length and the name length are in characters.
+                    let sql_return = set_output_wstring(
+                        &record.name,
+                        name,
+                        buffer_length.max(0) as usize,
+                        string_length_ptr,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        desc_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Err(error) => {
+                    desc_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// DescRec is a descriptor record, as returned by SQLGetDescRec.
+struct DescRec {
+    name: String,
+    desc_type: SmallInt,
+    sub_type: SmallInt,
+    length: Len,
+    precision: SmallInt,
+    scale: SmallInt,
+    nullable: Nullability,
+}
+
+impl DescRec {
+    /// write writes the numeric fields of the record to the given pointers, skipping the null
+    /// ones.
+    fn write(
+        &self,
+        type_ptr: *mut SmallInt,
+        sub_type_ptr: *mut SmallInt,
+        length_ptr: *mut Len,
+        precision_ptr: *mut SmallInt,
+        scale_ptr: *mut SmallInt,
+        nullable_ptr: *mut Nullability,
+    ) {
+        set_output_value(type_ptr, self.desc_type);
+        set_output_value(sub_type_ptr, self.sub_type);
+        set_output_value(length_ptr, self.length);
+        set_output_value(precision_ptr, self.precision);
+        set_output_value(scale_ptr, self.scale);
+        set_output_value(nullable_ptr, self.nullable);
+    }
+}
+
+/// sql_get_desc_rec returns the record [`record_number`] of [`desc`], or None for a record past
+/// SQL_DESC_COUNT. The length of the record is its SQL_DESC_OCTET_LENGTH. Only the records of
+/// implementation row descriptors have a name and a known nullability.
+fn sql_get_desc_rec(
+    desc: &RwLock<Descriptor>,
+    record_number: SmallInt,
+) -> Result<Option<DescRec>, ODBCError> {
+    let desc = desc.read().unwrap_or_else(PoisonError::into_inner);
+    let numeric = |field: Desc| match record_field(&desc, record_number, field)? {
+        None => Ok(0),
+        Some(value) => value
+            .as_len()
+            .ok_or(ODBCError::InvalidFieldIdentifier(field as SmallInt)),
+    };
+    if record_field(&desc, record_number, Desc::Type)?.is_none() {
+        return Ok(None);
+    }
+    let (name, nullable) = if desc.desc_kind == DescriptorKind::ImplementationRow {
+        let name = match record_field(&desc, record_number, Desc::Name)? {
+            Some(DescValue::String(name)) => name,
+            _ => String::new(),
+        };
+        (name, Nullability(numeric(Desc::Nullable)? as SmallInt))
+    } else {
+        (String::new(), Nullability::UNKNOWN)
+    };
+    Ok(Some(DescRec {
+        name,
+        desc_type: numeric(Desc::Type)? as SmallInt,
+        sub_type: numeric(Desc::DatetimeIntervalCode)? as SmallInt,
+        length: numeric(Desc::OctetLength)?,
+        precision: numeric(Desc::Precision)? as SmallInt,
+        scale: numeric(Desc::Scale)? as SmallInt,
+        nullable,
+    }))
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetDiagField(
-    _handle_type: HandleType,
+    handle_type: HandleType,
     handle: Handle,
-    _record_rumber: SmallInt,
-    _diag_identifier: SmallInt,
-    _diag_info_ptr: Pointer,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
+    record_number: SmallInt,
+    diag_identifier: SmallInt,
+    diag_info_ptr: Pointer,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(handle), "SQLGetDiagField")
+    panic_safe_exec(
+        "SQLGetDiagField",
+        handle,
+        handle_type,
+        || match sql_get_diag_field(handle_type, handle, record_number, diag_identifier) {
+            Ok(DiagValue::String(_)) if buffer_length < 0 => SqlReturn::ERROR,
+            Ok(DiagValue::String(value)) => set_output_string(
+                &value,
+                diag_info_ptr as *mut Char,
+                buffer_length as usize,
+                string_length_ptr,
+                MongoHandleRef::from(handle).charset(),
+            ),
+            Ok(value) => write_numeric_diag_field(value, diag_info_ptr),
+            Err(sql_return) => sql_return,
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetDiagFieldW(
-    _handle_type: HandleType,
-    _handle: Handle,
-    _record_rumber: SmallInt,
-    _diag_identifier: SmallInt,
-    _diag_info_ptr: Pointer,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
+    handle_type: HandleType,
+    handle: Handle,
+    record_number: SmallInt,
+    diag_identifier: SmallInt,
+    diag_info_ptr: Pointer,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec("SQLGetDiagFieldW", handle, handle_type, || {
+        match sql_get_diag_field(handle_type, handle, record_number, diag_identifier) {
+            Ok(DiagValue::String(_)) if buffer_length < 0 => SqlReturn::ERROR,
+            Ok(DiagValue::String(value)) => {
+                // The buffer length and the string length are in bytes, not in characters.
+                let mut string_length = 0;
+                let sql_return = set_output_wstring(
+                    &value,
+                    diag_info_ptr as *mut WChar,
+                    buffer_length as usize / size_of::<WChar>(),
+                    &mut string_length,
+                );
+                set_output_value(
+                    string_length_ptr,
+                    string_length * size_of::<WChar>() as SmallInt,
+                );
+                sql_return
+            }
+            Ok(value) => write_numeric_diag_field(value, diag_info_ptr),
+            Err(sql_return) => sql_return,
+        }
+    })
+}
+
+/// DiagValue is the value of a field of the diagnostics, of the type of the field.
+enum DiagValue {
+    String(String),
+    SmallInt(SmallInt),
+    Integer(Integer),
+    Len(Len),
+}
+
+/// sql_get_diag_field returns the value of the field [`diag_identifier`] of the header of the
+/// diagnostics of [`handle`], or of its record [`record_number`] for the fields of the records.
+/// As SQLGetDiagField doesn't post diagnostics, failures are reported by the returned SqlReturn:
+/// SQL_NO_DATA for a record past the last one, SQL_ERROR for an invalid record number or field,
+/// or a header field which isn't defined for the handle type.
+fn sql_get_diag_field(
+    handle_type: HandleType,
+    handle: Handle,
+    record_number: SmallInt,
+    diag_identifier: SmallInt,
+) -> Result<DiagValue, SqlReturn> {
+    let mongo_handle =
+        unsafe { (handle as *mut MongoHandle).as_ref() }.ok_or(SqlReturn::INVALID_HANDLE)?;
+    let diag_identifier: DiagIdentifier =
+        FromPrimitive::from_i16(diag_identifier).ok_or(SqlReturn::ERROR)?;
+    match (handle_type, mongo_handle) {
+        (HandleType::Env, MongoHandle::Env(env)) => {
+            let env_contents = env.read().unwrap_or_else(PoisonError::into_inner);
+            diag_field(
+                &env_contents.errors,
+                env_contents.return_code,
+                None,
+                "",
+                record_number,
+                diag_identifier,
+            )
+        }
+        (HandleType::Dbc, MongoHandle::Connection(conn)) => {
+            let conn_contents = conn.read().unwrap_or_else(PoisonError::into_inner);
+            diag_field(
+                &conn_contents.errors,
+                conn_contents.return_code,
+                None,
+                &conn_contents.data_source.name,
+                record_number,
+                diag_identifier,
+            )
+        }
+        (HandleType::Stmt, MongoHandle::Statement(stmt)) => {
+            let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+            let connection_name = unsafe { stmt_contents.connection.as_ref() }
+                .and_then(MongoHandle::as_connection)
+                .map(|conn| {
+                    conn.read()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .data_source
+                        .name
+                        .clone()
+                })
+                .unwrap_or_default();
+            diag_field(
+                &stmt_contents.errors,
+                stmt_contents.return_code,
+                Some(&stmt_contents),
+                &connection_name,
+                record_number,
+                diag_identifier,
+            )
+        }
+        (HandleType::Desc, MongoHandle::Descriptor(desc)) => {
+            let desc_contents = desc.read().unwrap_or_else(PoisonError::into_inner);
+            let connection_name = unsafe { desc_contents.connection.as_ref() }
+                .and_then(MongoHandle::as_connection)
+                .map(|conn| {
+                    conn.read()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .data_source
+                        .name
+                        .clone()
+                })
+                .unwrap_or_default();
+            diag_field(
+                &desc_contents.errors,
+                desc_contents.return_code,
+                None,
+                &connection_name,
+                record_number,
+                diag_identifier,
+            )
+        }
+        _ => Err(SqlReturn::INVALID_HANDLE),
+    }
+}
+
+/// diag_field returns the value of the field [`diag_identifier`] of diagnostics made of the
+/// records [`errors`], posted by a function which returned [`return_code`]. The row counts and
+/// dynamic function are only defined for a [`statement`]. The connection and server names are both
+/// the data source name of the connection.
+fn diag_field(
+    errors: &[ODBCError],
+    return_code: SqlReturn,
+    statement: Option<&Statement>,
+    connection_name: &str,
+    record_number: SmallInt,
+    diag_identifier: DiagIdentifier,
+) -> Result<DiagValue, SqlReturn> {
+    // The fields of the records are read from the record record_number, which must exist.
+    let record = || {
+        if record_number < 1 {
+            return Err(SqlReturn::ERROR);
+        }
+        errors
+            .get(record_number as usize - 1)
+            .ok_or(SqlReturn::NO_DATA)
+    };
+    Ok(match diag_identifier {
+        DiagIdentifier::Number => DiagValue::Integer(errors.len() as Integer),
+        DiagIdentifier::ReturnCode => DiagValue::SmallInt(return_code.0),
+        // The row counts are not supported: no statement inserts, updates or deletes rows, and
+        // result sets are read forward only so the number of rows isn't known before they have
+        // been entirely fetched. -1 means the count is not available.
+        DiagIdentifier::RowCount | DiagIdentifier::CursorRowCount => {
+            statement.ok_or(SqlReturn::ERROR)?;
+            DiagValue::Len(-1)
+        }
+        DiagIdentifier::DynamicFunction => DiagValue::String(
+            statement
+                .ok_or(SqlReturn::ERROR)?
+                .dynamic_function
+                .name()
+                .to_string(),
+        ),
+        DiagIdentifier::DynamicFunctionCode => {
+            DiagValue::Integer(statement.ok_or(SqlReturn::ERROR)?.dynamic_function as Integer)
+        }
+        DiagIdentifier::SqlState => DiagValue::String(record()?.get_sql_state().to_string()),
+        DiagIdentifier::Native => DiagValue::Integer(record()?.get_native_err_code()),
+        DiagIdentifier::MessageText => DiagValue::String(record()?.get_error_message()),
+        DiagIdentifier::ClassOrigin => DiagValue::String(record()?.get_class_origin().to_string()),
+        DiagIdentifier::SubclassOrigin => {
+            DiagValue::String(record()?.get_subclass_origin().to_string())
+        }
+        DiagIdentifier::ConnectionName | DiagIdentifier::ServerName => {
+            record()?;
+            DiagValue::String(connection_name.to_string())
+        }
+        DiagIdentifier::RowNumber => DiagValue::Len(record()?.get_row_number()),
+        DiagIdentifier::ColumnNumber => DiagValue::Integer(record()?.get_column_number()),
+    })
+}
+
+/// write_numeric_diag_field writes the numeric [`value`] of a field of the diagnostics to
+/// [`diag_info_ptr`]. Character values are written by the caller, and are rejected with
+/// SQL_ERROR.
+fn write_numeric_diag_field(value: DiagValue, diag_info_ptr: Pointer) -> SqlReturn {
+    match value {
+        DiagValue::SmallInt(value) => set_output_value(diag_info_ptr as *mut SmallInt, value),
+        DiagValue::Integer(value) => set_output_value(diag_info_ptr as *mut Integer, value),
+        DiagValue::Len(value) => set_output_value(diag_info_ptr as *mut Len, value),
+        DiagValue::String(_) => return SqlReturn::ERROR,
+    }
+    SqlReturn::SUCCESS
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetDiagRec(
-    _handle_type: HandleType,
+    handle_type: HandleType,
     handle: Handle,
-    _rec_number: SmallInt,
-    _state: *mut Char,
-    _native_error_ptr: *mut Integer,
-    _message_text: *mut Char,
-    _buffer_length: SmallInt,
-    _text_length_ptr: *mut SmallInt,
+    rec_number: SmallInt,
+    state: *mut Char,
+    native_error_ptr: *mut Integer,
+    message_text: *mut Char,
+    buffer_length: SmallInt,
+    text_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(handle), "SQLGetDiagRec")
+    panic_safe_exec("SQLGetDiagRec", handle, handle_type, || {
+        if buffer_length < 0 {
+            return SqlReturn::ERROR;
+        }
+        sql_get_diag_rec(handle_type, handle, rec_number, |odbc_err, charset| {
+            util::get_diag_rec(
+                odbc_err,
+                state,
+                message_text,
+                buffer_length,
+                text_length_ptr,
+                native_error_ptr,
+                charset,
+            )
+        })
+    })
 }
 
 #[no_mangle]
@@ -727,25 +2836,44 @@ pub extern "C" fn SQLGetDiagRecW(
     buffer_length: SmallInt,
     text_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    if rec_number < 1 || buffer_length < 0 {
+    panic_safe_exec("SQLGetDiagRecW", handle, handle_type, || {
+        if buffer_length < 0 {
+            return SqlReturn::ERROR;
+        }
+        sql_get_diag_rec(handle_type, handle, rec_number, |odbc_err, _| {
+            util::get_diag_wrec(
+                odbc_err,
+                state,
+                message_text,
+                buffer_length,
+                text_length_ptr,
+                native_error_ptr,
+            )
+        })
+    })
+}
+
+/// sql_get_diag_rec finds the diagnostic record [`rec_number`] of [`handle`] and passes it to
+/// [`write_diag_rec`], along with the charset of the narrow strings of the handle.
+fn sql_get_diag_rec(
+    handle_type: HandleType,
+    handle: Handle,
+    rec_number: SmallInt,
+    write_diag_rec: impl FnOnce(&ODBCError, Charset) -> SqlReturn,
+) -> SqlReturn {
+    if rec_number < 1 {
         return SqlReturn::ERROR;
     }
     let mongo_handle = handle as *mut MongoHandle;
+    let charset = unsafe { (*mongo_handle).charset() };
     // Make the record number zero-indexed
     let rec_number = (rec_number - 1) as usize;
     match handle_type {
         HandleType::Env => match unsafe { (*mongo_handle).as_env() } {
             Some(env) => {
-                let env_contents = (*env).read().unwrap();
+                let env_contents = (*env).read().unwrap_or_else(PoisonError::into_inner);
                 match env_contents.errors.get(rec_number) {
-                    Some(odbc_err) => util::get_diag_rec(
-                        odbc_err,
-                        state,
-                        message_text,
-                        buffer_length,
-                        text_length_ptr,
-                        native_error_ptr,
-                    ),
+                    Some(odbc_err) => write_diag_rec(odbc_err, charset),
                     None => SqlReturn::NO_DATA,
                 }
             }
@@ -753,16 +2881,9 @@ pub extern "C" fn SQLGetDiagRecW(
         },
         HandleType::Dbc => match unsafe { (*mongo_handle).as_connection() } {
             Some(dbc) => {
-                let dbc_contents = (*dbc).read().unwrap();
+                let dbc_contents = (*dbc).read().unwrap_or_else(PoisonError::into_inner);
                 match dbc_contents.errors.get(rec_number) {
-                    Some(odbc_err) => util::get_diag_rec(
-                        odbc_err,
-                        state,
-                        message_text,
-                        buffer_length,
-                        text_length_ptr,
-                        native_error_ptr,
-                    ),
+                    Some(odbc_err) => write_diag_rec(odbc_err, charset),
                     None => SqlReturn::NO_DATA,
                 }
             }
@@ -770,34 +2891,41 @@ pub extern "C" fn SQLGetDiagRecW(
         },
         HandleType::Stmt => match unsafe { (*mongo_handle).as_statement() } {
             Some(stmt) => {
-                let stmt_contents = (*stmt).read().unwrap();
+                let stmt_contents = (*stmt).read().unwrap_or_else(PoisonError::into_inner);
                 match stmt_contents.errors.get(rec_number) {
-                    Some(odbc_err) => util::get_diag_rec(
-                        odbc_err,
-                        state,
-                        message_text,
-                        buffer_length,
-                        text_length_ptr,
-                        native_error_ptr,
-                    ),
+                    Some(odbc_err) => write_diag_rec(odbc_err, charset),
+                    None => SqlReturn::NO_DATA,
+                }
+            }
+            None => SqlReturn::INVALID_HANDLE,
+        },
+        HandleType::Desc => match unsafe { (*mongo_handle).as_descriptor() } {
+            Some(desc) => {
+                let desc_contents = (*desc).read().unwrap_or_else(PoisonError::into_inner);
+                match desc_contents.errors.get(rec_number) {
+                    Some(odbc_err) => write_diag_rec(odbc_err, charset),
                     None => SqlReturn::NO_DATA,
                 }
             }
             None => SqlReturn::INVALID_HANDLE,
         },
-        HandleType::Desc => unimplemented!(),
     }
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetEnvAttr(
     environment_handle: HEnv,
-    _attribute: EnvironmentAttribute,
-    _value_ptr: Pointer,
+    attribute: EnvironmentAttribute,
+    value_ptr: Pointer,
     _buffer_length: Integer,
-    _string_length: *mut Integer,
+    string_length: *mut Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(environment_handle), "SQLGetEnvAttr")
+    panic_safe_exec(
+        "SQLGetEnvAttr",
+        environment_handle as Handle,
+        HandleType::Env,
+        || sql_get_env_attr(environment_handle, attribute, value_ptr, string_length),
+    )
 }
 
 #[no_mangle]
@@ -807,13 +2935,30 @@ pub extern "C" fn SQLGetEnvAttrW(
     value_ptr: Pointer,
     _buffer_length: Integer,
     string_length: *mut Integer,
+) -> SqlReturn {
+    panic_safe_exec(
+        "SQLGetEnvAttrW",
+        environment_handle as Handle,
+        HandleType::Env,
+        || sql_get_env_attr(environment_handle, attribute, value_ptr, string_length),
+    )
+}
+
+/// sql_get_env_attr writes the value of the environment attribute [`attribute`] to
+/// [`value_ptr`]. None of the attributes are strings, so it is shared by SQLGetEnvAttr and
+/// SQLGetEnvAttrW.
+fn sql_get_env_attr(
+    environment_handle: HEnv,
+    attribute: EnvironmentAttribute,
+    value_ptr: Pointer,
+    string_length: *mut Integer,
 ) -> SqlReturn {
     let env_handle = MongoHandleRef::from(environment_handle);
     env_handle.clear_diagnostics();
     match env_handle.as_env() {
         None => SqlReturn::INVALID_HANDLE,
         Some(env) => {
-            let env_contents = env.read().unwrap();
+            let env_contents = env.read().unwrap_or_else(PoisonError::into_inner);
             if value_ptr.is_null() {
                 set_str_length(string_length, 0);
             } else {
@@ -839,37 +2984,220 @@ pub extern "C" fn SQLGetEnvAttrW(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn SQLGetFunctions(
+    connection_handle: HDbc,
+    function_id: USmallInt,
+    supported_ptr: *mut USmallInt,
+) -> SqlReturn {
+    panic_safe_exec(
+        "SQLGetFunctions",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            if conn_handle.as_connection().is_none() {
+                return SqlReturn::INVALID_HANDLE;
+            }
+            match sql_get_functions(function_id) {
+                Ok(supported) => {
+                    set_output_array(supported_ptr, &supported);
+                    SqlReturn::SUCCESS
+                }
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_get_functions returns the values SQLGetFunctions writes for [`function_id`], from the
+/// registry of the exported functions: the SQL_API_ODBC3_ALL_FUNCTIONS bitmap, the
+/// SQL_API_ALL_FUNCTIONS array, or SQL_TRUE or SQL_FALSE for a single function. A function the
+/// driver doesn't export is not supported.
+fn sql_get_functions(function_id: USmallInt) -> Result<Vec<USmallInt>, ODBCError> {
+    match function_id {
+        SQL_API_ODBC3_ALL_FUNCTIONS => Ok(odbc3_functions_bitmap().to_vec()),
+        SQL_API_ALL_FUNCTIONS => Ok(odbc2_functions_array().to_vec()),
+        // The identifiers of the ODBC 3 functions start at 1001, and of the asynchronous
+        // functions at 1550.
+        1..=99 | 1001..=1099 | 1550..=1599 => {
+            let implemented =
+                find_function(function_id).is_some_and(|function| function.implemented);
+            Ok(vec![if implemented {
+                SqlBool::True as USmallInt
+            } else {
+                SqlBool::False as USmallInt
+            }])
+        }
+        _ => Err(ODBCError::FunctionTypeOutOfRange(function_id)),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn SQLGetInfo(
     connection_handle: HDbc,
-    _info_type: InfoType,
-    _info_value_ptr: Pointer,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
+    info_type: USmallInt,
+    info_value_ptr: Pointer,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(connection_handle), "SQLGetInfo")
+    panic_safe_exec(
+        "SQLGetInfo",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            let charset = conn_handle.charset();
+            let conn = match conn_handle.as_connection() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(conn) => conn,
+            };
+            match sql_get_info(conn, info_type) {
+                Ok(InfoValue::String(value)) => {
+                    let sql_return = set_output_string(
+                        &value,
+                        info_value_ptr as *mut Char,
+                        buffer_length.max(0) as usize,
+                        string_length_ptr,
+                        charset,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        conn_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Ok(InfoValue::USmallInt(value)) => {
+                    write_numeric_info(value, info_value_ptr, string_length_ptr);
+                    SqlReturn::SUCCESS
+                }
+                Ok(InfoValue::UInteger(value)) => {
+                    write_numeric_info(value, info_value_ptr, string_length_ptr);
+                    SqlReturn::SUCCESS
+                }
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetInfoW(
-    _connection_handle: HDbc,
-    _info_type: InfoType,
-    _info_value_ptr: Pointer,
-    _buffer_length: SmallInt,
-    _string_length_ptr: *mut SmallInt,
+    connection_handle: HDbc,
+    info_type: USmallInt,
+    info_value_ptr: Pointer,
+    buffer_length: SmallInt,
+    string_length_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLGetInfoW",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || {
+            let conn_handle = MongoHandleRef::from(connection_handle);
+            conn_handle.clear_diagnostics();
+            let conn = match conn_handle.as_connection() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(conn) => conn,
+            };
+            match sql_get_info(conn, info_type) {
+                Ok(InfoValue::String(value)) => {
+                    // The buffer length and the string length are in bytes, not in characters.
+                    let mut string_length = 0;
+                    let sql_return = set_output_wstring(
+                        &value,
+                        info_value_ptr as *mut WChar,
+                        buffer_length.max(0) as usize / size_of::<WChar>(),
+                        &mut string_length,
+                    );
+                    set_output_value(
+                        string_length_ptr,
+                        string_length * size_of::<WChar>() as SmallInt,
+                    );
+                    if sql_return == SqlReturn::SUCCESS_WITH_INFO {
+                        conn_handle.add_diag_info(ODBCError::OutStringTruncated(
+                            buffer_length.max(0) as usize,
+                        ));
+                    }
+                    sql_return
+                }
+                Ok(InfoValue::USmallInt(value)) => {
+                    write_numeric_info(value, info_value_ptr, string_length_ptr);
+                    SqlReturn::SUCCESS
+                }
+                Ok(InfoValue::UInteger(value)) => {
+                    write_numeric_info(value, info_value_ptr, string_length_ptr);
+                    SqlReturn::SUCCESS
+                }
+                Err(error) => {
+                    conn_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_get_info returns the value of the information type [`info_type`]. Most values come from
+/// the static info table, the others depend on the connection. The DBMS version requires the
+/// connection to be open.
+fn sql_get_info(conn: &RwLock<Connection>, info_type: USmallInt) -> Result<InfoValue, ODBCError> {
+    let invalid_info_type = move || ODBCError::InvalidInfoType(info_type);
+    let info_type: InfoType = FromPrimitive::from_u16(info_type).ok_or_else(invalid_info_type)?;
+    if let Some(value) = static_info(info_type) {
+        return Ok(value);
+    }
+    let conn_contents = conn.read().unwrap_or_else(PoisonError::into_inner);
+    Ok(InfoValue::String(match info_type {
+        InfoType::DataSourceName => conn_contents.data_source.name.clone(),
+        InfoType::ServerName => conn_contents.data_source.server.clone(),
+        InfoType::UserName => conn_contents.data_source.user.clone(),
+        InfoType::DatabaseName => conn_contents
+            .mongo_connection
+            .as_ref()
+            .and_then(|mongo_connection| mongo_connection.current_db.clone())
+            .or_else(|| conn_contents.attributes.current_db.clone())
+            .unwrap_or_default(),
+        InfoType::DbmsVer => {
+            let version = conn_contents
+                .mongo_connection
+                .as_ref()
+                .ok_or(ODBCError::ConnectionNotOpen)?
+                .get_dbms_version()
+                .map_err(ODBCError::Core)?;
+            format!("{} {} {}", odbc_version(&version), DBMS_NAME, version)
+        }
+        _ => return Err(invalid_info_type()),
+    }))
+}
+
+/// write_numeric_info writes the SQLUSMALLINT or SQLUINTEGER [`value`] to [`info_value_ptr`] and
+/// its size to [`string_length_ptr`].
+fn write_numeric_info<T>(value: T, info_value_ptr: Pointer, string_length_ptr: *mut SmallInt) {
+    set_output_value(info_value_ptr as *mut T, value);
+    set_output_value(string_length_ptr, size_of::<T>() as SmallInt);
 }
 
 #[no_mangle]
 pub extern "C" fn SQLGetStmtAttr(
     handle: HStmt,
-    _attribute: StatementAttribute,
-    _value_ptr: Pointer,
+    attribute: StatementAttribute,
+    value_ptr: Pointer,
     _buffer_length: Integer,
-    _string_length_ptr: *mut Integer,
+    string_length_ptr: *mut Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(handle), "SQLGetStmtAttr")
+    panic_safe_exec("SQLGetStmtAttr", handle as Handle, HandleType::Stmt, || {
+        sql_get_stmt_attr(handle, attribute, value_ptr, string_length_ptr)
+    })
 }
 
 #[no_mangle]
@@ -879,32 +3207,60 @@ pub extern "C" fn SQLGetStmtAttrW(
     value_ptr: Pointer,
     _buffer_length: Integer,
     string_length_ptr: *mut Integer,
+) -> SqlReturn {
+    panic_safe_exec(
+        "SQLGetStmtAttrW",
+        handle as Handle,
+        HandleType::Stmt,
+        || sql_get_stmt_attr(handle, attribute, value_ptr, string_length_ptr),
+    )
+}
+
+/// sql_get_stmt_attr writes the value of the statement attribute [`attribute`] to
+/// [`value_ptr`]. None of the attributes are strings, so it is shared by SQLGetStmtAttr and
+/// SQLGetStmtAttrW.
+fn sql_get_stmt_attr(
+    handle: HStmt,
+    attribute: StatementAttribute,
+    value_ptr: Pointer,
+    string_length_ptr: *mut Integer,
 ) -> SqlReturn {
     let stmt_handle = MongoHandleRef::from(handle);
     stmt_handle.clear_diagnostics();
     match stmt_handle.as_statement() {
         None => SqlReturn::INVALID_HANDLE,
         Some(stmt) => {
-            let stmt_contents = stmt.read().unwrap();
+            let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+            let descriptors = stmt_contents
+                .ard()
+                .and_then(|ard| stmt_contents.ird().map(|ird| (ard, ird)));
+            let (ard, ird) = match descriptors {
+                Ok(descriptors) => descriptors,
+                Err(error) => {
+                    drop(stmt_contents);
+                    stmt_handle.add_diag_info(error);
+                    return SqlReturn::ERROR;
+                }
+            };
             if !value_ptr.is_null() {
                 // Most attributes have type SQLULEN, so default to the size of that
                 // type.
                 set_str_length(string_length_ptr, size_of::<ULen>() as Integer);
                 match attribute {
                     StatementAttribute::AppRowDesc => unsafe {
-                        *(value_ptr as *mut Pointer) = stmt_contents.attributes.app_row_desc;
+                        *(value_ptr as *mut _) = stmt_contents.attributes.app_row_desc;
                         set_str_length(string_length_ptr, size_of::<Pointer>() as Integer);
                     },
                     StatementAttribute::AppParamDesc => unsafe {
-                        *(value_ptr as *mut Pointer) = stmt_contents.attributes.app_param_desc;
+                        *(value_ptr as *mut _) = stmt_contents.attributes.app_param_desc;
                         set_str_length(string_length_ptr, size_of::<Pointer>() as Integer);
                     },
                     StatementAttribute::ImpRowDesc => unsafe {
-                        *(value_ptr as *mut Pointer) = stmt_contents.attributes.imp_row_desc;
+                        *(value_ptr as *mut _) = stmt_contents.attributes.imp_row_desc;
                         set_str_length(string_length_ptr, size_of::<Pointer>() as Integer);
                     },
                     StatementAttribute::ImpParamDesc => unsafe {
-                        *(value_ptr as *mut Pointer) = stmt_contents.attributes.imp_param_desc;
+                        *(value_ptr as *mut _) = stmt_contents.attributes.imp_param_desc;
                         set_str_length(string_length_ptr, size_of::<Pointer>() as Integer);
                     },
                     StatementAttribute::FetchBookmarkPtr => unsafe {
@@ -972,11 +3328,15 @@ pub extern "C" fn SQLGetStmtAttrW(
                         *(value_ptr as *mut RetrieveData) = stmt_contents.attributes.retrieve_data;
                     },
                     StatementAttribute::RowBindOffsetPtr => unsafe {
-                        *(value_ptr as *mut _) = stmt_contents.attributes.row_bind_offset_ptr;
+                        *(value_ptr as *mut _) = ard
+                            .read()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .bind_offset_ptr;
                         set_str_length(string_length_ptr, size_of::<*mut ULen>() as Integer)
                     },
                     StatementAttribute::RowBindType => unsafe {
-                        *(value_ptr as *mut ULen) = stmt_contents.attributes.row_bind_type;
+                        *(value_ptr as *mut ULen) =
+                            ard.read().unwrap_or_else(PoisonError::into_inner).bind_type;
                     },
                     StatementAttribute::RowNumber => unsafe {
                         *(value_ptr as *mut ULen) = stmt_contents.attributes.row_number;
@@ -986,15 +3346,24 @@ pub extern "C" fn SQLGetStmtAttrW(
                         set_str_length(string_length_ptr, size_of::<*mut USmallInt>() as Integer)
                     },
                     StatementAttribute::RowStatusPtr => unsafe {
-                        *(value_ptr as *mut _) = stmt_contents.attributes.row_status_ptr;
+                        *(value_ptr as *mut _) = ird
+                            .read()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .array_status_ptr;
                         set_str_length(string_length_ptr, size_of::<*mut USmallInt>() as Integer)
                     },
                     StatementAttribute::RowsFetchedPtr => unsafe {
-                        *(value_ptr as *mut _) = stmt_contents.attributes.rows_fetched_ptr;
+                        *(value_ptr as *mut _) = ird
+                            .read()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .rows_processed_ptr;
                         set_str_length(string_length_ptr, size_of::<*mut ULen>() as Integer)
                     },
                     StatementAttribute::RowArraySize => unsafe {
-                        *(value_ptr as *mut ULen) = stmt_contents.attributes.row_array_size;
+                        *(value_ptr as *mut ULen) = ard
+                            .read()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .array_size;
                     },
                     StatementAttribute::SimulateCursor => unsafe {
                         *(value_ptr as *mut ULen) = stmt_contents.attributes.simulate_cursor;
@@ -1013,13 +3382,61 @@ pub extern "C" fn SQLGetStmtAttrW(
 }
 
 #[no_mangle]
-pub extern "C" fn SQLGetTypeInfo(_handle: HStmt, _data_type: SqlDataType) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLGetTypeInfo(handle: HStmt, data_type: SqlDataType) -> SqlReturn {
+    panic_safe_exec("SQLGetTypeInfo", handle as Handle, HandleType::Stmt, || {
+        let stmt_handle = MongoHandleRef::from(handle);
+        stmt_handle.clear_diagnostics();
+        let stmt = match stmt_handle.as_statement() {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(stmt) => stmt,
+        };
+        match sql_get_type_info(stmt, data_type) {
+            Ok(_) => SqlReturn::SUCCESS,
+            Err(error) => {
+                stmt_handle.add_diag_info(error);
+                SqlReturn::ERROR
+            }
+        }
+    })
+}
+
+/// sql_get_type_info stores on the statement the result set describing the types mapped to the
+/// given SQL type, or all the supported types for SQL_ALL_TYPES.
+fn sql_get_type_info(stmt: &RwLock<Statement>, data_type: SqlDataType) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::ExecDirect("SQLGetTypeInfo"))?;
+    stmt_contents.discard_result_set();
+    stmt_contents.mongo_statement = Some(Box::new(MongoTypesInfo::list_types_info(data_type)));
+    stmt_contents.state = StatementState::ExecutedHasResultSet;
+    Ok(())
 }
 
 #[no_mangle]
-pub extern "C" fn SQLMoreResults(_handle: HStmt) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLMoreResults(handle: HStmt) -> SqlReturn {
+    panic_safe_exec("SQLMoreResults", handle as Handle, HandleType::Stmt, || {
+        let stmt_handle = MongoHandleRef::from(handle);
+        stmt_handle.clear_diagnostics();
+        let stmt = match stmt_handle.as_statement() {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(stmt) => stmt,
+        };
+        match sql_more_results(stmt) {
+            Ok(_) => SqlReturn::NO_DATA,
+            Err(error) => {
+                stmt_handle.add_diag_info(error);
+                SqlReturn::ERROR
+            }
+        }
+    })
+}
+
+/// sql_more_results moves to the next result set of the statement. A query has a single result
+/// set, so there never is one: the cursor is closed and SQL_NO_DATA returned.
+fn sql_more_results(stmt: &RwLock<Statement>) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::Other("SQLMoreResults"))?;
+    stmt_contents.close_cursor();
+    Ok(())
 }
 
 #[no_mangle]
@@ -1031,19 +3448,29 @@ pub extern "C" fn SQLNativeSql(
     _buffer_len: Integer,
     _out_statement_len: *mut Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(connection_handle), "SQLNativeSql")
+    panic_safe_exec(
+        "SQLNativeSql",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || unsupported_function(MongoHandleRef::from(connection_handle), "SQLNativeSql"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLNativeSqlW(
-    _connection_handle: HDbc,
+    connection_handle: HDbc,
     _in_statement_text: *const WChar,
     _in_statement_len: Integer,
     _out_statement_text: *mut WChar,
     _buffer_len: Integer,
     _out_statement_len: *mut Integer,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLNativeSqlW",
+        connection_handle as Handle,
+        HandleType::Dbc,
+        || unsupported_function(MongoHandleRef::from(connection_handle), "SQLNativeSqlW"),
+    )
 }
 
 #[no_mangle]
@@ -1051,64 +3478,210 @@ pub extern "C" fn SQLNumParams(
     statement_handle: HStmt,
     _param_count_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLNumParams")
+    panic_safe_exec(
+        "SQLNumParams",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLNumParams"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLNumResultCols(
     statement_handle: HStmt,
-    _column_count_ptr: *mut SmallInt,
+    column_count_ptr: *mut SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLNumResultCols")
+    panic_safe_exec(
+        "SQLNumResultCols",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            let stmt_handle = MongoHandleRef::from(statement_handle);
+            stmt_handle.clear_diagnostics();
+            let stmt = match stmt_handle.as_statement() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(stmt) => stmt,
+            };
+            match sql_num_result_cols(stmt) {
+                Ok(column_count) => {
+                    set_output_value(column_count_ptr, column_count);
+                    SqlReturn::SUCCESS
+                }
+                Err(error) => {
+                    stmt_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_num_result_cols returns the number of columns of the statement's result set.
+fn sql_num_result_cols(stmt: &RwLock<Statement>) -> Result<SmallInt, ODBCError> {
+    let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::DescribeResultSet("SQLNumResultCols"))?;
+    let mongo_statement = stmt_contents
+        .mongo_statement
+        .as_ref()
+        .ok_or(ODBCError::FunctionSequenceError("SQLNumResultCols"))?;
+    Ok(mongo_statement._get_col_count() as SmallInt)
 }
 
 #[no_mangle]
 pub extern "C" fn SQLParamData(hstmt: HStmt, _value_ptr_ptr: *mut Pointer) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(hstmt), "SQLParamData")
+    panic_safe_exec("SQLParamData", hstmt as Handle, HandleType::Stmt, || {
+        unsupported_function(MongoHandleRef::from(hstmt), "SQLParamData")
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLPrepare(
     hstmt: HStmt,
-    _statement_text: *const Char,
-    _text_length: Integer,
+    statement_text: *const Char,
+    text_length: Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(hstmt), "SQLPrepare")
+    panic_safe_exec("SQLPrepare", hstmt as Handle, HandleType::Stmt, || {
+        let stmt_handle = MongoHandleRef::from(hstmt);
+        stmt_handle.clear_diagnostics();
+        let charset = stmt_handle.charset();
+        let stmt = match stmt_handle.as_statement() {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(stmt) => stmt,
+        };
+        let query = input_text_to_string(statement_text, text_length as isize, charset);
+        match sql_prepare(stmt, &query) {
+            Ok(_) => SqlReturn::SUCCESS,
+            Err(error) => {
+                stmt_handle.add_diag_info(error);
+                SqlReturn::ERROR
+            }
+        }
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLPrepareW(
     hstmt: HStmt,
-    _statement_text: *const WChar,
-    _text_length: Integer,
+    statement_text: *const WChar,
+    text_length: Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(hstmt), "SQLPrepareW")
+    panic_safe_exec("SQLPrepareW", hstmt as Handle, HandleType::Stmt, || {
+        let stmt_handle = MongoHandleRef::from(hstmt);
+        stmt_handle.clear_diagnostics();
+        let stmt = match stmt_handle.as_statement() {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(stmt) => stmt,
+        };
+        let query = input_wtext_to_string(statement_text, text_length as isize);
+        match sql_prepare(stmt, &query) {
+            Ok(_) => SqlReturn::SUCCESS,
+            Err(error) => {
+                stmt_handle.add_diag_info(error);
+                SqlReturn::ERROR
+            }
+        }
+    })
+}
+
+/// sql_prepare prepares the given MongoSQL query on the current database of the statement's
+/// connection, to be executed by SQLExecute. The result set metadata is retrieved so that the
+/// columns can be described before the query is executed.
+fn sql_prepare(stmt: &RwLock<Statement>, query: &str) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::Prepare)?;
+    let conn = unsafe { stmt_contents.connection.as_ref() }
+        .and_then(MongoHandle::as_connection)
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    let conn_contents = conn.read().unwrap_or_else(PoisonError::into_inner);
+    let mongo_connection = conn_contents
+        .mongo_connection
+        .as_ref()
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    // A call failing before the query is sent leaves the previous result set untouched, while a
+    // query failing on the server discards it, as the statement transitions require.
+    stmt_contents.discard_result_set();
+    let mongo_query = MongoQuery::prepare(mongo_connection, query).map_err(ODBCError::Core)?;
+    stmt_contents.dynamic_function = DynamicFunction::SelectCursor;
+    stmt_contents.mongo_statement = Some(Box::new(mongo_query));
+    stmt_contents.is_prepared = true;
+    stmt_contents.state = StatementState::PreparedHasResultSet;
+    Ok(())
 }
 
 #[no_mangle]
 pub extern "C" fn SQLPrimaryKeys(
     statement_handle: HStmt,
-    _catalog_name: *const Char,
-    _catalog_name_length: SmallInt,
+    catalog_name: *const Char,
+    catalog_name_length: SmallInt,
     _schema_name: *const Char,
     _schema_name_length: SmallInt,
-    _table_name: *const Char,
-    _table_name_length: SmallInt,
+    table_name: *const Char,
+    table_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLPrimaryKeys")
+    panic_safe_exec(
+        "SQLPrimaryKeys",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, charset| {
+                let argument = |text, text_length| input_text_argument(text, text_length, charset);
+                sql_primary_keys(
+                    stmt,
+                    argument(catalog_name, catalog_name_length),
+                    argument(table_name, table_name_length),
+                )
+            })
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLPrimaryKeysW(
-    _statement_handle: HStmt,
-    _catalog_name: *const WChar,
-    _catalog_name_length: SmallInt,
+    statement_handle: HStmt,
+    catalog_name: *const WChar,
+    catalog_name_length: SmallInt,
     _schema_name: *const WChar,
     _schema_name_length: SmallInt,
-    _table_name: *const WChar,
-    _table_name_length: SmallInt,
+    table_name: *const WChar,
+    table_name_length: SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLPrimaryKeysW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, _| {
+                sql_primary_keys(
+                    stmt,
+                    input_wtext_argument(catalog_name, catalog_name_length),
+                    input_wtext_argument(table_name, table_name_length),
+                )
+            })
+        },
+    )
+}
+
+/// sql_primary_keys stores on the statement the SQLPrimaryKeys result set listing the _id
+/// primary key of the collection [`table_name`] of the database [`catalog_name`]. Neither
+/// argument is a search pattern. A [`None`] catalog name was passed as a null pointer and
+/// matches all the databases, while the table name is required. Views have no primary key.
+fn sql_primary_keys(
+    stmt: &RwLock<Statement>,
+    catalog_name: Option<String>,
+    table_name: Option<String>,
+) -> Result<(), ODBCError> {
+    let table_name = table_name.ok_or(ODBCError::InvalidUseOfNullPointer("TableName"))?;
+    let db_name_filter = catalog_name.map_or(SQL_ALL.to_string(), |catalog_name| {
+        escape_pattern(&catalog_name)
+    });
+    exec_catalog_function(stmt, "SQLPrimaryKeys", |mongo_connection, query_timeout| {
+        MongoPrimaryKeys::list_primary_keys(
+            mongo_connection,
+            query_timeout,
+            &db_name_filter,
+            &escape_pattern(&table_name),
+        )
+    })
 }
 
 #[no_mangle]
@@ -1123,9 +3696,16 @@ pub extern "C" fn SQLProcedureColumns(
     _column_name: *const Char,
     _column_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(
-        MongoHandleRef::from(statement_handle),
+    panic_safe_exec(
         "SQLProcedureColumns",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            unsupported_function(
+                MongoHandleRef::from(statement_handle),
+                "SQLProcedureColumns",
+            )
+        },
     )
 }
 
@@ -1141,9 +3721,16 @@ pub extern "C" fn SQLProcedureColumnsW(
     _column_name: *const WChar,
     _column_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(
-        MongoHandleRef::from(statement_handle),
+    panic_safe_exec(
         "SQLProcedureColumnsW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            unsupported_function(
+                MongoHandleRef::from(statement_handle),
+                "SQLProcedureColumnsW",
+            )
+        },
     )
 }
 
@@ -1157,7 +3744,12 @@ pub extern "C" fn SQLProcedures(
     _proc_name: *const Char,
     _proc_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLProcedures")
+    panic_safe_exec(
+        "SQLProcedures",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLProcedures"),
+    )
 }
 
 #[no_mangle]
@@ -1170,7 +3762,12 @@ pub extern "C" fn SQLProceduresW(
     _proc_name: *const WChar,
     _proc_name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLProceduresW")
+    panic_safe_exec(
+        "SQLProceduresW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLProceduresW"),
+    )
 }
 
 #[no_mangle]
@@ -1179,12 +3776,22 @@ pub extern "C" fn SQLPutData(
     _data_ptr: Pointer,
     _str_len_or_ind_ptr: Len,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLPutData")
+    panic_safe_exec(
+        "SQLPutData",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLPutData"),
+    )
 }
 
 #[no_mangle]
-pub extern "C" fn SQLRowCount(_statement_handle: HStmt, _row_count_ptr: *mut Len) -> SqlReturn {
-    unimplemented!()
+pub extern "C" fn SQLRowCount(statement_handle: HStmt, _row_count_ptr: *mut Len) -> SqlReturn {
+    panic_safe_exec(
+        "SQLRowCount",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLRowCount"),
+    )
 }
 
 #[no_mangle]
@@ -1194,17 +3801,24 @@ pub extern "C" fn SQLSetConnectAttr(
     _value: Pointer,
     _str_length: Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(hdbc), "SQLSetConnectAttr")
+    panic_safe_exec("SQLSetConnectAttr", hdbc as Handle, HandleType::Dbc, || {
+        unsupported_function(MongoHandleRef::from(hdbc), "SQLSetConnectAttr")
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn SQLSetConnectAttrW(
-    _hdbc: HDbc,
+    hdbc: HDbc,
     _attr: ConnectionAttribute,
     _value: Pointer,
     _str_length: Integer,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLSetConnectAttrW",
+        hdbc as Handle,
+        HandleType::Dbc,
+        || unsupported_function(MongoHandleRef::from(hdbc), "SQLSetConnectAttrW"),
+    )
 }
 
 #[no_mangle]
@@ -1213,43 +3827,229 @@ pub extern "C" fn SQLSetCursorName(
     _cursor_name: *const Char,
     _name_length: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLSetCursorName")
+    panic_safe_exec(
+        "SQLSetCursorName",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLSetCursorName"),
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn SQLSetCursorNameW(
+    statement_handle: HStmt,
+    _cursor_name: *const WChar,
+    _name_length: SmallInt,
+) -> SqlReturn {
+    panic_safe_exec(
+        "SQLSetCursorNameW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLSetCursorNameW"),
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn SQLSetDescField(
+    desc_handle: HDesc,
+    rec_number: SmallInt,
+    field_identifier: SmallInt,
+    value_ptr: Pointer,
+    _buffer_length: Integer,
+) -> SqlReturn {
+    panic_safe_exec(
+        "SQLSetDescField",
+        desc_handle as Handle,
+        HandleType::Desc,
+        || {
+            let mongo_handle = MongoHandleRef::from(desc_handle);
+            mongo_handle.clear_diagnostics();
+            let desc = match mongo_handle.as_descriptor() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(desc) => desc,
+            };
+            match sql_set_desc_field(desc, rec_number, field_identifier, value_ptr) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    mongo_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
+}
+
+/// sql_set_desc_field sets the field [`field_identifier`] of the header of [`desc`], or of its
+/// record [`record_number`] for the fields of the records, to [`value`], which holds the value
+/// itself for the integer fields. Setting a record field other than the deferred
+/// SQL_DESC_DATA_PTR, SQL_DESC_INDICATOR_PTR and SQL_DESC_OCTET_LENGTH_PTR unbinds the record,
+/// and binding a record checks its consistency.
+/// Implementation row descriptors are read-only, except for their status array and the number of
+/// rows processed.
+fn sql_set_desc_field(
+    desc: &RwLock<Descriptor>,
+    record_number: SmallInt,
+    field_identifier: SmallInt,
+    value: Pointer,
+) -> Result<(), ODBCError> {
+    let field = desc_field_identifier(field_identifier)?;
+    let mut desc = desc.write().unwrap_or_else(PoisonError::into_inner);
+    let is_application = desc.desc_kind == DescriptorKind::Application;
+    match field {
+        Desc::ArrayStatusPtr => desc.array_status_ptr = value as *mut USmallInt,
+        Desc::RowsProcessedPtr if !is_application => desc.rows_processed_ptr = value as *mut ULen,
+        _ if desc.desc_kind == DescriptorKind::ImplementationRow => {
+            return Err(ODBCError::CannotModifyIrd)
+        }
+        Desc::ArraySize if is_application => {
+            if value as ULen == 0 {
+                return Err(ODBCError::InvalidAttrValue("SQL_DESC_ARRAY_SIZE"));
+            }
+            desc.array_size = value as ULen;
+        }
+        Desc::BindOffsetPtr if is_application => desc.bind_offset_ptr = value as *mut ULen,
+        Desc::BindType if is_application => desc.bind_type = value as ULen,
+        Desc::Count => {
+            let count = value as SmallInt;
+            if count < 0 {
+                return Err(ODBCError::InvalidDescriptorIndex(count.into()));
+            }
+            desc.records.resize(count as usize, DescRecord::default());
+        }
+        // SQL_DESC_ALLOC_TYPE is read-only.
+        Desc::AllocType
+        | Desc::ArraySize
+        | Desc::BindOffsetPtr
+        | Desc::BindType
+        | Desc::RowsProcessedPtr => {
+            return Err(ODBCError::InvalidFieldIdentifier(field_identifier))
+        }
+        _ => {
+            if record_number < 1 {
+                return Err(ODBCError::InvalidDescriptorIndex(record_number.into()));
+            }
+            let mut record = desc
+                .records
+                .get(record_number as usize - 1)
+                .copied()
+                .unwrap_or_default();
+            match field {
+                Desc::Type => {
+                    record.desc_type = value as SmallInt;
+                    record.datetime_interval_code = 0;
+                }
+                Desc::ConciseType => record.set_concise_type(value as SmallInt),
+                Desc::DatetimeIntervalCode => record.datetime_interval_code = value as SmallInt,
+                Desc::DataPtr => record.data_ptr = value,
+                Desc::IndicatorPtr => record.indicator_ptr = value as *mut Len,
+                Desc::OctetLengthPtr => record.octet_length_ptr = value as *mut Len,
+                Desc::OctetLength => record.octet_length = value as Len,
+                Desc::Length => record.length = value as ULen,
+                Desc::Precision => record.precision = value as SmallInt,
+                Desc::Scale => record.scale = value as SmallInt,
+                _ => return Err(ODBCError::InvalidFieldIdentifier(field_identifier)),
+            }
+            if !matches!(
+                field,
+                Desc::DataPtr | Desc::IndicatorPtr | Desc::OctetLengthPtr
+            ) {
+                record.data_ptr = null_mut();
+            }
+            check_record_consistency(&record)?;
+            *desc.record_mut(record_number as USmallInt) = record;
+        }
+    }
+    Ok(())
 }
 
-#[no_mangle]
-pub extern "C" fn SQLSetCursorNameW(
-    _statement_handle: HStmt,
-    _cursor_name: *const WChar,
-    _name_length: SmallInt,
-) -> SqlReturn {
-    unimplemented!()
+/// check_record_consistency checks that a bound record can be fetched into: its type must be a
+/// supported C type and its buffer length can't be negative.
+fn check_record_consistency(record: &DescRecord) -> Result<(), ODBCError> {
+    if record.data_ptr.is_null() {
+        return Ok(());
+    }
+    if c_type_from_code(record.concise_type()).is_none() {
+        return Err(ODBCError::InconsistentDescriptorInformation(format!(
+            "unsupported C type {}",
+            record.concise_type()
+        )));
+    }
+    if record.octet_length < 0 {
+        return Err(ODBCError::InconsistentDescriptorInformation(format!(
+            "negative octet length {}",
+            record.octet_length
+        )));
+    }
+    Ok(())
 }
 
 #[no_mangle]
-pub extern "C" fn SQLSetDescField(
-    _desc_handle: HDesc,
-    _rec_number: SmallInt,
-    _field_identifier: SmallInt,
-    _value_ptr: Pointer,
-    _buffer_length: Integer,
+pub extern "C" fn SQLSetDescRec(
+    desc_handle: HDesc,
+    rec_number: SmallInt,
+    desc_type: SmallInt,
+    desc_sub_type: SmallInt,
+    length: Len,
+    precision: SmallInt,
+    scale: SmallInt,
+    data_ptr: Pointer,
+    string_length_ptr: *const Len,
+    indicator_ptr: *const Len,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLSetDescRec",
+        desc_handle as Handle,
+        HandleType::Desc,
+        || {
+            let mongo_handle = MongoHandleRef::from(desc_handle);
+            mongo_handle.clear_diagnostics();
+            let desc = match mongo_handle.as_descriptor() {
+                None => return SqlReturn::INVALID_HANDLE,
+                Some(desc) => desc,
+            };
+            // The subtype is the datetime interval code of the datetime types.
+            let record = DescRecord {
+                desc_type,
+                datetime_interval_code: if desc_type == SqlDataType::DATETIME.0 {
+                    desc_sub_type
+                } else {
+                    0
+                },
+                data_ptr,
+                octet_length: length,
+                octet_length_ptr: string_length_ptr as *mut Len,
+                indicator_ptr: indicator_ptr as *mut Len,
+                precision,
+                scale,
+                ..Default::default()
+            };
+            match sql_set_desc_rec(desc, rec_number, record) {
+                Ok(_) => SqlReturn::SUCCESS,
+                Err(error) => {
+                    mongo_handle.add_diag_info(error);
+                    SqlReturn::ERROR
+                }
+            }
+        },
+    )
 }
 
-#[no_mangle]
-pub extern "C" fn SQLSetDescRec(
-    _desc_handle: HDesc,
-    _rec_number: SmallInt,
-    _desc_type: SmallInt,
-    _desc_sub_type: SmallInt,
-    _length: Len,
-    _precision: SmallInt,
-    _scale: SmallInt,
-    _data_ptr: Pointer,
-    _string_length_ptr: *const Len,
-    _indicator_ptr: *const Len,
-) -> SqlReturn {
-    unimplemented!()
+/// sql_set_desc_rec replaces the record [`record_number`] of [`desc`] with [`record`].
+fn sql_set_desc_rec(
+    desc: &RwLock<Descriptor>,
+    record_number: SmallInt,
+    record: DescRecord,
+) -> Result<(), ODBCError> {
+    let mut desc = desc.write().unwrap_or_else(PoisonError::into_inner);
+    if desc.desc_kind == DescriptorKind::ImplementationRow {
+        return Err(ODBCError::CannotModifyIrd);
+    }
+    if record_number < 1 {
+        return Err(ODBCError::InvalidDescriptorIndex(record_number.into()));
+    }
+    check_record_consistency(&record)?;
+    *desc.record_mut(record_number as USmallInt) = record;
+    Ok(())
 }
 
 #[no_mangle]
@@ -1259,17 +4059,27 @@ pub extern "C" fn SQLSetPos(
     _operation: USmallInt,
     _lock_type: USmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLSetPos")
+    panic_safe_exec(
+        "SQLSetPos",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLSetPos"),
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLSetEnvAttr(
     environment_handle: HEnv,
-    _attribute: EnvironmentAttribute,
-    _value: Pointer,
+    attribute: EnvironmentAttribute,
+    value: Pointer,
     _string_length: Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(environment_handle), "SQLSetEnvAttr")
+    panic_safe_exec(
+        "SQLSetEnvAttr",
+        environment_handle as Handle,
+        HandleType::Env,
+        || sql_set_env_attr(environment_handle, attribute, value),
+    )
 }
 
 #[no_mangle]
@@ -1278,15 +4088,38 @@ pub extern "C" fn SQLSetEnvAttrW(
     attribute: EnvironmentAttribute,
     value: Pointer,
     _string_length: Integer,
+) -> SqlReturn {
+    panic_safe_exec(
+        "SQLSetEnvAttrW",
+        environment_handle as Handle,
+        HandleType::Env,
+        || sql_set_env_attr(environment_handle, attribute, value),
+    )
+}
+
+/// sql_set_env_attr sets the environment attribute [`attribute`] to [`value`]. None of the
+/// attributes are strings, so it is shared by SQLSetEnvAttr and SQLSetEnvAttrW.
+fn sql_set_env_attr(
+    environment_handle: HEnv,
+    attribute: EnvironmentAttribute,
+    value: Pointer,
 ) -> SqlReturn {
     let env_handle = MongoHandleRef::from(environment_handle);
     env_handle.clear_diagnostics();
     match env_handle.as_env() {
         None => SqlReturn::INVALID_HANDLE,
+        // The environment attributes can't change once connections are allocated on it.
+        Some(env)
+            if env.read().unwrap_or_else(PoisonError::into_inner).state
+                == EnvState::ConnectionAllocated =>
+        {
+            env_handle.add_diag_info(ODBCError::AttributeCannotBeSetNow(env_attr_name(attribute)));
+            SqlReturn::ERROR
+        }
         Some(env) => match attribute {
             EnvironmentAttribute::OdbcVersion => match FromPrimitive::from_i32(value as i32) {
                 Some(version) => {
-                    let mut env_contents = (*env).write().unwrap();
+                    let mut env_contents = (*env).write().unwrap_or_else(PoisonError::into_inner);
                     env_contents.attributes.odbc_ver = version;
                     SqlReturn::SUCCESS
                 }
@@ -1328,14 +4161,26 @@ pub extern "C" fn SQLSetEnvAttrW(
     }
 }
 
+/// env_attr_name returns the name of the environment attribute [`attribute`], for diagnostics.
+fn env_attr_name(attribute: EnvironmentAttribute) -> &'static str {
+    match attribute {
+        EnvironmentAttribute::OdbcVersion => "SQL_ATTR_ODBC_VERSION",
+        EnvironmentAttribute::ConnectionPooling => "SQL_ATTR_CONNECTION_POOLING",
+        EnvironmentAttribute::CpMatch => "SQL_ATTR_CP_MATCH",
+        EnvironmentAttribute::OutputNts => "SQL_ATTR_OUTPUT_NTS",
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn SQLSetStmtAttr(
     hstmt: HStmt,
-    _attr: StatementAttribute,
-    _value: Pointer,
+    attr: StatementAttribute,
+    value: Pointer,
     _str_length: Integer,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(hstmt), "SQLSetStmtAttr")
+    panic_safe_exec("SQLSetStmtAttr", hstmt as Handle, HandleType::Stmt, || {
+        sql_set_stmt_attr(hstmt, attr, value)
+    })
 }
 
 #[no_mangle]
@@ -1345,27 +4190,40 @@ pub extern "C" fn SQLSetStmtAttrW(
     value: Pointer,
     _str_length: Integer,
 ) -> SqlReturn {
+    panic_safe_exec("SQLSetStmtAttrW", hstmt as Handle, HandleType::Stmt, || {
+        sql_set_stmt_attr(hstmt, attr, value)
+    })
+}
+
+/// sql_set_stmt_attr sets the statement attribute [`attr`] to [`value`]. None of the attributes
+/// are strings, so it is shared by SQLSetStmtAttr and SQLSetStmtAttrW.
+fn sql_set_stmt_attr(hstmt: HStmt, attr: StatementAttribute, value: Pointer) -> SqlReturn {
     let stmt_handle = MongoHandleRef::from(hstmt);
     stmt_handle.clear_diagnostics();
+    let state_check = stmt_handle.as_statement().map(|stmt| {
+        stmt.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .check_state(stmt_attr_function(attr))
+    });
+    if let Some(Err(error)) = state_check {
+        stmt_handle.add_diag_info(error);
+        return SqlReturn::ERROR;
+    }
     match stmt_handle.as_statement() {
         None => SqlReturn::INVALID_HANDLE,
         Some(stmt) => match attr {
-            StatementAttribute::AppRowDesc => {
-                stmt_handle.add_diag_info(ODBCError::Unimplemented("SQL_ATTR_APP_ROW_DESC"));
-                SqlReturn::ERROR
-            }
-            StatementAttribute::AppParamDesc => {
-                stmt_handle.add_diag_info(ODBCError::Unimplemented("SQL_ATTR_APP_PARAM_DESC"));
-                SqlReturn::ERROR
-            }
-            StatementAttribute::ImpRowDesc => {
-                // TODO: SQL_681, determine the correct SQL state
-                stmt_handle.add_diag_info(ODBCError::Unimplemented("SQL_ATTR_IMP_ROW_DESC"));
-                SqlReturn::ERROR
+            StatementAttribute::AppRowDesc | StatementAttribute::AppParamDesc => {
+                match sql_set_app_desc(stmt, attr, value as *mut MongoHandle) {
+                    Ok(_) => SqlReturn::SUCCESS,
+                    Err(error) => {
+                        stmt_handle.add_diag_info(error);
+                        SqlReturn::ERROR
+                    }
+                }
             }
-            StatementAttribute::ImpParamDesc => {
-                // TODO: SQL_681, determine the correct SQL state
-                stmt_handle.add_diag_info(ODBCError::Unimplemented("SQL_ATTR_IMP_PARAM_DESC"));
+            // The implementation descriptors can't be replaced.
+            StatementAttribute::ImpRowDesc | StatementAttribute::ImpParamDesc => {
+                stmt_handle.add_diag_info(ODBCError::InvalidUseOfAutomaticDescriptor);
                 SqlReturn::ERROR
             }
             StatementAttribute::CursorScrollable => {
@@ -1428,14 +4286,15 @@ pub extern "C" fn SQLSetStmtAttrW(
                 SqlReturn::ERROR
             }
             StatementAttribute::MaxRows => {
-                let mut stmt_contents = stmt.write().unwrap();
+                let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
                 stmt_contents.attributes.max_rows = value as ULen;
                 SqlReturn::SUCCESS
             }
             StatementAttribute::NoScan => {
                 match FromPrimitive::from_i32(value as i32) {
                     Some(ns) => {
-                        let mut stmt_contents = stmt.write().unwrap();
+                        let mut stmt_contents =
+                            stmt.write().unwrap_or_else(PoisonError::into_inner);
                         stmt_contents.attributes.no_scan = ns
                     }
                     None => {
@@ -1471,7 +4330,7 @@ pub extern "C" fn SQLSetStmtAttrW(
                 SqlReturn::ERROR
             }
             StatementAttribute::QueryTimeout => {
-                let mut stmt_contents = stmt.write().unwrap();
+                let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
                 stmt_contents.attributes.query_timeout = value as ULen;
                 SqlReturn::SUCCESS
             }
@@ -1484,16 +4343,28 @@ pub extern "C" fn SQLSetStmtAttrW(
                 }
             },
             StatementAttribute::RowBindOffsetPtr => {
-                stmt_handle.add_diag_info(ODBCError::Unimplemented("SQL_ATTR_ROW_BIND_OFFSET_PTR"));
-                SqlReturn::ERROR
+                match set_stmt_desc_field(stmt, Statement::ard, |ard| {
+                    ard.bind_offset_ptr = value as *mut ULen
+                }) {
+                    Ok(_) => SqlReturn::SUCCESS,
+                    Err(error) => {
+                        stmt_handle.add_diag_info(error);
+                        SqlReturn::ERROR
+                    }
+                }
             }
             StatementAttribute::RowBindType => {
-                let mut stmt_contents = stmt.write().unwrap();
-                stmt_contents.attributes.row_bind_type = value as ULen;
-                SqlReturn::SUCCESS
+                match set_stmt_desc_field(stmt, Statement::ard, |ard| ard.bind_type = value as ULen)
+                {
+                    Ok(_) => SqlReturn::SUCCESS,
+                    Err(error) => {
+                        stmt_handle.add_diag_info(error);
+                        SqlReturn::ERROR
+                    }
+                }
             }
             StatementAttribute::RowNumber => {
-                let mut stmt_contents = stmt.write().unwrap();
+                let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
                 stmt_contents.attributes.row_number = value as ULen;
                 SqlReturn::SUCCESS
             }
@@ -1502,20 +4373,36 @@ pub extern "C" fn SQLSetStmtAttrW(
                 SqlReturn::ERROR
             }
             StatementAttribute::RowStatusPtr => {
-                let mut stmt_contents = stmt.write().unwrap();
-                stmt_contents.attributes.row_status_ptr = value as *mut USmallInt;
-                SqlReturn::SUCCESS
+                match set_stmt_desc_field(stmt, Statement::ird, |ird| {
+                    ird.array_status_ptr = value as *mut USmallInt
+                }) {
+                    Ok(_) => SqlReturn::SUCCESS,
+                    Err(error) => {
+                        stmt_handle.add_diag_info(error);
+                        SqlReturn::ERROR
+                    }
+                }
             }
             StatementAttribute::RowsFetchedPtr => {
-                let mut stmt_contents = stmt.write().unwrap();
-                stmt_contents.attributes.rows_fetched_ptr = value as *mut ULen;
-                SqlReturn::SUCCESS
+                match set_stmt_desc_field(stmt, Statement::ird, |ird| {
+                    ird.rows_processed_ptr = value as *mut ULen
+                }) {
+                    Ok(_) => SqlReturn::SUCCESS,
+                    Err(error) => {
+                        stmt_handle.add_diag_info(error);
+                        SqlReturn::ERROR
+                    }
+                }
             }
             StatementAttribute::RowArraySize => match FromPrimitive::from_i32(value as i32) {
                 Some(ras) => {
-                    let mut stmt_contents = stmt.write().unwrap();
-                    stmt_contents.attributes.row_array_size = ras;
-                    SqlReturn::SUCCESS
+                    match set_stmt_desc_field(stmt, Statement::ard, |ard| ard.array_size = ras) {
+                        Ok(_) => SqlReturn::SUCCESS,
+                        Err(error) => {
+                            stmt_handle.add_diag_info(error);
+                            SqlReturn::ERROR
+                        }
+                    }
                 }
                 None => {
                     stmt_handle
@@ -1529,7 +4416,7 @@ pub extern "C" fn SQLSetStmtAttrW(
             }
             StatementAttribute::UseBookmarks => match FromPrimitive::from_i32(value as i32) {
                 Some(ub) => {
-                    let mut stmt_contents = stmt.write().unwrap();
+                    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
                     stmt_contents.attributes.use_bookmarks = ub;
                     SqlReturn::SUCCESS
                 }
@@ -1547,51 +4434,267 @@ pub extern "C" fn SQLSetStmtAttrW(
     }
 }
 
+/// set_stmt_desc_field applies [`set`] to the descriptor of the statement returned by [`desc`],
+/// for the statement attributes which are fields of a descriptor.
+fn set_stmt_desc_field(
+    stmt: &RwLock<Statement>,
+    desc: fn(&Statement) -> Result<&RwLock<Descriptor>, ODBCError>,
+    set: impl FnOnce(&mut Descriptor),
+) -> Result<(), ODBCError> {
+    let stmt_contents = stmt.read().unwrap_or_else(PoisonError::into_inner);
+    set(&mut desc(&stmt_contents)?
+        .write()
+        .unwrap_or_else(PoisonError::into_inner));
+    Ok(())
+}
+
+/// stmt_attr_function returns how the state of the statement restricts setting [`attr`]: the
+/// attributes describing the cursor can't change once the statement is prepared or executed.
+fn stmt_attr_function(attr: StatementAttribute) -> StatementFunction {
+    match attr {
+        StatementAttribute::Concurrency => StatementFunction::SetCursorAttr("SQL_ATTR_CONCURRENCY"),
+        StatementAttribute::CursorType => StatementFunction::SetCursorAttr("SQL_ATTR_CURSOR_TYPE"),
+        StatementAttribute::SimulateCursor => {
+            StatementFunction::SetCursorAttr("SQL_ATTR_SIMULATE_CURSOR")
+        }
+        StatementAttribute::UseBookmarks => {
+            StatementFunction::SetCursorAttr("SQL_ATTR_USE_BOOKMARKS")
+        }
+        _ => StatementFunction::Other("SQLSetStmtAttr"),
+    }
+}
+
+/// sql_set_app_desc attaches the explicitly allocated descriptor [`desc_handle`] to the statement
+/// as its application row or parameter descriptor, according to [`attr`]. A null handle, or the
+/// handle of the statement's own implicit descriptor, reattaches the implicit descriptor.
+fn sql_set_app_desc(
+    stmt: &RwLock<Statement>,
+    attr: StatementAttribute,
+    desc_handle: *mut MongoHandle,
+) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    let (implicit_desc, attr_name) = if attr == StatementAttribute::AppRowDesc {
+        (stmt_contents.implicit_app_row_desc, "SQL_ATTR_APP_ROW_DESC")
+    } else {
+        (
+            stmt_contents.implicit_app_param_desc,
+            "SQL_ATTR_APP_PARAM_DESC",
+        )
+    };
+    let desc_handle = if desc_handle.is_null() || desc_handle == implicit_desc {
+        implicit_desc
+    } else {
+        let desc = MongoHandle::from_live(desc_handle as Handle, HandleType::Desc)
+            .and_then(|desc| desc.as_descriptor())
+            .ok_or(ODBCError::InvalidAttrValue(attr_name))?;
+        let desc_contents = desc.read().unwrap_or_else(PoisonError::into_inner);
+        // The descriptors implicitly allocated for other statements can't be shared.
+        if desc_contents.alloc_type == AllocType::Auto {
+            return Err(ODBCError::InvalidUseOfAutomaticDescriptor);
+        }
+        if desc_contents.connection != stmt_contents.connection {
+            return Err(ODBCError::InvalidAttrValue(attr_name));
+        }
+        desc_handle
+    };
+    if attr == StatementAttribute::AppRowDesc {
+        stmt_contents.attributes.app_row_desc = desc_handle;
+    } else {
+        stmt_contents.attributes.app_param_desc = desc_handle;
+    }
+    Ok(())
+}
+
 #[no_mangle]
 pub extern "C" fn SQLSpecialColumns(
     statement_handle: HStmt,
-    _identifier_type: SmallInt,
-    _catalog_name: *const Char,
-    _catalog_name_length: SmallInt,
+    identifier_type: SmallInt,
+    catalog_name: *const Char,
+    catalog_name_length: SmallInt,
     _schema_name: *const Char,
     _schema_name_length: SmallInt,
-    _table_name: *const Char,
-    _table_name_length: SmallInt,
-    _scope: SmallInt,
+    table_name: *const Char,
+    table_name_length: SmallInt,
+    scope: SmallInt,
     _nullable: Nullability,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLSpecialColumns")
+    panic_safe_exec(
+        "SQLSpecialColumns",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, charset| {
+                let argument = |text, text_length| input_text_argument(text, text_length, charset);
+                sql_special_columns(
+                    stmt,
+                    identifier_type,
+                    argument(catalog_name, catalog_name_length),
+                    argument(table_name, table_name_length),
+                    scope,
+                )
+            })
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLSpecialColumnsW(
-    _statement_handle: HStmt,
-    _identifier_type: SmallInt,
-    _catalog_name: *const WChar,
-    _catalog_name_length: SmallInt,
+    statement_handle: HStmt,
+    identifier_type: SmallInt,
+    catalog_name: *const WChar,
+    catalog_name_length: SmallInt,
     _schema_name: *const WChar,
     _schema_name_length: SmallInt,
-    _table_name: *const WChar,
-    _table_name_length: SmallInt,
-    _scope: SmallInt,
+    table_name: *const WChar,
+    table_name_length: SmallInt,
+    scope: SmallInt,
     _nullable: Nullability,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLSpecialColumnsW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, _| {
+                sql_special_columns(
+                    stmt,
+                    identifier_type,
+                    input_wtext_argument(catalog_name, catalog_name_length),
+                    input_wtext_argument(table_name, table_name_length),
+                    scope,
+                )
+            })
+        },
+    )
+}
+
+/// sql_special_columns stores on the statement the SQLSpecialColumns result set for the
+/// collection [`table_name`] of the database [`catalog_name`]. Neither argument is a search
+/// pattern. A [`None`] catalog name was passed as a null pointer and matches all the databases,
+/// while the table name is required. SQL_BEST_ROWID returns the _id field, which identifies a
+/// document for the whole session and so satisfies any [`scope`]. SQL_ROWVER returns no column
+/// since no field is updated automatically. The nullable argument is ignored since _id is never
+/// null.
+fn sql_special_columns(
+    stmt: &RwLock<Statement>,
+    identifier_type: SmallInt,
+    catalog_name: Option<String>,
+    table_name: Option<String>,
+    scope: SmallInt,
+) -> Result<(), ODBCError> {
+    let identifier_type: IdentifierType = FromPrimitive::from_i16(identifier_type)
+        .ok_or(ODBCError::ColumnTypeOutOfRange(identifier_type))?;
+    let _: RowIdScope = FromPrimitive::from_i16(scope).ok_or(ODBCError::ScopeOutOfRange(scope))?;
+    let table_name = table_name.ok_or(ODBCError::InvalidUseOfNullPointer("TableName"))?;
+    let db_name_filter = catalog_name.map_or(SQL_ALL.to_string(), |catalog_name| {
+        escape_pattern(&catalog_name)
+    });
+    exec_catalog_function(
+        stmt,
+        "SQLSpecialColumns",
+        |mongo_connection, query_timeout| match identifier_type {
+            IdentifierType::BestRowid => MongoSpecialColumns::list_best_rowid(
+                mongo_connection,
+                query_timeout,
+                &db_name_filter,
+                &escape_pattern(&table_name),
+            ),
+            IdentifierType::Rowver => Ok(MongoSpecialColumns::empty()),
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLStatistics(
-    _statement_handle: HStmt,
-    _catalog_name: *const Char,
-    _catalog_name_length: SmallInt,
+    statement_handle: HStmt,
+    catalog_name: *const Char,
+    catalog_name_length: SmallInt,
     _schema_name: *const Char,
     _schema_name_length: SmallInt,
-    _table_name: *const Char,
-    _table_name_length: SmallInt,
-    _unique: SmallInt,
-    _reserved: SmallInt,
+    table_name: *const Char,
+    table_name_length: SmallInt,
+    unique: SmallInt,
+    reserved: SmallInt,
+) -> SqlReturn {
+    panic_safe_exec(
+        "SQLStatistics",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, charset| {
+                let argument = |text, text_length| input_text_argument(text, text_length, charset);
+                sql_statistics(
+                    stmt,
+                    argument(catalog_name, catalog_name_length),
+                    argument(table_name, table_name_length),
+                    unique,
+                    reserved,
+                )
+            })
+        },
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn SQLStatisticsW(
+    statement_handle: HStmt,
+    catalog_name: *const WChar,
+    catalog_name_length: SmallInt,
+    _schema_name: *const WChar,
+    _schema_name_length: SmallInt,
+    table_name: *const WChar,
+    table_name_length: SmallInt,
+    unique: SmallInt,
+    reserved: SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLStatisticsW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, _| {
+                sql_statistics(
+                    stmt,
+                    input_wtext_argument(catalog_name, catalog_name_length),
+                    input_wtext_argument(table_name, table_name_length),
+                    unique,
+                    reserved,
+                )
+            })
+        },
+    )
+}
+
+/// sql_statistics stores on the statement the SQLStatistics result set listing the index keys of
+/// the collection [`table_name`] of the database [`catalog_name`]. Neither argument is a search
+/// pattern. A [`None`] catalog name was passed as a null pointer and matches all the databases,
+/// while the table name is required. [`unique`] restricts the result to the unique indexes, and
+/// SQL_ENSURE as [`reserved`] adds the SQL_TABLE_STAT row holding the number of documents.
+fn sql_statistics(
+    stmt: &RwLock<Statement>,
+    catalog_name: Option<String>,
+    table_name: Option<String>,
+    unique: SmallInt,
+    reserved: SmallInt,
+) -> Result<(), ODBCError> {
+    let unique: IndexUniqueness =
+        FromPrimitive::from_i16(unique).ok_or(ODBCError::UniquenessOptionOutOfRange(unique))?;
+    let accuracy: StatisticsAccuracy =
+        FromPrimitive::from_i16(reserved).ok_or(ODBCError::AccuracyOptionOutOfRange(reserved))?;
+    let table_name = table_name.ok_or(ODBCError::InvalidUseOfNullPointer("TableName"))?;
+    let db_name_filter = catalog_name.map_or(SQL_ALL.to_string(), |catalog_name| {
+        escape_pattern(&catalog_name)
+    });
+    exec_catalog_function(stmt, "SQLStatistics", |mongo_connection, query_timeout| {
+        MongoStatistics::list_statistics(
+            mongo_connection,
+            query_timeout,
+            &db_name_filter,
+            &escape_pattern(&table_name),
+            unique == IndexUniqueness::Unique,
+            accuracy == StatisticsAccuracy::Ensure,
+        )
+    })
 }
 
 #[no_mangle]
@@ -1604,12 +4707,17 @@ pub extern "C" fn SQLTablePrivileges(
     _table_name: *const Char,
     _name_length_3: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLTablePrivileges")
+    panic_safe_exec(
+        "SQLTablePrivileges",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || unsupported_function(MongoHandleRef::from(statement_handle), "SQLTablePrivileges"),
+    )
 }
 
 #[no_mangle]
-pub extern "C" fn SQLTablesPrivilegesW(
-    _statement_handle: HStmt,
+pub extern "C" fn SQLTablePrivilegesW(
+    statement_handle: HStmt,
     _catalog_name: *const WChar,
     _name_length_1: SmallInt,
     _schema_name: *const WChar,
@@ -1617,46 +4725,299 @@ pub extern "C" fn SQLTablesPrivilegesW(
     _table_name: *const WChar,
     _name_length_3: SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLTablePrivilegesW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            unsupported_function(
+                MongoHandleRef::from(statement_handle),
+                "SQLTablePrivilegesW",
+            )
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLTables(
     statement_handle: HStmt,
-    _catalog_name: *const Char,
-    _name_length_1: SmallInt,
-    _schema_name: *const Char,
-    _name_length_2: SmallInt,
-    _table_name: *const Char,
-    _name_length_3: SmallInt,
-    _table_type: *const Char,
-    _name_length_4: SmallInt,
+    catalog_name: *const Char,
+    name_length_1: SmallInt,
+    schema_name: *const Char,
+    name_length_2: SmallInt,
+    table_name: *const Char,
+    name_length_3: SmallInt,
+    table_type: *const Char,
+    name_length_4: SmallInt,
 ) -> SqlReturn {
-    unsupported_function(MongoHandleRef::from(statement_handle), "SQLTables")
+    panic_safe_exec(
+        "SQLTables",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, charset| {
+                let argument = |text, text_length| input_text_argument(text, text_length, charset);
+                sql_tables(
+                    stmt,
+                    argument(catalog_name, name_length_1),
+                    argument(schema_name, name_length_2),
+                    argument(table_name, name_length_3),
+                    argument(table_type, name_length_4),
+                )
+            })
+        },
+    )
 }
 
 #[no_mangle]
 pub extern "C" fn SQLTablesW(
-    _statement_handle: HStmt,
-    _catalog_name: *const WChar,
-    _name_length_1: SmallInt,
-    _schema_name: *const WChar,
-    _name_length_2: SmallInt,
-    _table_name: *const WChar,
-    _name_length_3: SmallInt,
-    _table_type: *const WChar,
-    _name_length_4: SmallInt,
+    statement_handle: HStmt,
+    catalog_name: *const WChar,
+    name_length_1: SmallInt,
+    schema_name: *const WChar,
+    name_length_2: SmallInt,
+    table_name: *const WChar,
+    name_length_3: SmallInt,
+    table_type: *const WChar,
+    name_length_4: SmallInt,
 ) -> SqlReturn {
-    unimplemented!()
+    panic_safe_exec(
+        "SQLTablesW",
+        statement_handle as Handle,
+        HandleType::Stmt,
+        || {
+            exec_statement_function(statement_handle, |stmt, _| {
+                sql_tables(
+                    stmt,
+                    input_wtext_argument(catalog_name, name_length_1),
+                    input_wtext_argument(schema_name, name_length_2),
+                    input_wtext_argument(table_name, name_length_3),
+                    input_wtext_argument(table_type, name_length_4),
+                )
+            })
+        },
+    )
+}
+
+/// sql_tables stores on the statement the SQLTables result set for the given arguments. A
+/// [`None`] argument was passed as a null pointer.
+/// The special modes are:
+/// - SQL_ALL_CATALOGS (catalog "%", schema and table "") lists the databases.
+/// - SQL_ALL_SCHEMAS (schema "%", catalog and table "") lists no schemas, since MongoDB has none.
+/// - SQL_ALL_TABLE_TYPES (table type "%", catalog, schema and table "") lists TABLE and VIEW.
+///
+/// Otherwise, the catalog and table names are search patterns matched against the database and
+/// collection names, a null pattern matching all the names. The schema name is ignored, and the
+/// table type is a comma-separated list of the table types to list, optionally quoted.
+fn sql_tables(
+    stmt: &RwLock<Statement>,
+    catalog_name: Option<String>,
+    schema_name: Option<String>,
+    table_name: Option<String>,
+    table_type: Option<String>,
+) -> Result<(), ODBCError> {
+    let is_empty = |argument: &Option<String>| argument.as_deref() == Some("");
+    let is_all = |argument: &Option<String>| argument.as_deref() == Some(SQL_ALL);
+    if is_all(&schema_name) && is_empty(&catalog_name) && is_empty(&table_name) {
+        set_catalog_result_set(stmt, "SQLTables", MongoCollections::empty())
+    } else if is_all(&table_type)
+        && is_empty(&catalog_name)
+        && is_empty(&schema_name)
+        && is_empty(&table_name)
+    {
+        set_catalog_result_set(stmt, "SQLTables", MongoTableTypes::list_table_types())
+    } else if is_all(&catalog_name) && is_empty(&schema_name) && is_empty(&table_name) {
+        exec_catalog_function(stmt, "SQLTables", MongoDatabases::list_all_catalogs)
+    } else {
+        let table_types = table_types(table_type.as_deref().unwrap_or_default());
+        exec_catalog_function(stmt, "SQLTables", |mongo_connection, query_timeout| {
+            MongoCollections::list_tables(
+                mongo_connection,
+                query_timeout,
+                catalog_name.as_deref().unwrap_or(SQL_ALL),
+                table_name.as_deref().unwrap_or(SQL_ALL),
+                &table_types.iter().map(String::as_str).collect::<Vec<_>>(),
+            )
+        })
+    }
+}
+
+/// table_types parses the SQLTables [`table_type`] argument, a comma-separated list of table
+/// types which can be enclosed in single quotes. Returns an empty list if all the table types are
+/// requested.
+fn table_types(table_type: &str) -> Vec<String> {
+    let table_types: Vec<String> = table_type
+        .split(',')
+        .map(|table_type| table_type.trim().trim_matches('\'').trim().to_uppercase())
+        .filter(|table_type| !table_type.is_empty())
+        .collect();
+    if table_types.iter().any(|table_type| table_type == SQL_ALL) {
+        return vec![];
+    }
+    table_types
+}
+
+/// exec_statement_function runs the body [`function`] of an exported statement function on the
+/// statement [`statement_handle`], with the charset of its narrow strings, once its diagnostics
+/// have been cleared. The error of [`function`] is posted as a diagnostic.
+fn exec_statement_function(
+    statement_handle: HStmt,
+    function: impl FnOnce(&RwLock<Statement>, Charset) -> Result<(), ODBCError>,
+) -> SqlReturn {
+    let stmt_handle = MongoHandleRef::from(statement_handle);
+    stmt_handle.clear_diagnostics();
+    let charset = stmt_handle.charset();
+    let stmt = match stmt_handle.as_statement() {
+        None => return SqlReturn::INVALID_HANDLE,
+        Some(stmt) => stmt,
+    };
+    match function(stmt, charset) {
+        Ok(_) => SqlReturn::SUCCESS,
+        Err(error) => {
+            stmt_handle.add_diag_info(error);
+            SqlReturn::ERROR
+        }
+    }
 }
 
-mod util {
-    use crate::{api::errors::ODBCError, handles::definitions::MongoHandle};
-    use odbc_sys::{Integer, SmallInt, SqlReturn, WChar};
-    use std::{cmp::min, ptr::copy_nonoverlapping};
+/// exec_catalog_function runs the catalog function [`function_name`] on the statement [`stmt`]:
+/// [`list`] is called with the MongoDB connection of the statement and its query timeout in
+/// seconds, and the statement it returns becomes the result set. The previous result set is only
+/// discarded once the function is known to be able to run on the statement.
+fn exec_catalog_function<S: MongoStatement + 'static>(
+    stmt: &RwLock<Statement>,
+    function_name: &'static str,
+    list: impl FnOnce(&MongoConnection, Option<i32>) -> mongo_odbc_core::Result<S>,
+) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::ExecDirect(function_name))?;
+    let conn = unsafe { stmt_contents.connection.as_ref() }
+        .and_then(MongoHandle::as_connection)
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    let conn_contents = conn.read().unwrap_or_else(PoisonError::into_inner);
+    let mongo_connection = conn_contents
+        .mongo_connection
+        .as_ref()
+        .ok_or(ODBCError::ConnectionNotOpen)?;
+    let query_timeout = stmt_contents.query_timeout();
+    stmt_contents.discard_result_set();
+    let mongo_statement = list(mongo_connection, query_timeout).map_err(ODBCError::Core)?;
+    stmt_contents.mongo_statement = Some(Box::new(mongo_statement));
+    stmt_contents.state = StatementState::ExecutedHasResultSet;
+    Ok(())
+}
+
+/// set_catalog_result_set makes [`mongo_statement`] the result set of the catalog function
+/// [`function_name`] on the statement [`stmt`], for the results which don't depend on the
+/// server.
+fn set_catalog_result_set<S: MongoStatement + 'static>(
+    stmt: &RwLock<Statement>,
+    function_name: &'static str,
+    mongo_statement: S,
+) -> Result<(), ODBCError> {
+    let mut stmt_contents = stmt.write().unwrap_or_else(PoisonError::into_inner);
+    stmt_contents.check_state(StatementFunction::ExecDirect(function_name))?;
+    stmt_contents.discard_result_set();
+    stmt_contents.mongo_statement = Some(Box::new(mongo_statement));
+    stmt_contents.state = StatementState::ExecutedHasResultSet;
+    Ok(())
+}
+
+pub(crate) mod util {
+    use crate::{
+        api::{encoding::Charset, errors::ODBCError},
+        handles::definitions::MongoHandle,
+    };
+    use odbc_sys::{Char, Handle, HandleType, Integer, SmallInt, SqlReturn, WChar, NTS};
+    use std::{
+        cell::Cell,
+        cmp::min,
+        panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe},
+        ptr::copy_nonoverlapping,
+        sync::Once,
+    };
+
+    // The functions which return the diagnostics of the previous function called on a handle.
+    const DIAGNOSTIC_FUNCTIONS: [&str; 4] = [
+        "SQLGetDiagField",
+        "SQLGetDiagFieldW",
+        "SQLGetDiagRec",
+        "SQLGetDiagRecW",
+    ];
+
+    thread_local! {
+        // Whether the thread is running the body of an exported function, whose panics are
+        // reported in a diagnostic instead of being printed.
+        static IN_EXPORTED_FUNCTION: Cell<bool> = const { Cell::new(false) };
+    }
+
+    static QUIET_PANIC_HOOK: Once = Once::new();
+
+    /// install_quiet_panic_hook wraps the panic hook in place, which prints the panic to the
+    /// standard error of the application, so that it is skipped for the panics caught by
+    /// panic_safe_exec. The panics raised outside the driver still reach the previous hook.
+    fn install_quiet_panic_hook() {
+        QUIET_PANIC_HOOK.call_once(|| {
+            let previous_hook = take_hook();
+            set_hook(Box::new(move |info| {
+                if !IN_EXPORTED_FUNCTION.with(Cell::get) {
+                    previous_hook(info)
+                }
+            }));
+        });
+    }
+
+    /// panic_safe_exec runs the body [`function`] of the exported function [`name`] if [`handle`]
+    /// is a live handle of the type [`handle_type`], and returns SQL_INVALID_HANDLE otherwise. A
+    /// panic never unwinds into the calling application, it is reported as SQL_ERROR with an
+    /// HY000 diagnostic on [`handle`], without being printed. The return code is recorded on
+    /// [`handle`] for SQL_DIAG_RETURNCODE.
+    pub fn panic_safe_exec(
+        name: &'static str,
+        handle: Handle,
+        handle_type: HandleType,
+        function: impl FnOnce() -> SqlReturn,
+    ) -> SqlReturn {
+        let mongo_handle = match MongoHandle::from_live(handle, handle_type) {
+            None => return SqlReturn::INVALID_HANDLE,
+            Some(mongo_handle) => mongo_handle,
+        };
+        install_quiet_panic_hook();
+        let in_exported_function = IN_EXPORTED_FUNCTION.with(|flag| flag.replace(true));
+        let result = catch_unwind(AssertUnwindSafe(function));
+        IN_EXPORTED_FUNCTION.with(|flag| flag.set(in_exported_function));
+        let sql_return = match result {
+            Ok(sql_return) => sql_return,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                mongo_handle.add_diag_info(ODBCError::Panic(name, message));
+                SqlReturn::ERROR
+            }
+        };
+        // The diagnostic functions describe the previous call and leave its return code in
+        // place, and the handle may have been freed by the function.
+        if !DIAGNOSTIC_FUNCTIONS.contains(&name) {
+            if let Some(mongo_handle) = MongoHandle::from_live(handle, handle_type) {
+                mongo_handle.set_return_code(sql_return);
+            }
+        }
+        sql_return
+    }
+
+    /// set_sql_state writes the given sql state to the [`output_ptr`] as a narrow string.
+    pub fn set_sql_state(sql_state: &str, output_ptr: *mut Char) {
+        let sql_state = &format!("{}\0", sql_state);
+        unsafe {
+            copy_nonoverlapping(sql_state.as_ptr(), output_ptr, 6);
+        }
+    }
 
-    /// set_sql_state writes the given sql state to the [`output_ptr`].
-    pub fn set_sql_state(sql_state: &str, output_ptr: *mut WChar) {
+    /// set_sql_wstate writes the given sql state to the [`output_ptr`].
+    pub fn set_sql_wstate(sql_state: &str, output_ptr: *mut WChar) {
         let sql_state = &format!("{}\0", sql_state);
         let state_u16 = sql_state.encode_utf16().collect::<Vec<u16>>();
         unsafe {
@@ -1664,41 +5025,156 @@ mod util {
         }
     }
 
-    /// set_error_message writes [`error_message`] to the [`output_ptr`]. [`buffer_len`] is the
-    /// length of the [`output_ptr`] buffer in characters; the error message should be truncated
-    /// if it is longer than the buffer length. The number of characters written to [`output_ptr`]
+    /// input_text_to_string converts the narrow input string [`text`] of [`text_length`] bytes,
+    /// encoded with [`charset`], into a String. A length of SQL_NTS means the string is null
+    /// terminated.
+    pub fn input_text_to_string(text: *const Char, text_length: isize, charset: Charset) -> String {
+        if text.is_null() {
+            return String::new();
+        }
+        let text_length = if text_length == NTS {
+            (0..)
+                .take_while(|i| unsafe { *text.offset(*i) } != 0)
+                .count()
+        } else {
+            text_length.max(0) as usize
+        };
+        let bytes = unsafe { std::slice::from_raw_parts(text, text_length) };
+        charset.decode(bytes)
+    }
+
+    /// input_wtext_to_string converts the wide input string [`text`] of [`text_length`]
+    /// characters into a String. A length of SQL_NTS means the string is null terminated.
+    pub fn input_wtext_to_string(text: *const WChar, text_length: isize) -> String {
+        if text.is_null() {
+            return String::new();
+        }
+        let text_length = if text_length == NTS {
+            (0..)
+                .take_while(|i| unsafe { *text.offset(*i) } != 0)
+                .count()
+        } else {
+            text_length.max(0) as usize
+        };
+        let chars = unsafe { std::slice::from_raw_parts(text, text_length) };
+        String::from_utf16_lossy(chars)
+    }
+
+    /// input_text_argument converts the narrow string argument [`text`] of a catalog function
+    /// like input_text_to_string, or returns None for a null pointer, which doesn't restrict the
+    /// result.
+    pub fn input_text_argument(
+        text: *const Char,
+        text_length: SmallInt,
+        charset: Charset,
+    ) -> Option<String> {
+        (!text.is_null()).then(|| input_text_to_string(text, text_length as isize, charset))
+    }
+
+    /// input_wtext_argument converts the wide string argument [`text`] of a catalog function
+    /// like input_wtext_to_string, or returns None for a null pointer, which doesn't restrict the
+    /// result.
+    pub fn input_wtext_argument(text: *const WChar, text_length: SmallInt) -> Option<String> {
+        (!text.is_null()).then(|| input_wtext_to_string(text, text_length as isize))
+    }
+
+    /// set_output_wstring writes [`message`] to the [`output_ptr`]. [`buffer_len`] is the
+    /// length of the [`output_ptr`] buffer in characters; the message should be truncated
+    /// if it is longer than the buffer length. The number of characters in the complete message
     /// should be stored in [`text_length_ptr`].
-    pub fn set_error_message(
-        error_message: String,
+    pub fn set_output_wstring(
+        message: &str,
         output_ptr: *mut WChar,
         buffer_len: usize,
         text_length_ptr: *mut SmallInt,
     ) -> SqlReturn {
-        unsafe {
-            // Check if the entire error message plus a null terminator can fit in the buffer;
-            // we should truncate the error message if it's too long.
-            let mut message_u16 = error_message.encode_utf16().collect::<Vec<u16>>();
-            let message_len = message_u16.len();
-            let num_chars = min(message_len + 1, buffer_len);
-            message_u16.resize(num_chars - 1, 0);
-            message_u16.push('\u{0}' as u16);
-            copy_nonoverlapping(message_u16.as_ptr(), output_ptr, num_chars);
-            // Store the number of characters in the error message string, excluding the
-            // null terminator, in text_length_ptr
-            if !text_length_ptr.is_null() {
-                *text_length_ptr = (num_chars - 1) as SmallInt;
-            }
-            if num_chars < message_len {
-                SqlReturn::SUCCESS_WITH_INFO
-            } else {
+        let message_u16 = message.encode_utf16().collect::<Vec<u16>>();
+        write_output(&message_u16, output_ptr, buffer_len, text_length_ptr)
+    }
+
+    /// set_output_string writes [`message`] to the [`output_ptr`] as a narrow string encoded
+    /// with [`charset`]. [`buffer_len`] is the length of the [`output_ptr`] buffer in bytes; the
+    /// message should be truncated if it is longer than the buffer length, without splitting a
+    /// multibyte character. The number of bytes in the complete message should be stored in
+    /// [`text_length_ptr`].
+    pub fn set_output_string(
+        message: &str,
+        output_ptr: *mut Char,
+        buffer_len: usize,
+        text_length_ptr: *mut SmallInt,
+        charset: Charset,
+    ) -> SqlReturn {
+        let bytes = charset.encode(message);
+        let buffer_len = match buffer_len {
+            0 => 0,
+            _ => charset.truncation_length(&bytes, buffer_len - 1) + 1,
+        };
+        write_output(&bytes, output_ptr, buffer_len, text_length_ptr)
+    }
+
+    // write_output copies as many [`chars`] as possible followed by a null terminator into a
+    // buffer of [`buffer_len`] characters.
+    fn write_output<T: Copy + Default>(
+        chars: &[T],
+        output_ptr: *mut T,
+        buffer_len: usize,
+        text_length_ptr: *mut SmallInt,
+    ) -> SqlReturn {
+        // Store the number of characters in the string, excluding the
+        // null terminator, in text_length_ptr
+        if !text_length_ptr.is_null() {
+            unsafe { *text_length_ptr = chars.len() as SmallInt };
+        }
+        // Without an output buffer only the length is reported, which is not a truncation.
+        if output_ptr.is_null() {
+            return SqlReturn::SUCCESS;
+        }
+        if buffer_len == 0 {
+            return if chars.is_empty() {
                 SqlReturn::SUCCESS
-            }
+            } else {
+                SqlReturn::SUCCESS_WITH_INFO
+            };
+        }
+        // Check if the entire string plus a null terminator can fit in the buffer;
+        // we should truncate the string if it's too long.
+        let num_chars = min(chars.len(), buffer_len - 1);
+        unsafe {
+            copy_nonoverlapping(chars.as_ptr(), output_ptr, num_chars);
+            *output_ptr.add(num_chars) = T::default();
+        }
+        if num_chars < chars.len() {
+            SqlReturn::SUCCESS_WITH_INFO
+        } else {
+            SqlReturn::SUCCESS
         }
     }
 
     /// get_diag_rec copies the given ODBC error's diagnostic information
-    /// into the provided pointers.
+    /// into the provided pointers, as narrow strings encoded with [`charset`].
     pub fn get_diag_rec(
+        error: &ODBCError,
+        state: *mut Char,
+        message_text: *mut Char,
+        buffer_length: SmallInt,
+        text_length_ptr: *mut SmallInt,
+        native_error_ptr: *mut Integer,
+        charset: Charset,
+    ) -> SqlReturn {
+        unsafe { *native_error_ptr = error.get_native_err_code() };
+        set_sql_state(error.get_sql_state(), state);
+        set_output_string(
+            &error.get_error_message(),
+            message_text,
+            buffer_length as usize,
+            text_length_ptr,
+            charset,
+        )
+    }
+
+    /// get_diag_wrec copies the given ODBC error's diagnostic information
+    /// into the provided pointers.
+    pub fn get_diag_wrec(
         error: &ODBCError,
         state: *mut WChar,
         message_text: *mut WChar,
@@ -1707,9 +5183,9 @@ mod util {
         native_error_ptr: *mut Integer,
     ) -> SqlReturn {
         unsafe { *native_error_ptr = error.get_native_err_code() };
-        set_sql_state(error.get_sql_state(), state);
-        set_error_message(
-            error.get_error_message(),
+        set_sql_wstate(error.get_sql_state(), state);
+        set_output_wstring(
+            &error.get_error_message(),
             message_text,
             buffer_length as usize,
             text_length_ptr,
@@ -1722,6 +5198,21 @@ mod util {
         SqlReturn::ERROR
     }
 
+    /// set_output_value writes [`value`] to [`output_ptr`], unless it is null.
+    pub fn set_output_value<T>(output_ptr: *mut T, value: T) {
+        if !output_ptr.is_null() {
+            unsafe { *output_ptr = value }
+        }
+    }
+
+    /// set_output_array copies [`values`] to the array at [`output_ptr`], unless it is null. The
+    /// array must hold at least as many elements as [`values`].
+    pub fn set_output_array<T: Copy>(output_ptr: *mut T, values: &[T]) {
+        if !output_ptr.is_null() {
+            unsafe { copy_nonoverlapping(values.as_ptr(), output_ptr, values.len()) }
+        }
+    }
+
     /// set_str_length writes the given length to [`string_length_ptr`].
     pub fn set_str_length(string_length_ptr: *mut Integer, length: Integer) {
         if !string_length_ptr.is_null() {
@@ -0,0 +1,372 @@
+use crate::{
+    api::{
+        definitions::{DiagIdentifier, DynamicFunction},
+        errors::ODBCError,
+        test_fixtures::{
+            connection, free_handles, new_connection, new_env, new_statement, statement,
+        },
+    },
+    handles::definitions::*,
+    SQLBindCol, SQLFetch, SQLGetData, SQLGetDiagField, SQLGetDiagFieldW, SQLGetTypeInfo,
+    SQLMoreResults,
+};
+use odbc_sys::{
+    CDataType, Char, HandleType, Integer, Len, Pointer, SmallInt, SqlDataType, SqlReturn,
+};
+use std::{mem::size_of, ptr::null_mut};
+
+fn string_field(
+    handle_type: HandleType,
+    handle: *mut MongoHandle,
+    record_number: SmallInt,
+    diag_identifier: DiagIdentifier,
+) -> (SqlReturn, String) {
+    let mut buffer = [0u8; 100];
+    let mut string_length = 0;
+    let sql_return = SQLGetDiagField(
+        handle_type,
+        handle as *mut _,
+        record_number,
+        diag_identifier as SmallInt,
+        buffer.as_mut_ptr() as Pointer,
+        buffer.len() as SmallInt,
+        &mut string_length,
+    );
+    (
+        sql_return,
+        String::from_utf8(buffer[..string_length as usize].to_vec()).unwrap(),
+    )
+}
+
+fn numeric_field<T: Default>(
+    handle_type: HandleType,
+    handle: *mut MongoHandle,
+    record_number: SmallInt,
+    diag_identifier: DiagIdentifier,
+) -> (SqlReturn, T) {
+    let mut value = T::default();
+    let sql_return = SQLGetDiagFieldW(
+        handle_type,
+        handle as *mut _,
+        record_number,
+        diag_identifier as SmallInt,
+        &mut value as *mut T as Pointer,
+        0,
+        null_mut(),
+    );
+    (sql_return, value)
+}
+
+#[test]
+fn header_fields() {
+    let env_handle: *mut _ = new_env();
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_field::<Integer>(HandleType::Env, env_handle, 0, DiagIdentifier::Number)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, SqlReturn::SUCCESS.0),
+        numeric_field::<SmallInt>(HandleType::Env, env_handle, 0, DiagIdentifier::ReturnCode)
+    );
+
+    unsafe { (*env_handle).add_diag_info(ODBCError::OutStringTruncated(10)) };
+    unsafe { (*env_handle).add_diag_info(ODBCError::Unimplemented("SQLDrivers")) };
+    assert_eq!(
+        (SqlReturn::SUCCESS, 2),
+        numeric_field::<Integer>(HandleType::Env, env_handle, 0, DiagIdentifier::Number)
+    );
+
+    // The row counts and the dynamic function are only defined for statements.
+    assert_eq!(
+        SqlReturn::ERROR,
+        numeric_field::<Len>(HandleType::Env, env_handle, 0, DiagIdentifier::RowCount).0
+    );
+    assert_eq!(
+        SqlReturn::ERROR,
+        string_field(
+            HandleType::Env,
+            env_handle,
+            0,
+            DiagIdentifier::DynamicFunction
+        )
+        .0
+    );
+
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    // The row counts are not available.
+    assert_eq!(
+        (SqlReturn::SUCCESS, -1),
+        numeric_field::<Len>(
+            HandleType::Stmt,
+            stmt_handle,
+            0,
+            DiagIdentifier::CursorRowCount
+        )
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, String::new()),
+        string_field(
+            HandleType::Stmt,
+            stmt_handle,
+            0,
+            DiagIdentifier::DynamicFunction
+        )
+    );
+    statement(stmt_handle).write().unwrap().dynamic_function = DynamicFunction::SelectCursor;
+    assert_eq!(
+        (SqlReturn::SUCCESS, "SELECT CURSOR".to_string()),
+        string_field(
+            HandleType::Stmt,
+            stmt_handle,
+            0,
+            DiagIdentifier::DynamicFunction
+        )
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 85),
+        numeric_field::<Integer>(
+            HandleType::Stmt,
+            stmt_handle,
+            0,
+            DiagIdentifier::DynamicFunctionCode
+        )
+    );
+    // The diagnostics of the next function call replace those of the previous one.
+    unsafe { (*stmt_handle).clear_diagnostics() };
+    assert_eq!(
+        (SqlReturn::SUCCESS, String::new()),
+        string_field(
+            HandleType::Stmt,
+            stmt_handle,
+            0,
+            DiagIdentifier::DynamicFunction
+        )
+    );
+    free_handles(&[stmt_handle, env_handle]);
+}
+
+#[test]
+fn return_code() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let stmt = stmt_handle as *mut _;
+    let return_code =
+        || numeric_field::<SmallInt>(HandleType::Stmt, stmt_handle, 0, DiagIdentifier::ReturnCode);
+    assert_eq!((SqlReturn::SUCCESS, SqlReturn::SUCCESS.0), return_code());
+
+    // The return code is the one of the last function called, with or without diagnostics.
+    assert_eq!(SqlReturn::ERROR, SQLFetch(stmt));
+    assert_eq!((SqlReturn::SUCCESS, SqlReturn::ERROR.0), return_code());
+    // Reading the diagnostics doesn't change it.
+    assert_eq!((SqlReturn::SUCCESS, SqlReturn::ERROR.0), return_code());
+    assert_eq!(SqlReturn::NO_DATA, SQLMoreResults(stmt));
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_field::<Integer>(HandleType::Stmt, stmt_handle, 0, DiagIdentifier::Number)
+    );
+    assert_eq!((SqlReturn::SUCCESS, SqlReturn::NO_DATA.0), return_code());
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt, SqlDataType::UNKNOWN_TYPE)
+    );
+    assert_eq!((SqlReturn::SUCCESS, SqlReturn::SUCCESS.0), return_code());
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn record_fields() {
+    let env_handle: *mut _ = new_env();
+    let conn_handle: *mut _ = new_connection(env_handle, ConnectionState::Allocated);
+    connection(conn_handle).write().unwrap().data_source.name = "mongo".to_string();
+    unsafe {
+        (*conn_handle).add_diag_info(ODBCError::DsnNotFound("mongo".to_string()));
+        (*conn_handle).add_diag_info(ODBCError::ColumnTypeOutOfRange(3));
+        (*conn_handle).add_diag_info(ODBCError::Unimplemented("SQLDrivers"));
+    }
+
+    let field = |record_number, diag_identifier| {
+        string_field(HandleType::Dbc, conn_handle, record_number, diag_identifier)
+    };
+    assert_eq!(
+        (SqlReturn::SUCCESS, "IM002".to_string()),
+        field(1, DiagIdentifier::SqlState)
+    );
+    assert_eq!(
+        (
+            SqlReturn::SUCCESS,
+            "[MongoDB][API] Data source name \"mongo\" not found".to_string()
+        ),
+        field(1, DiagIdentifier::MessageText)
+    );
+    assert_eq!(
+        vec!["ODBC 3.0", "ISO 9075", "ISO 9075"],
+        (1..=3)
+            .map(|record_number| field(record_number, DiagIdentifier::ClassOrigin).1)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["ODBC 3.0", "ODBC 3.0", "ISO 9075"],
+        (1..=3)
+            .map(|record_number| field(record_number, DiagIdentifier::SubclassOrigin).1)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, "mongo".to_string()),
+        field(2, DiagIdentifier::ConnectionName)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, "mongo".to_string()),
+        field(2, DiagIdentifier::ServerName)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 0),
+        numeric_field::<Integer>(HandleType::Dbc, conn_handle, 3, DiagIdentifier::Native)
+    );
+    // The diagnostics of a connection aren't associated with rows or columns.
+    assert_eq!(
+        (SqlReturn::SUCCESS, -1),
+        numeric_field::<Len>(HandleType::Dbc, conn_handle, 3, DiagIdentifier::RowNumber)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, -1),
+        numeric_field::<Integer>(
+            HandleType::Dbc,
+            conn_handle,
+            3,
+            DiagIdentifier::ColumnNumber
+        )
+    );
+
+    assert_eq!(SqlReturn::NO_DATA, field(4, DiagIdentifier::SqlState).0);
+    assert_eq!(SqlReturn::ERROR, field(0, DiagIdentifier::SqlState).0);
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLGetDiagField(
+            HandleType::Dbc,
+            conn_handle as *mut _,
+            1,
+            1000,
+            null_mut(),
+            0,
+            null_mut(),
+        )
+    );
+    // The handle type must match the handle.
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        string_field(HandleType::Env, conn_handle, 1, DiagIdentifier::SqlState).0
+    );
+    free_handles(&[conn_handle, env_handle]);
+}
+
+#[test]
+fn truncated_string_fields() {
+    let env_handle: *mut _ = new_env();
+    unsafe { (*env_handle).add_diag_info(ODBCError::Unimplemented("SQLDrivers")) };
+
+    let mut sql_state = [0 as Char; 4];
+    let mut string_length = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS_WITH_INFO,
+        SQLGetDiagField(
+            HandleType::Env,
+            env_handle as *mut _,
+            1,
+            DiagIdentifier::SqlState as SmallInt,
+            sql_state.as_mut_ptr() as Pointer,
+            sql_state.len() as SmallInt,
+            &mut string_length,
+        )
+    );
+    assert_eq!(b"HYC\0", &sql_state);
+    assert_eq!(5, string_length);
+
+    // The lengths of the wide strings are in bytes.
+    let mut sql_state = [0u16; 4];
+    assert_eq!(
+        SqlReturn::SUCCESS_WITH_INFO,
+        SQLGetDiagFieldW(
+            HandleType::Env,
+            env_handle as *mut _,
+            1,
+            DiagIdentifier::SqlState as SmallInt,
+            sql_state.as_mut_ptr() as Pointer,
+            (sql_state.len() * size_of::<u16>()) as SmallInt,
+            &mut string_length,
+        )
+    );
+    assert_eq!("HYC\0", String::from_utf16(&sql_state).unwrap());
+    assert_eq!(10, string_length);
+    free_handles(&[env_handle]);
+}
+
+#[test]
+fn row_and_column_numbers() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let mut type_name = [0u8; 2];
+    let mut type_name_len: Len = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt_handle as *mut _, SqlDataType::VARCHAR)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLBindCol(
+            stmt_handle as *mut _,
+            1,
+            CDataType::Char,
+            type_name.as_mut_ptr() as Pointer,
+            type_name.len() as Len,
+            &mut type_name_len,
+        )
+    );
+
+    // The truncation of the bound TYPE_NAME is reported for the first row of the rowset.
+    assert_eq!(
+        SqlReturn::SUCCESS_WITH_INFO,
+        SQLFetch(stmt_handle as *mut _)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, "01004".to_string()),
+        string_field(HandleType::Stmt, stmt_handle, 1, DiagIdentifier::SqlState)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 1),
+        numeric_field::<Len>(HandleType::Stmt, stmt_handle, 1, DiagIdentifier::RowNumber)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 1),
+        numeric_field::<Integer>(
+            HandleType::Stmt,
+            stmt_handle,
+            1,
+            DiagIdentifier::ColumnNumber
+        )
+    );
+
+    // SQLGetData reports the column, but not the row.
+    assert_eq!(
+        SqlReturn::SUCCESS_WITH_INFO,
+        SQLGetData(
+            stmt_handle as *mut _,
+            1,
+            CDataType::Char,
+            type_name.as_mut_ptr() as Pointer,
+            type_name.len() as Len,
+            &mut type_name_len,
+        )
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, -2),
+        numeric_field::<Len>(HandleType::Stmt, stmt_handle, 1, DiagIdentifier::RowNumber)
+    );
+    assert_eq!(
+        (SqlReturn::SUCCESS, 1),
+        numeric_field::<Integer>(
+            HandleType::Stmt,
+            stmt_handle,
+            1,
+            DiagIdentifier::ColumnNumber
+        )
+    );
+    free_handles(&[stmt_handle]);
+}
@@ -1,6 +1,13 @@
-use crate::{api::errors::ODBCError, handles::definitions::*, SQLGetDiagRecW};
+use crate::{
+    api::{
+        errors::ODBCError,
+        test_fixtures::{free_handles, new_connection, new_env, new_statement},
+    },
+    handles::definitions::*,
+    SQLGetDiagRecW,
+};
 use odbc_sys::{HandleType, SqlReturn};
-use std::sync::RwLock;
+use std::ptr::null_mut;
 
 const UNIMPLEMENTED_FUNC: &str = "HYC00\0";
 
@@ -41,27 +48,20 @@ fn simple() {
         assert_eq!(0, *native_err_ptr);
     }
 
-    let env_handle: *mut _ =
-        &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+    let env_handle: *mut _ = new_env();
     validate_diag_rec(HandleType::Env, env_handle);
 
-    let conn_handle: *mut _ = &mut MongoHandle::Connection(RwLock::new(Connection::with_state(
-        env_handle,
-        ConnectionState::Allocated,
-    )));
+    let conn_handle: *mut _ = new_connection(env_handle, ConnectionState::Allocated);
     validate_diag_rec(HandleType::Dbc, conn_handle);
 
-    let stmt_handle: *mut _ = &mut MongoHandle::Statement(RwLock::new(Statement::with_state(
-        std::ptr::null_mut(),
-        StatementState::Allocated,
-    )));
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
     validate_diag_rec(HandleType::Stmt, stmt_handle);
+    free_handles(&[stmt_handle, conn_handle, env_handle]);
 }
 
 #[test]
 fn error_message() {
-    let env_handle: *mut _ =
-        &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+    let env_handle: *mut _ = new_env();
 
     // Initialize buffers
     let sql_state = &mut [0u16; 6] as *mut _;
@@ -109,12 +109,12 @@ fn error_message() {
         "[MongoDB][API] The feature SQLDriv✐𑜲 is not implemented\0",
         String::from_utf16(unsafe { &*(message_text as *const [u16; 57]) }).unwrap()
     );
+    free_handles(&[env_handle]);
 }
 
 #[test]
 fn invalid_ops() {
-    let env_handle: *mut _ =
-        &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+    let env_handle: *mut _ = new_env();
 
     // Initialize buffers
     let sql_state = &mut [0u16; 6] as *mut _;
@@ -165,4 +165,5 @@ fn invalid_ops() {
             text_length_ptr
         )
     );
+    free_handles(&[env_handle]);
 }
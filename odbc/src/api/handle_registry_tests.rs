@@ -0,0 +1,155 @@
+use crate::{
+    api::test_fixtures::{allocate, free_handles, open_connection, statement},
+    handles::definitions::*,
+    SQLAllocHandle, SQLCopyDesc, SQLDisconnect, SQLFreeHandle, SQLGetDescField, SQLGetEnvAttr,
+    SQLGetInfo, SQLNumResultCols,
+};
+use odbc_sys::{
+    Desc, EnvironmentAttribute, Handle, HandleType, Integer, Pointer, SmallInt, SqlReturn,
+};
+use std::{ptr::null_mut, sync::RwLock};
+
+fn odbc_version(env_handle: *mut MongoHandle) -> SqlReturn {
+    let mut version: Integer = 0;
+    SQLGetEnvAttr(
+        env_handle as *mut _,
+        EnvironmentAttribute::OdbcVersion,
+        &mut version as *mut Integer as Pointer,
+        0,
+        null_mut(),
+    )
+}
+
+#[test]
+fn live_handles() {
+    let env = allocate(HandleType::Env, null_mut());
+    let conn = open_connection(env);
+    let stmt = allocate(HandleType::Stmt, conn);
+    let desc = allocate(HandleType::Desc, conn);
+    assert_eq!(SqlReturn::SUCCESS, odbc_version(env));
+    for (handle, handle_type) in [
+        (env, HandleType::Env),
+        (conn, HandleType::Dbc),
+        (stmt, HandleType::Stmt),
+        (desc, HandleType::Desc),
+    ] {
+        assert!(MongoHandle::from_live(handle as Handle, handle_type).is_some());
+    }
+    // The implicitly allocated descriptors are live as long as their statement.
+    let implicit_descriptors = statement(stmt).read().unwrap().implicit_descriptors();
+    for implicit_desc in implicit_descriptors {
+        assert!(MongoHandle::from_live(implicit_desc as Handle, HandleType::Desc).is_some());
+    }
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Stmt, stmt as Handle)
+    );
+    for implicit_desc in implicit_descriptors {
+        assert!(MongoHandle::from_live(implicit_desc as Handle, HandleType::Desc).is_none());
+    }
+    free_handles(&[desc, conn, env]);
+}
+
+#[test]
+fn invalid_handles() {
+    let env = allocate(HandleType::Env, null_mut());
+    let conn = allocate(HandleType::Dbc, env);
+    // A null handle, and a handle the driver didn't allocate.
+    let unallocated: *mut _ =
+        &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+    assert_eq!(SqlReturn::INVALID_HANDLE, odbc_version(null_mut()));
+    assert_eq!(SqlReturn::INVALID_HANDLE, odbc_version(unallocated));
+    // A handle of another type.
+    assert_eq!(SqlReturn::INVALID_HANDLE, odbc_version(conn));
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLGetInfo(env as *mut _, 17, null_mut(), 0, null_mut())
+    );
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLFreeHandle(HandleType::Stmt, conn as Handle)
+    );
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLNumResultCols(conn as *mut _, &mut 0)
+    );
+    // A handle used as the input handle of a handle it can't allocate.
+    let mut handle: Handle = null_mut();
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLAllocHandle(HandleType::Stmt, env as Handle, &mut handle)
+    );
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLAllocHandle(HandleType::Dbc, unallocated as Handle, &mut handle)
+    );
+    free_handles(&[conn, env]);
+}
+
+#[test]
+fn freed_handles() {
+    let env = allocate(HandleType::Env, null_mut());
+    let conn = open_connection(env);
+    let desc = allocate(HandleType::Desc, conn);
+    let other_desc = allocate(HandleType::Desc, conn);
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Desc, desc as Handle)
+    );
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLFreeHandle(HandleType::Desc, desc as Handle)
+    );
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLCopyDesc(desc as *mut _, other_desc as *mut _)
+    );
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLCopyDesc(other_desc as *mut _, desc as *mut _)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Desc, other_desc as Handle)
+    );
+    assert_eq!(SqlReturn::SUCCESS, SQLDisconnect(conn as *mut _));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Dbc, conn as Handle)
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Env, env as Handle)
+    );
+    assert_eq!(SqlReturn::INVALID_HANDLE, odbc_version(env));
+}
+
+#[test]
+fn descriptor_of_freed_connection() {
+    let env = allocate(HandleType::Env, null_mut());
+    let conn = open_connection(env);
+    let desc = allocate(HandleType::Desc, conn);
+    assert_eq!(SqlReturn::SUCCESS, SQLDisconnect(conn as *mut _));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Dbc, conn as Handle)
+    );
+    // The descriptor was freed along with its connection.
+    let mut count: SmallInt = 0;
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLGetDescField(
+            desc as *mut _,
+            0,
+            Desc::Count as SmallInt,
+            &mut count as *mut SmallInt as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+    assert_eq!(
+        SqlReturn::INVALID_HANDLE,
+        SQLFreeHandle(HandleType::Desc, desc as Handle)
+    );
+    free_handles(&[env]);
+}
@@ -0,0 +1,521 @@
+use crate::api::definitions::InfoType;
+use mongo_odbc_core::SEARCH_PATTERN_ESCAPE;
+
+/// InfoValue is the value of an information type, returned by SQLGetInfo either as a character
+/// string or as a SQLUSMALLINT or SQLUINTEGER.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InfoValue {
+    String(String),
+    USmallInt(u16),
+    UInteger(u32),
+}
+
+// The name of the DBMS reported by SQL_DBMS_NAME.
+pub const DBMS_NAME: &str = "MongoDB";
+// The version of ODBC the driver supports.
+const DRIVER_ODBC_VER: &str = "03.80";
+// The file name of the driver library.
+#[cfg(target_os = "windows")]
+pub const DRIVER_NAME: &str = "mongoodbc.dll";
+#[cfg(target_os = "macos")]
+pub const DRIVER_NAME: &str = "libmongoodbc.dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const DRIVER_NAME: &str = "libmongoodbc.so";
+
+// MongoSQL quotes identifiers with backticks.
+const IDENTIFIER_QUOTE_CHAR: &str = "`";
+// MongoSQL only accepts letters, digits and underscores in unquoted identifiers, any other
+// character requires the identifier to be quoted.
+const SPECIAL_CHARACTERS: &str = "";
+// The MongoSQL keywords which are not ODBC keywords.
+const KEYWORDS: &str = "AGGREGATE,ARRAY,BINDATA,BOOL,BSON_DATE,BSON_TIMESTAMP,DBPOINTER,DOCUMENT,\
+ERROR,FLATTEN,JAVASCRIPT,JAVASCRIPTWITHSCOPE,LIMIT,LONG,MAXKEY,MINKEY,MISSING,OBJECTID,OFFSET,\
+REGEX,SAMPLE,SYMBOL,UNDEFINED,UNWIND";
+
+// The maximum length of a database name.
+const MAX_CATALOG_NAME_LEN: u16 = 64;
+// The maximum length of a collection namespace.
+const MAX_TABLE_NAME_LEN: u16 = 255;
+// The maximum number of fields in a compound index.
+const MAX_COLUMNS_IN_INDEX: u16 = 32;
+// The maximum size of a BSON document, which bounds both rows and commands.
+const MAX_BSON_SIZE: u32 = 16 * 1024 * 1024;
+
+// SQL_CONVERT_* bitmasks.
+const SQL_CVT_CHAR: u32 = 0x1;
+const SQL_CVT_NUMERIC: u32 = 0x2;
+const SQL_CVT_DECIMAL: u32 = 0x4;
+const SQL_CVT_INTEGER: u32 = 0x8;
+const SQL_CVT_FLOAT: u32 = 0x20;
+const SQL_CVT_REAL: u32 = 0x40;
+const SQL_CVT_DOUBLE: u32 = 0x80;
+const SQL_CVT_VARCHAR: u32 = 0x100;
+const SQL_CVT_LONGVARCHAR: u32 = 0x200;
+const SQL_CVT_BIT: u32 = 0x1000;
+const SQL_CVT_BIGINT: u32 = 0x4000;
+const SQL_CVT_TIMESTAMP: u32 = 0x20000;
+const SQL_CVT_WCHAR: u32 = 0x200000;
+const SQL_CVT_WLONGVARCHAR: u32 = 0x400000;
+const SQL_CVT_WVARCHAR: u32 = 0x800000;
+// The character types a value can be CAST to.
+const CVT_CHARACTER: u32 = SQL_CVT_CHAR
+    | SQL_CVT_VARCHAR
+    | SQL_CVT_LONGVARCHAR
+    | SQL_CVT_WCHAR
+    | SQL_CVT_WVARCHAR
+    | SQL_CVT_WLONGVARCHAR;
+// The numeric and boolean types a value can be CAST to.
+const CVT_NUMERIC: u32 = SQL_CVT_NUMERIC
+    | SQL_CVT_DECIMAL
+    | SQL_CVT_INTEGER
+    | SQL_CVT_BIGINT
+    | SQL_CVT_FLOAT
+    | SQL_CVT_REAL
+    | SQL_CVT_DOUBLE
+    | SQL_CVT_BIT;
+
+// SQL_CONVERT_FUNCTIONS
+const SQL_FN_CVT_CAST: u32 = 0x2;
+
+// SQL_NUMERIC_FUNCTIONS
+const SQL_FN_NUM_ABS: u32 = 0x1;
+const SQL_FN_NUM_CEILING: u32 = 0x20;
+const SQL_FN_NUM_COS: u32 = 0x40;
+const SQL_FN_NUM_FLOOR: u32 = 0x200;
+const SQL_FN_NUM_LOG: u32 = 0x400;
+const SQL_FN_NUM_MOD: u32 = 0x800;
+const SQL_FN_NUM_SIN: u32 = 0x2000;
+const SQL_FN_NUM_SQRT: u32 = 0x4000;
+const SQL_FN_NUM_TAN: u32 = 0x8000;
+const SQL_FN_NUM_DEGREES: u32 = 0x40000;
+const SQL_FN_NUM_LOG10: u32 = 0x80000;
+const SQL_FN_NUM_POWER: u32 = 0x100000;
+const SQL_FN_NUM_RADIANS: u32 = 0x200000;
+const SQL_FN_NUM_ROUND: u32 = 0x400000;
+
+// SQL_STRING_FUNCTIONS
+const SQL_FN_STR_CONCAT: u32 = 0x1;
+const SQL_FN_STR_LTRIM: u32 = 0x8;
+const SQL_FN_STR_LCASE: u32 = 0x40;
+const SQL_FN_STR_RTRIM: u32 = 0x400;
+const SQL_FN_STR_SUBSTRING: u32 = 0x800;
+const SQL_FN_STR_UCASE: u32 = 0x1000;
+const SQL_FN_STR_CHAR_LENGTH: u32 = 0x100000;
+const SQL_FN_STR_CHARACTER_LENGTH: u32 = 0x200000;
+const SQL_FN_STR_OCTET_LENGTH: u32 = 0x400000;
+const SQL_FN_STR_POSITION: u32 = 0x800000;
+
+// SQL_TIMEDATE_FUNCTIONS
+const SQL_FN_TD_CURRENT_TIMESTAMP: u32 = 0x80000;
+const SQL_FN_TD_EXTRACT: u32 = 0x100000;
+
+// SQL_SQL92_DATETIME_FUNCTIONS
+const SQL_SDF_CURRENT_TIMESTAMP: u32 = 0x4;
+
+// SQL_SQL92_NUMERIC_VALUE_FUNCTIONS
+const SQL_SNVF_CHAR_LENGTH: u32 = 0x2;
+const SQL_SNVF_CHARACTER_LENGTH: u32 = 0x4;
+const SQL_SNVF_EXTRACT: u32 = 0x8;
+const SQL_SNVF_OCTET_LENGTH: u32 = 0x10;
+const SQL_SNVF_POSITION: u32 = 0x20;
+
+// SQL_SQL92_STRING_FUNCTIONS
+const SQL_SSF_LOWER: u32 = 0x2;
+const SQL_SSF_UPPER: u32 = 0x4;
+const SQL_SSF_SUBSTRING: u32 = 0x8;
+const SQL_SSF_TRIM_BOTH: u32 = 0x20;
+const SQL_SSF_TRIM_LEADING: u32 = 0x40;
+const SQL_SSF_TRIM_TRAILING: u32 = 0x80;
+
+// SQL_SQL92_PREDICATES
+const SQL_SP_EXISTS: u32 = 0x1;
+const SQL_SP_ISNOTNULL: u32 = 0x2;
+const SQL_SP_ISNULL: u32 = 0x4;
+const SQL_SP_LIKE: u32 = 0x200;
+const SQL_SP_IN: u32 = 0x400;
+const SQL_SP_BETWEEN: u32 = 0x800;
+const SQL_SP_COMPARISON: u32 = 0x1000;
+const SQL_SP_QUANTIFIED_COMPARISON: u32 = 0x2000;
+
+// SQL_SQL92_RELATIONAL_JOIN_OPERATORS
+const SQL_SRJO_CROSS_JOIN: u32 = 0x2;
+const SQL_SRJO_INNER_JOIN: u32 = 0x10;
+const SQL_SRJO_LEFT_OUTER_JOIN: u32 = 0x40;
+const SQL_SRJO_RIGHT_OUTER_JOIN: u32 = 0x100;
+
+// SQL_SQL92_VALUE_EXPRESSIONS
+const SQL_SVE_CASE: u32 = 0x1;
+const SQL_SVE_CAST: u32 = 0x2;
+const SQL_SVE_COALESCE: u32 = 0x4;
+const SQL_SVE_NULLIF: u32 = 0x8;
+
+// SQL_AGGREGATE_FUNCTIONS
+const SQL_AF_AVG: u32 = 0x1;
+const SQL_AF_COUNT: u32 = 0x2;
+const SQL_AF_MAX: u32 = 0x4;
+const SQL_AF_MIN: u32 = 0x8;
+const SQL_AF_SUM: u32 = 0x10;
+const SQL_AF_DISTINCT: u32 = 0x20;
+const SQL_AF_ALL: u32 = 0x40;
+
+// SQL_OJ_CAPABILITIES
+const SQL_OJ_LEFT: u32 = 0x1;
+const SQL_OJ_RIGHT: u32 = 0x2;
+const SQL_OJ_NESTED: u32 = 0x8;
+const SQL_OJ_NOT_ORDERED: u32 = 0x10;
+const SQL_OJ_INNER: u32 = 0x20;
+const SQL_OJ_ALL_COMPARISON_OPS: u32 = 0x40;
+
+// SQL_SUBQUERIES
+const SQL_SQ_COMPARISON: u32 = 0x1;
+const SQL_SQ_EXISTS: u32 = 0x2;
+const SQL_SQ_IN: u32 = 0x4;
+const SQL_SQ_QUANTIFIED: u32 = 0x8;
+const SQL_SQ_CORRELATED_SUBQUERIES: u32 = 0x10;
+
+// SQL_UNION
+const SQL_U_UNION_ALL: u32 = 0x2;
+
+// SQL_GETDATA_EXTENSIONS
+const SQL_GD_ANY_COLUMN: u32 = 0x1;
+const SQL_GD_ANY_ORDER: u32 = 0x2;
+const SQL_GD_BOUND: u32 = 0x8;
+
+// SQL_FORWARD_ONLY_CURSOR_ATTRIBUTES1 and 2
+const SQL_CA1_NEXT: u32 = 0x1;
+const SQL_CA2_READ_ONLY_CONCURRENCY: u32 = 0x1;
+
+// SQL_DATETIME_LITERALS
+const SQL_DL_SQL92_TIMESTAMP: u32 = 0x4;
+
+// SQL_CATALOG_USAGE
+const SQL_CU_DML_STATEMENTS: u32 = 0x1;
+
+// SQL_SCROLL_OPTIONS
+const SQL_SO_FORWARD_ONLY: u32 = 0x1;
+
+// SQL_CURSOR_SENSITIVITY
+const SQL_INSENSITIVE: u32 = 1;
+
+// SQL_ODBC_INTERFACE_CONFORMANCE
+const SQL_OIC_CORE: u32 = 1;
+
+// SQL_SQL_CONFORMANCE
+const SQL_SC_SQL92_ENTRY: u32 = 0x1;
+
+// SQL_PARAM_ARRAY_ROW_COUNTS and SQL_PARAM_ARRAY_SELECTS
+const SQL_PARC_NO_BATCH: u32 = 2;
+const SQL_PAS_NO_SELECT: u32 = 3;
+
+// SQL_CURSOR_COMMIT_BEHAVIOR and SQL_CURSOR_ROLLBACK_BEHAVIOR
+const SQL_CB_PRESERVE: u16 = 2;
+// SQL_CONCAT_NULL_BEHAVIOR
+const SQL_CB_NULL: u16 = 0;
+// SQL_IDENTIFIER_CASE and SQL_QUOTED_IDENTIFIER_CASE
+const SQL_IC_SENSITIVE: u16 = 3;
+// SQL_CATALOG_LOCATION
+const SQL_CL_START: u16 = 1;
+// SQL_CORRELATION_NAME
+const SQL_CN_ANY: u16 = 2;
+// SQL_GROUP_BY
+const SQL_GB_GROUP_BY_CONTAINS_SELECT: u16 = 2;
+// SQL_NON_NULLABLE_COLUMNS
+const SQL_NNC_NULL: u16 = 0;
+// SQL_NULL_COLLATION
+const SQL_NC_LOW: u16 = 1;
+// SQL_TXN_CAPABLE
+const SQL_TC_NONE: u16 = 0;
+// SQL_FILE_USAGE
+const SQL_FILE_NOT_SUPPORTED: u16 = 0;
+
+/// static_info returns the value of the given information type when it doesn't depend on the
+/// connection, or [`None`] otherwise. Limits of 0 mean there is no limit, and bitmasks of 0 that
+/// the feature is not supported.
+pub fn static_info(info_type: InfoType) -> Option<InfoValue> {
+    let string = |value: &str| Some(InfoValue::String(value.to_string()));
+    let usmallint = |value: u16| Some(InfoValue::USmallInt(value));
+    let uinteger = |value: u32| Some(InfoValue::UInteger(value));
+    match info_type {
+        // Driver information
+        InfoType::DriverName => string(DRIVER_NAME),
+        InfoType::DriverVer => string(&odbc_version(env!("CARGO_PKG_VERSION"))),
+        InfoType::DriverOdbcVer => string(DRIVER_ODBC_VER),
+        InfoType::DbmsName => string(DBMS_NAME),
+        InfoType::ActiveEnvironments
+        | InfoType::MaxDriverConnections
+        | InfoType::MaxConcurrentActivities => usmallint(0),
+        InfoType::AsyncMode
+        | InfoType::AsyncDbcFunctions
+        | InfoType::AsyncNotification
+        | InfoType::MaxAsyncConcurrentStatements
+        | InfoType::DriverAwarePoolingSupported
+        | InfoType::BatchRowCount
+        | InfoType::BatchSupport
+        | InfoType::InfoSchemaViews => uinteger(0),
+        InfoType::FileUsage => usmallint(SQL_FILE_NOT_SUPPORTED),
+        InfoType::GetdataExtensions => {
+            uinteger(SQL_GD_ANY_COLUMN | SQL_GD_ANY_ORDER | SQL_GD_BOUND)
+        }
+        InfoType::OdbcInterfaceConformance => uinteger(SQL_OIC_CORE),
+        InfoType::ParamArrayRowCounts => uinteger(SQL_PARC_NO_BATCH),
+        InfoType::ParamArraySelects => uinteger(SQL_PAS_NO_SELECT),
+        InfoType::RowUpdates => string("N"),
+        InfoType::SearchPatternEscape => string(&SEARCH_PATTERN_ESCAPE.to_string()),
+        InfoType::StandardCliConformance => uinteger(0),
+        InfoType::XopenCliYear => string(""),
+
+        // Cursor attributes: only forward only read only cursors are supported.
+        InfoType::ForwardOnlyCursorAttributes1 => uinteger(SQL_CA1_NEXT),
+        InfoType::ForwardOnlyCursorAttributes2 => uinteger(SQL_CA2_READ_ONLY_CONCURRENCY),
+        InfoType::DynamicCursorAttributes1
+        | InfoType::DynamicCursorAttributes2
+        | InfoType::KeysetCursorAttributes1
+        | InfoType::KeysetCursorAttributes2
+        | InfoType::StaticCursorAttributes1
+        | InfoType::StaticCursorAttributes2 => uinteger(0),
+        InfoType::ScrollOptions => uinteger(SQL_SO_FORWARD_ONLY),
+        InfoType::CursorSensitivity => uinteger(SQL_INSENSITIVE),
+        InfoType::CursorCommitBehavior | InfoType::CursorRollbackBehavior => {
+            usmallint(SQL_CB_PRESERVE)
+        }
+        InfoType::BookmarkPersistence
+        | InfoType::LockTypes
+        | InfoType::PosOperations
+        | InfoType::PositionedStatements
+        | InfoType::StaticSensitivity => uinteger(0),
+
+        // Data source information
+        InfoType::AccessibleTables => string("Y"),
+        InfoType::AccessibleProcedures => string("N"),
+        InfoType::CatalogTerm => string("database"),
+        InfoType::SchemaTerm | InfoType::ProcedureTerm | InfoType::CollationSeq => string(""),
+        InfoType::TableTerm => string("collection"),
+        InfoType::ConcatNullBehavior => usmallint(SQL_CB_NULL),
+        InfoType::DataSourceReadOnly => string("Y"),
+        InfoType::DescribeParameter
+        | InfoType::MultResultSets
+        | InfoType::MultipleActiveTxn
+        | InfoType::NeedLongDataLen
+        | InfoType::Integrity
+        | InfoType::Procedures => string("N"),
+        InfoType::NullCollation => usmallint(SQL_NC_LOW),
+
+        // Transactions are not supported.
+        InfoType::TxnCapable => usmallint(SQL_TC_NONE),
+        InfoType::DefaultTxnIsolation | InfoType::TxnIsolationOption => uinteger(0),
+
+        // Supported SQL
+        InfoType::AggregateFunctions => uinteger(
+            SQL_AF_ALL
+                | SQL_AF_AVG
+                | SQL_AF_COUNT
+                | SQL_AF_DISTINCT
+                | SQL_AF_MAX
+                | SQL_AF_MIN
+                | SQL_AF_SUM,
+        ),
+        InfoType::CatalogLocation => usmallint(SQL_CL_START),
+        InfoType::CatalogName => string("Y"),
+        InfoType::CatalogNameSeparator => string("."),
+        InfoType::CatalogUsage => uinteger(SQL_CU_DML_STATEMENTS),
+        InfoType::SchemaUsage => uinteger(0),
+        InfoType::ColumnAlias => string("Y"),
+        InfoType::CorrelationName => usmallint(SQL_CN_ANY),
+        InfoType::DatetimeLiterals => uinteger(SQL_DL_SQL92_TIMESTAMP),
+        InfoType::ExpressionsInOrderby | InfoType::OrderByColumnsInSelect => string("N"),
+        InfoType::GroupBy => usmallint(SQL_GB_GROUP_BY_CONTAINS_SELECT),
+        InfoType::IdentifierCase | InfoType::QuotedIdentifierCase => usmallint(SQL_IC_SENSITIVE),
+        InfoType::IdentifierQuoteChar => string(IDENTIFIER_QUOTE_CHAR),
+        InfoType::Keywords => string(KEYWORDS),
+        InfoType::LikeEscapeClause => string("Y"),
+        InfoType::NonNullableColumns => usmallint(SQL_NNC_NULL),
+        InfoType::OjCapabilities => uinteger(
+            SQL_OJ_LEFT
+                | SQL_OJ_RIGHT
+                | SQL_OJ_NESTED
+                | SQL_OJ_NOT_ORDERED
+                | SQL_OJ_INNER
+                | SQL_OJ_ALL_COMPARISON_OPS,
+        ),
+        InfoType::OuterJoins => string("Y"),
+        InfoType::SpecialCharacters => string(SPECIAL_CHARACTERS),
+        InfoType::SqlConformance => uinteger(SQL_SC_SQL92_ENTRY),
+        InfoType::Sql92Predicates => uinteger(
+            SQL_SP_EXISTS
+                | SQL_SP_ISNOTNULL
+                | SQL_SP_ISNULL
+                | SQL_SP_LIKE
+                | SQL_SP_IN
+                | SQL_SP_BETWEEN
+                | SQL_SP_COMPARISON
+                | SQL_SP_QUANTIFIED_COMPARISON,
+        ),
+        InfoType::Sql92RelationalJoinOperators => uinteger(
+            SQL_SRJO_CROSS_JOIN
+                | SQL_SRJO_INNER_JOIN
+                | SQL_SRJO_LEFT_OUTER_JOIN
+                | SQL_SRJO_RIGHT_OUTER_JOIN,
+        ),
+        InfoType::Sql92ValueExpressions => {
+            uinteger(SQL_SVE_CASE | SQL_SVE_CAST | SQL_SVE_COALESCE | SQL_SVE_NULLIF)
+        }
+        InfoType::Subqueries => uinteger(
+            SQL_SQ_COMPARISON
+                | SQL_SQ_EXISTS
+                | SQL_SQ_IN
+                | SQL_SQ_QUANTIFIED
+                | SQL_SQ_CORRELATED_SUBQUERIES,
+        ),
+        InfoType::Union => uinteger(SQL_U_UNION_ALL),
+        // The data source is read only.
+        InfoType::AlterDomain
+        | InfoType::AlterTable
+        | InfoType::CreateAssertion
+        | InfoType::CreateCharacterSet
+        | InfoType::CreateCollation
+        | InfoType::CreateDomain
+        | InfoType::CreateSchema
+        | InfoType::CreateTable
+        | InfoType::CreateTranslation
+        | InfoType::CreateView
+        | InfoType::DdlIndex
+        | InfoType::DropAssertion
+        | InfoType::DropCharacterSet
+        | InfoType::DropCollation
+        | InfoType::DropDomain
+        | InfoType::DropSchema
+        | InfoType::DropTable
+        | InfoType::DropTranslation
+        | InfoType::DropView
+        | InfoType::IndexKeywords
+        | InfoType::InsertStatement
+        | InfoType::Sql92ForeignKeyDeleteRule
+        | InfoType::Sql92ForeignKeyUpdateRule
+        | InfoType::Sql92Grant
+        | InfoType::Sql92Revoke
+        | InfoType::Sql92RowValueConstructor => uinteger(0),
+
+        // SQL limits
+        InfoType::MaxCatalogNameLen => usmallint(MAX_CATALOG_NAME_LEN),
+        InfoType::MaxTableNameLen => usmallint(MAX_TABLE_NAME_LEN),
+        InfoType::MaxColumnsInIndex => usmallint(MAX_COLUMNS_IN_INDEX),
+        InfoType::MaxColumnNameLen
+        | InfoType::MaxCursorNameLen
+        | InfoType::MaxSchemaNameLen
+        | InfoType::MaxProcedureNameLen
+        | InfoType::MaxIdentifierLen
+        | InfoType::MaxColumnsInGroupBy
+        | InfoType::MaxColumnsInOrderBy
+        | InfoType::MaxColumnsInSelect
+        | InfoType::MaxColumnsInTable
+        | InfoType::MaxTablesInSelect
+        | InfoType::MaxUserNameLen => usmallint(0),
+        InfoType::MaxRowSize | InfoType::MaxStatementLen => uinteger(MAX_BSON_SIZE),
+        InfoType::MaxRowSizeIncludesLong => string("Y"),
+        InfoType::MaxIndexSize | InfoType::MaxCharLiteralLen | InfoType::MaxBinaryLiteralLen => {
+            uinteger(0)
+        }
+
+        // Scalar functions
+        InfoType::ConvertFunctions => uinteger(SQL_FN_CVT_CAST),
+        InfoType::NumericFunctions => uinteger(
+            SQL_FN_NUM_ABS
+                | SQL_FN_NUM_CEILING
+                | SQL_FN_NUM_COS
+                | SQL_FN_NUM_DEGREES
+                | SQL_FN_NUM_FLOOR
+                | SQL_FN_NUM_LOG
+                | SQL_FN_NUM_LOG10
+                | SQL_FN_NUM_MOD
+                | SQL_FN_NUM_POWER
+                | SQL_FN_NUM_RADIANS
+                | SQL_FN_NUM_ROUND
+                | SQL_FN_NUM_SIN
+                | SQL_FN_NUM_SQRT
+                | SQL_FN_NUM_TAN,
+        ),
+        InfoType::StringFunctions => uinteger(
+            SQL_FN_STR_CONCAT
+                | SQL_FN_STR_LTRIM
+                | SQL_FN_STR_LCASE
+                | SQL_FN_STR_RTRIM
+                | SQL_FN_STR_SUBSTRING
+                | SQL_FN_STR_UCASE
+                | SQL_FN_STR_CHAR_LENGTH
+                | SQL_FN_STR_CHARACTER_LENGTH
+                | SQL_FN_STR_OCTET_LENGTH
+                | SQL_FN_STR_POSITION,
+        ),
+        InfoType::TimedateFunctions => uinteger(SQL_FN_TD_CURRENT_TIMESTAMP | SQL_FN_TD_EXTRACT),
+        InfoType::SystemFunctions
+        | InfoType::TimedateAddIntervals
+        | InfoType::TimedateDiffIntervals => uinteger(0),
+        InfoType::Sql92DatetimeFunctions => uinteger(SQL_SDF_CURRENT_TIMESTAMP),
+        InfoType::Sql92NumericValueFunctions => uinteger(
+            SQL_SNVF_CHAR_LENGTH
+                | SQL_SNVF_CHARACTER_LENGTH
+                | SQL_SNVF_EXTRACT
+                | SQL_SNVF_OCTET_LENGTH
+                | SQL_SNVF_POSITION,
+        ),
+        InfoType::Sql92StringFunctions => uinteger(
+            SQL_SSF_LOWER
+                | SQL_SSF_UPPER
+                | SQL_SSF_SUBSTRING
+                | SQL_SSF_TRIM_BOTH
+                | SQL_SSF_TRIM_LEADING
+                | SQL_SSF_TRIM_TRAILING,
+        ),
+
+        // Conversions with CAST
+        InfoType::ConvertChar
+        | InfoType::ConvertVarchar
+        | InfoType::ConvertLongvarchar
+        | InfoType::ConvertWchar
+        | InfoType::ConvertWvarchar
+        | InfoType::ConvertWlongvarchar => {
+            uinteger(CVT_CHARACTER | CVT_NUMERIC | SQL_CVT_TIMESTAMP)
+        }
+        InfoType::ConvertBigint
+        | InfoType::ConvertBit
+        | InfoType::ConvertDecimal
+        | InfoType::ConvertDouble
+        | InfoType::ConvertFloat
+        | InfoType::ConvertInteger
+        | InfoType::ConvertNumeric
+        | InfoType::ConvertReal
+        | InfoType::ConvertSmallint
+        | InfoType::ConvertTinyint => uinteger(CVT_CHARACTER | CVT_NUMERIC),
+        InfoType::ConvertDate | InfoType::ConvertTimestamp => {
+            uinteger(CVT_CHARACTER | SQL_CVT_TIMESTAMP)
+        }
+        InfoType::ConvertBinary
+        | InfoType::ConvertVarbinary
+        | InfoType::ConvertLongvarbinary
+        | InfoType::ConvertTime
+        | InfoType::ConvertGuid
+        | InfoType::ConvertIntervalDayTime
+        | InfoType::ConvertIntervalYearMonth => uinteger(0),
+
+        // The values which depend on the connection.
+        InfoType::DataSourceName
+        | InfoType::ServerName
+        | InfoType::UserName
+        | InfoType::DatabaseName
+        | InfoType::DbmsVer => None,
+    }
+}
+
+/// odbc_version formats a version such as 6.0.3 or 7.0.2-rc1 as the ##.##.#### version
+/// expected by SQL_DRIVER_VER and SQL_DBMS_VER.
+pub fn odbc_version(version: &str) -> String {
+    let mut parts = version.split('.').map(|part| {
+        part.chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse::<u32>()
+            .unwrap_or(0)
+    });
+    let mut next = || parts.next().unwrap_or(0);
+    format!("{:02}.{:02}.{:04}", next(), next(), next())
+}
@@ -0,0 +1,138 @@
+use crate::{
+    api::{
+        definitions::InfoType,
+        info::odbc_version,
+        test_fixtures::{free_handles, new_connection, sql_state, with_errors},
+    },
+    handles::definitions::ConnectionState,
+    SQLGetInfo, SQLGetInfoW,
+};
+use odbc_sys::{SmallInt, SqlReturn, USmallInt};
+use std::{ffi::c_void, ptr::null_mut};
+
+#[test]
+fn string_info() {
+    let conn_handle = new_connection(null_mut(), ConnectionState::Allocated);
+    let mut buffer = [0u16; 10];
+    let mut string_length: SmallInt = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetInfoW(
+            conn_handle as *mut _,
+            InfoType::IdentifierQuoteChar as USmallInt,
+            buffer.as_mut_ptr() as *mut c_void,
+            (buffer.len() * 2) as SmallInt,
+            &mut string_length,
+        )
+    );
+    assert_eq!("`", String::from_utf16_lossy(&buffer[..1]));
+    // The length of wide strings is in bytes.
+    assert_eq!(2, string_length);
+    // Identifiers with characters other than letters, digits and underscores must be quoted.
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetInfoW(
+            conn_handle as *mut _,
+            InfoType::SpecialCharacters as USmallInt,
+            buffer.as_mut_ptr() as *mut c_void,
+            (buffer.len() * 2) as SmallInt,
+            &mut string_length,
+        )
+    );
+    assert_eq!(0, buffer[0]);
+    assert_eq!(0, string_length);
+
+    // Without an output buffer only the length is reported, which is not a truncation.
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetInfo(
+            conn_handle as *mut _,
+            InfoType::TableTerm as USmallInt,
+            null_mut(),
+            0,
+            &mut string_length,
+        )
+    );
+    assert_eq!(10, string_length);
+    assert_eq!(0, with_errors(conn_handle, Vec::len));
+
+    // The narrow string is truncated to the buffer length in bytes, null terminator included.
+    let mut buffer = [0u8; 4];
+    assert_eq!(
+        SqlReturn::SUCCESS_WITH_INFO,
+        SQLGetInfo(
+            conn_handle as *mut _,
+            InfoType::TableTerm as USmallInt,
+            buffer.as_mut_ptr() as *mut c_void,
+            buffer.len() as SmallInt,
+            &mut string_length,
+        )
+    );
+    assert_eq!(b"col\0", &buffer);
+    assert_eq!(10, string_length);
+    assert_eq!("01004", sql_state(conn_handle));
+    free_handles(&[conn_handle]);
+}
+
+#[test]
+fn numeric_info() {
+    let conn_handle = new_connection(null_mut(), ConnectionState::Allocated);
+    let mut string_length: SmallInt = 0;
+    let mut txn_capable: USmallInt = 42;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetInfoW(
+            conn_handle as *mut _,
+            InfoType::TxnCapable as USmallInt,
+            &mut txn_capable as *mut _ as *mut c_void,
+            0,
+            &mut string_length,
+        )
+    );
+    assert_eq!(0, txn_capable);
+    assert_eq!(2, string_length);
+
+    let mut getdata_extensions: u32 = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetInfoW(
+            conn_handle as *mut _,
+            InfoType::GetdataExtensions as USmallInt,
+            &mut getdata_extensions as *mut _ as *mut c_void,
+            0,
+            &mut string_length,
+        )
+    );
+    // SQL_GD_ANY_COLUMN | SQL_GD_ANY_ORDER | SQL_GD_BOUND
+    assert_eq!(0xB, getdata_extensions);
+    assert_eq!(4, string_length);
+    free_handles(&[conn_handle]);
+}
+
+#[test]
+fn invalid_info() {
+    // (info type, expected SQL state)
+    for (info_type, expected) in [(9999, "HY096"), (InfoType::DbmsVer as USmallInt, "08003")] {
+        let conn_handle = new_connection(null_mut(), ConnectionState::Allocated);
+        let mut buffer = [0u16; 10];
+        assert_eq!(
+            SqlReturn::ERROR,
+            SQLGetInfoW(
+                conn_handle as *mut _,
+                info_type,
+                buffer.as_mut_ptr() as *mut c_void,
+                (buffer.len() * 2) as SmallInt,
+                std::ptr::null_mut(),
+            )
+        );
+        assert_eq!(expected, sql_state(conn_handle));
+        free_handles(&[conn_handle]);
+    }
+}
+
+#[test]
+fn version_format() {
+    assert_eq!("07.00.0002", odbc_version("7.0.2"));
+    assert_eq!("06.00.0003", odbc_version("6.0.3-rc1"));
+    assert_eq!("00.01.0000", odbc_version("0.1"));
+}
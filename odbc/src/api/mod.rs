@@ -1,11 +1,61 @@
+pub(crate) mod data;
 pub(crate) mod definitions;
+pub(crate) mod dsn;
+pub(crate) mod encoding;
 pub(crate) mod errors;
 mod functions;
 pub use functions::*;
+pub(crate) mod info;
+pub(crate) mod odbc_uri;
+pub(crate) mod supported_functions;
 
+#[cfg(test)]
+mod col_attribute_tests;
+#[cfg(test)]
+mod columns_tests;
+#[cfg(test)]
+mod data_tests;
+#[cfg(test)]
+mod descriptor_tests;
+#[cfg(test)]
+mod driver_connect_tests;
+#[cfg(test)]
+mod dsn_tests;
+#[cfg(test)]
+mod encoding_tests;
 #[cfg(test)]
 mod env_attr_tests;
 #[cfg(test)]
+mod errors_tests;
+#[cfg(test)]
+mod exec_direct_tests;
+#[cfg(test)]
+mod fetch_tests;
+#[cfg(test)]
+mod free_stmt_tests;
+#[cfg(test)]
+mod get_diag_field_tests;
+#[cfg(test)]
 mod get_diag_rec_tests;
 #[cfg(test)]
+mod handle_registry_tests;
+#[cfg(test)]
+mod info_tests;
+#[cfg(test)]
+mod odbc_uri_tests;
+#[cfg(test)]
+mod panic_safety_tests;
+#[cfg(test)]
+mod primary_keys_tests;
+#[cfg(test)]
+mod state_tests;
+#[cfg(test)]
+mod statistics_tests;
+#[cfg(test)]
 mod stmt_attr_tests;
+#[cfg(test)]
+mod supported_functions_tests;
+#[cfg(test)]
+mod tables_tests;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
@@ -0,0 +1,233 @@
+use crate::api::errors::ODBCError;
+use std::collections::HashMap;
+
+pub type Result<T> = std::result::Result<T, ODBCError>;
+
+// Connection string keywords.
+pub const DRIVER: &str = "DRIVER";
+pub const DSN: &str = "DSN";
+pub const SERVER: &str = "SERVER";
+pub const USER: &str = "USER";
+pub const PWD: &str = "PWD";
+pub const DATABASE: &str = "DATABASE";
+pub const AUTH_SRC: &str = "AUTH_SRC";
+pub const CHARSET: &str = "CHARSET";
+
+// The order in which the attributes are written back in the normalized connection string.
+const NORMALIZED_ORDER: [&str; 8] = [DRIVER, DSN, SERVER, USER, PWD, DATABASE, AUTH_SRC, CHARSET];
+
+// Keywords which are accepted as aliases of one of the keywords above.
+const ALIASES: [(&str, &str); 2] = [("UID", USER), ("PASSWORD", PWD)];
+
+/// ODBCUri holds the attributes of an ODBC connection string, keyed by their upper-cased
+/// keyword. Keywords are matched case-insensitively and each keyword may only be specified once.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ODBCUri {
+    attributes: HashMap<String, String>,
+}
+
+impl ODBCUri {
+    /// new parses a connection string of the form `KEYWORD=value;KEYWORD={value};...`.
+    /// Values surrounded by braces may contain `;`, and a `}` inside a braced value is escaped
+    /// as `}}`.
+    pub fn new(odbc_uri: &str) -> Result<Self> {
+        let mut attributes = HashMap::new();
+        let mut chars = odbc_uri.chars().peekable();
+        loop {
+            let mut keyword = String::new();
+            let mut found_equal = false;
+            for c in chars.by_ref() {
+                match c {
+                    '=' => {
+                        found_equal = true;
+                        break;
+                    }
+                    ';' if keyword.trim().is_empty() => keyword.clear(),
+                    ';' => {
+                        return Err(ODBCError::InvalidConnectionString(format!(
+                            "missing value for keyword '{}'",
+                            keyword.trim()
+                        )))
+                    }
+                    _ => keyword.push(c),
+                }
+            }
+            let keyword = keyword.trim();
+            if !found_equal {
+                if keyword.is_empty() {
+                    break;
+                }
+                return Err(ODBCError::InvalidConnectionString(format!(
+                    "missing value for keyword '{}'",
+                    keyword
+                )));
+            }
+            if keyword.is_empty() {
+                return Err(ODBCError::InvalidConnectionString(
+                    "missing keyword before '='".to_string(),
+                ));
+            }
+
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            let value = if chars.next_if_eq(&'{').is_some() {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => {
+                            if chars.next_if_eq(&'}').is_some() {
+                                value.push('}');
+                            } else {
+                                break;
+                            }
+                        }
+                        Some(c) => value.push(c),
+                        None => {
+                            return Err(ODBCError::InvalidConnectionString(format!(
+                                "missing closing brace for the value of keyword '{}'",
+                                keyword
+                            )))
+                        }
+                    }
+                }
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                match chars.next() {
+                    Some(';') | None => value,
+                    Some(_) => {
+                        return Err(ODBCError::InvalidConnectionString(format!(
+                            "unexpected characters after the closing brace of keyword '{}'",
+                            keyword
+                        )))
+                    }
+                }
+            } else {
+                let value: String = chars.by_ref().take_while(|c| *c != ';').collect();
+                value.trim().to_string()
+            };
+
+            let keyword = normalize_keyword(keyword);
+            if attributes.contains_key(&keyword) {
+                return Err(ODBCError::InvalidConnectionString(format!(
+                    "keyword '{}' is specified more than once",
+                    keyword
+                )));
+            }
+            attributes.insert(keyword, value);
+        }
+        Ok(ODBCUri { attributes })
+    }
+
+    /// get returns the value of the given keyword, if it was specified.
+    pub fn get(&self, keyword: &str) -> Option<&str> {
+        self.attributes
+            .get(&normalize_keyword(keyword))
+            .map(String::as_str)
+    }
+
+    /// set sets the value of the given keyword, replacing the existing value if any.
+    pub fn set(&mut self, keyword: &str, value: String) {
+        self.attributes.insert(normalize_keyword(keyword), value);
+    }
+
+    /// remove unsets the given keyword, returning its value if it was set.
+    pub fn remove(&mut self, keyword: &str) -> Option<String> {
+        self.attributes.remove(&normalize_keyword(keyword))
+    }
+
+    /// set_defaults sets the value of each of the given keywords which are not already set.
+    pub fn set_defaults(&mut self, defaults: ODBCUri) {
+        defaults
+            .attributes
+            .into_iter()
+            .for_each(|(keyword, value)| {
+                self.attributes.entry(keyword).or_insert(value);
+            });
+    }
+
+    /// missing_attributes returns the required keywords which are not set. A server is always
+    /// required, and a user name requires a password and vice versa.
+    pub fn missing_attributes(&self) -> Vec<&'static str> {
+        let mut missing = vec![];
+        if self.get(SERVER).is_none() {
+            missing.push(SERVER);
+        }
+        match (self.get(USER), self.get(PWD)) {
+            (Some(_), None) => missing.push(PWD),
+            (None, Some(_)) => missing.push(USER),
+            _ => {}
+        }
+        missing
+    }
+
+    /// to_mongo_uri builds the MongoDB connection URI corresponding to this connection string.
+    pub fn to_mongo_uri(&self) -> Result<String> {
+        let missing = self.missing_attributes();
+        if !missing.is_empty() {
+            return Err(ODBCError::InvalidConnectionString(format!(
+                "missing required attributes {}",
+                missing.join(", ")
+            )));
+        }
+        let mut uri = "mongodb://".to_string();
+        if let (Some(user), Some(pwd)) = (self.get(USER), self.get(PWD)) {
+            uri.push_str(&format!(
+                "{}:{}@",
+                percent_encode(user),
+                percent_encode(pwd)
+            ));
+        }
+        uri.push_str(self.get(SERVER).unwrap_or_default());
+        uri.push('/');
+        if let Some(database) = self.get(DATABASE) {
+            uri.push_str(&percent_encode(database));
+        }
+        if let Some(auth_src) = self.get(AUTH_SRC) {
+            uri.push_str(&format!("?authSource={}", percent_encode(auth_src)));
+        }
+        Ok(uri)
+    }
+
+    /// to_connection_string returns the normalized connection string for the known keywords,
+    /// bracing the values which need it.
+    pub fn to_connection_string(&self) -> String {
+        NORMALIZED_ORDER
+            .iter()
+            .filter_map(|keyword| {
+                self.get(keyword)
+                    .map(|value| format!("{}={}", keyword, brace_value(value)))
+            })
+            .collect::<Vec<String>>()
+            .join(";")
+    }
+}
+
+// normalize_keyword upper-cases the keyword and resolves its aliases.
+fn normalize_keyword(keyword: &str) -> String {
+    let keyword = keyword.trim().to_uppercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == keyword)
+        .map_or(keyword, |(_, name)| name.to_string())
+}
+
+// brace_value surrounds the value with braces if it contains characters which would otherwise
+// be misinterpreted by a connection string parser.
+fn brace_value(value: &str) -> String {
+    if value.contains([';', '{', '}', '=']) || value.trim().len() != value.len() {
+        format!("{{{}}}", value.replace('}', "}}"))
+    } else {
+        value.to_string()
+    }
+}
+
+// percent_encode escapes all the characters which are not unreserved in an URI (RFC 3986).
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
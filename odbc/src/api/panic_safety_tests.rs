@@ -0,0 +1,566 @@
+use crate::{
+    api::{
+        errors::ODBCError,
+        functions::util::panic_safe_exec,
+        test_fixtures::{
+            allocate, environment, free_handles, open_connection, sql_state, with_errors,
+        },
+    },
+    handles::definitions::*,
+    *,
+};
+use odbc_sys::{
+    BulkOperation, CDataType, CompletionType, ConnectionAttribute, Desc, DriverConnectOption,
+    EnvironmentAttribute, FetchOrientation, Handle, HandleType, Integer, Len, Nullability,
+    ParamType, Pointer, SmallInt, SqlDataType, SqlReturn, StatementAttribute, USmallInt, WChar,
+    NTS,
+};
+use std::ptr::null_mut;
+
+const SMALL_NTS: SmallInt = NTS as SmallInt;
+const INTEGER_NTS: Integer = NTS as Integer;
+
+/// assert_no_panic asserts that the call to [`name`] returned and left no panic diagnostic on
+/// [`handle`].
+fn assert_no_panic(name: &str, handle: *mut MongoHandle, sql_return: SqlReturn) {
+    assert_ne!(SqlReturn::INVALID_HANDLE, sql_return, "{}", name);
+    let panicked = with_errors(handle, |errors| {
+        errors
+            .iter()
+            .any(|error| matches!(error, ODBCError::Panic(_, _)))
+    });
+    assert!(!panicked, "{} panicked", name);
+}
+
+#[test]
+fn panic_is_reported_as_general_error() {
+    let env = allocate(HandleType::Env, null_mut());
+    assert_eq!(
+        SqlReturn::ERROR,
+        panic_safe_exec("SQLTest", env as Handle, HandleType::Env, || {
+            panic!("an unexpected failure")
+        })
+    );
+    assert_eq!(1, with_errors(env, Vec::len));
+    assert_eq!("HY000", sql_state(env));
+    assert_eq!(
+        "[MongoDB][API] Internal error in SQLTest: an unexpected failure",
+        with_errors(env, |errors| errors[0].get_error_message())
+    );
+    free_handles(&[env]);
+}
+
+#[test]
+fn poisoned_handle_is_recovered() {
+    let env = allocate(HandleType::Env, null_mut());
+    let _ = panic_safe_exec("SQLTest", env as Handle, HandleType::Env, || {
+        let _guard = environment(env).write();
+        panic!("while holding the lock")
+    });
+    assert!(environment(env).is_poisoned());
+    let mut version: Integer = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetEnvAttr(
+            env as *mut _,
+            EnvironmentAttribute::OdbcVersion,
+            &mut version as *mut Integer as Pointer,
+            0,
+            null_mut(),
+        )
+    );
+    free_handles(&[env]);
+}
+
+/// call_statement_functions calls every function taking a statement handle on [`stmt`].
+fn call_statement_functions(stmt: *mut MongoHandle) {
+    let h = stmt as *mut _;
+    let text = [0u8];
+    let wtext = [0 as WChar];
+    let (t, w) = (text.as_ptr(), wtext.as_ptr());
+    // The buffers are aligned for the numeric values some of the functions write to them.
+    let mut buffer = [0u64; 128];
+    let mut wbuffer = [0u64; 256];
+    let b = buffer.as_mut_ptr() as *mut u8;
+    let wb = wbuffer.as_mut_ptr() as *mut WChar;
+    let p = b as Pointer;
+    let (mut small, mut len) = (0 as SmallInt, 0 as Len);
+    let (mut data_type, mut size) = (SqlDataType::UNKNOWN_TYPE, 0);
+    let mut nullability = Nullability::UNKNOWN;
+    let mut value_ptr: Pointer = null_mut();
+
+    for (name, sql_return) in [
+        (
+            "SQLBindCol",
+            SQLBindCol(h, 1, CDataType::Char, p, 1024, &mut 0),
+        ),
+        (
+            "SQLBindParameter",
+            SQLBindParameter(
+                h,
+                1,
+                ParamType::Input,
+                CDataType::Char,
+                SqlDataType::VARCHAR,
+                0,
+                0,
+                p,
+                1024,
+                &mut 0,
+            ),
+        ),
+        (
+            "SQLBulkOperations",
+            SQLBulkOperations(h, BulkOperation::Add),
+        ),
+        ("SQLCancel", SQLCancel(h)),
+        (
+            "SQLCancelHandle",
+            SQLCancelHandle(HandleType::Stmt, h as Handle),
+        ),
+        ("SQLCloseCursor", SQLCloseCursor(h)),
+        (
+            "SQLColAttribute",
+            SQLColAttribute(h, 1, Desc::Name, p, 1024, &mut 0, &mut 0),
+        ),
+        (
+            "SQLColAttributeW",
+            SQLColAttributeW(h, 1, Desc::Name, wb as Pointer, 1024, &mut 0, &mut 0),
+        ),
+        (
+            "SQLColumnPrivileges",
+            SQLColumnPrivileges(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLColumnPrivilegesW",
+            SQLColumnPrivilegesW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        (
+            "SQLColumns",
+            SQLColumns(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLColumnsW",
+            SQLColumnsW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        (
+            "SQLCompleteAsync",
+            SQLCompleteAsync(HandleType::Stmt, h as Handle, &mut 0),
+        ),
+        (
+            "SQLDescribeCol",
+            SQLDescribeCol(
+                h,
+                1,
+                b,
+                1024,
+                &mut small,
+                &mut data_type,
+                &mut size,
+                &mut 0,
+                &mut nullability,
+            ),
+        ),
+        (
+            "SQLDescribeColW",
+            SQLDescribeColW(
+                h,
+                1,
+                wb,
+                1024,
+                &mut 0,
+                &mut data_type,
+                &mut 0,
+                &mut 0,
+                &mut nullability,
+            ),
+        ),
+        (
+            "SQLDescribeParam",
+            SQLDescribeParam(h, 1, &mut data_type, &mut 0, &mut 0, &mut 0),
+        ),
+        ("SQLExecDirect", SQLExecDirect(h, t, INTEGER_NTS)),
+        ("SQLExecDirectW", SQLExecDirectW(h, w, INTEGER_NTS)),
+        ("SQLExecute", SQLExecute(h)),
+        ("SQLFetch", SQLFetch(h)),
+        (
+            "SQLFetchScroll",
+            SQLFetchScroll(h, FetchOrientation::Next, 0),
+        ),
+        (
+            "SQLForeignKeys",
+            SQLForeignKeys(
+                h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t,
+                SMALL_NTS,
+            ),
+        ),
+        (
+            "SQLForeignKeysW",
+            SQLForeignKeysW(
+                h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w,
+                SMALL_NTS,
+            ),
+        ),
+        ("SQLGetCursorName", SQLGetCursorName(h, b, 1024, &mut 0)),
+        ("SQLGetCursorNameW", SQLGetCursorNameW(h, wb, 1024, &mut 0)),
+        (
+            "SQLGetData",
+            SQLGetData(h, 1, CDataType::Char, p, 1024, &mut len),
+        ),
+        (
+            "SQLGetStmtAttr",
+            SQLGetStmtAttr(
+                h,
+                StatementAttribute::RowArraySize,
+                &mut len as *mut Len as Pointer,
+                0,
+                &mut 0,
+            ),
+        ),
+        (
+            "SQLGetStmtAttrW",
+            SQLGetStmtAttrW(
+                h,
+                StatementAttribute::AppRowDesc,
+                &mut value_ptr as *mut Pointer as Pointer,
+                0,
+                &mut 0,
+            ),
+        ),
+        ("SQLMoreResults", SQLMoreResults(h)),
+        ("SQLNumParams", SQLNumParams(h, &mut 0)),
+        ("SQLNumResultCols", SQLNumResultCols(h, &mut 0)),
+        ("SQLParamData", SQLParamData(h, &mut null_mut())),
+        ("SQLPrepare", SQLPrepare(h, t, INTEGER_NTS)),
+        ("SQLPrepareW", SQLPrepareW(h, w, INTEGER_NTS)),
+        (
+            "SQLPrimaryKeys",
+            SQLPrimaryKeys(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLPrimaryKeysW",
+            SQLPrimaryKeysW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        (
+            "SQLProcedureColumns",
+            SQLProcedureColumns(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLProcedureColumnsW",
+            SQLProcedureColumnsW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        (
+            "SQLProcedures",
+            SQLProcedures(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLProceduresW",
+            SQLProceduresW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        ("SQLPutData", SQLPutData(h, p, 0)),
+        ("SQLRowCount", SQLRowCount(h, &mut 0)),
+        ("SQLSetCursorName", SQLSetCursorName(h, t, SMALL_NTS)),
+        ("SQLSetCursorNameW", SQLSetCursorNameW(h, w, SMALL_NTS)),
+        ("SQLSetPos", SQLSetPos(h, 1, 0, 0)),
+        (
+            "SQLSetStmtAttr",
+            SQLSetStmtAttr(h, StatementAttribute::RowArraySize, 1 as Pointer, 0),
+        ),
+        (
+            "SQLSetStmtAttrW",
+            SQLSetStmtAttrW(h, StatementAttribute::AppRowDesc, null_mut(), 0),
+        ),
+        (
+            "SQLSpecialColumns",
+            SQLSpecialColumns(
+                h,
+                1,
+                t,
+                SMALL_NTS,
+                t,
+                SMALL_NTS,
+                t,
+                SMALL_NTS,
+                0,
+                Nullability::NULLABLE,
+            ),
+        ),
+        (
+            "SQLSpecialColumnsW",
+            SQLSpecialColumnsW(
+                h,
+                1,
+                w,
+                SMALL_NTS,
+                w,
+                SMALL_NTS,
+                w,
+                SMALL_NTS,
+                0,
+                Nullability::NULLABLE,
+            ),
+        ),
+        (
+            "SQLStatistics",
+            SQLStatistics(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, 0, 0),
+        ),
+        (
+            "SQLStatisticsW",
+            SQLStatisticsW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, 0, 0),
+        ),
+        (
+            "SQLTablePrivileges",
+            SQLTablePrivileges(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLTablePrivilegesW",
+            SQLTablePrivilegesW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        (
+            "SQLTables",
+            SQLTables(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLTablesW",
+            SQLTablesW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        (
+            "SQLGetTypeInfo",
+            SQLGetTypeInfo(h, SqlDataType::UNKNOWN_TYPE),
+        ),
+        ("SQLFreeStmt", SQLFreeStmt(h, 0)),
+    ] {
+        assert_no_panic(name, stmt, sql_return);
+    }
+}
+
+#[test]
+fn exported_functions_do_not_panic() {
+    let env = allocate(HandleType::Env, null_mut());
+    // Statements and descriptors are only allocated on open connections.
+    let conn = open_connection(env);
+    let stmt = allocate(HandleType::Stmt, conn);
+    let desc = allocate(HandleType::Desc, conn);
+    let other_desc = allocate(HandleType::Desc, conn);
+    let (e, c, d) = (env as *mut _, conn as *mut _, desc as *mut _);
+    let text = [0u8];
+    let wtext = [0 as WChar];
+    let (t, w) = (text.as_ptr(), wtext.as_ptr());
+    let mut buffer = [0u64; 128];
+    let mut wbuffer = [0u64; 256];
+    let (b, wb) = (
+        buffer.as_mut_ptr() as *mut u8,
+        wbuffer.as_mut_ptr() as *mut WChar,
+    );
+    let p = b as Pointer;
+    let mut nullability = Nullability::UNKNOWN;
+
+    // Environment functions.
+    for (name, sql_return) in [
+        (
+            "SQLSetEnvAttr",
+            SQLSetEnvAttr(e, EnvironmentAttribute::OdbcVersion, 3 as Pointer, 0),
+        ),
+        (
+            "SQLSetEnvAttrW",
+            SQLSetEnvAttrW(e, EnvironmentAttribute::OdbcVersion, 3 as Pointer, 0),
+        ),
+        (
+            "SQLGetEnvAttr",
+            SQLGetEnvAttr(e, EnvironmentAttribute::OdbcVersion, p, 0, &mut 0),
+        ),
+        (
+            "SQLGetEnvAttrW",
+            SQLGetEnvAttrW(e, EnvironmentAttribute::OdbcVersion, p, 0, &mut 0),
+        ),
+        (
+            "SQLDataSources",
+            SQLDataSources(e, FetchOrientation::First, b, 512, &mut 0, b, 512, &mut 0),
+        ),
+        (
+            "SQLDataSourcesW",
+            SQLDataSourcesW(e, FetchOrientation::First, wb, 512, &mut 0, wb, 512, &mut 0),
+        ),
+        (
+            "SQLDrivers",
+            SQLDrivers(e, FetchOrientation::First, b, 512, &mut 0, b, 512, &mut 0),
+        ),
+        (
+            "SQLDriversW",
+            SQLDriversW(e, FetchOrientation::First, wb, 512, &mut 0, wb, 512, &mut 0),
+        ),
+        (
+            "SQLEndTran",
+            SQLEndTran(HandleType::Env, e as Handle, CompletionType::Commit),
+        ),
+        (
+            "SQLGetDiagRec",
+            SQLGetDiagRec(HandleType::Env, e as Handle, 1, b, &mut 0, b, 512, &mut 0),
+        ),
+        (
+            "SQLGetDiagRecW",
+            SQLGetDiagRecW(HandleType::Env, e as Handle, 1, wb, &mut 0, wb, 512, &mut 0),
+        ),
+        (
+            "SQLGetDiagField",
+            SQLGetDiagField(HandleType::Env, e as Handle, 0, 2, p, 0, &mut 0),
+        ),
+        (
+            "SQLGetDiagFieldW",
+            SQLGetDiagFieldW(HandleType::Env, e as Handle, 0, 2, p, 0, &mut 0),
+        ),
+    ] {
+        assert_no_panic(name, env, sql_return);
+    }
+
+    // Connection functions, on a connection that is not connected.
+    for (name, sql_return) in [
+        (
+            "SQLBrowseConnect",
+            SQLBrowseConnect(c, t, SMALL_NTS, b, 512, &mut 0),
+        ),
+        (
+            "SQLBrowseConnectW",
+            SQLBrowseConnectW(c, w, SMALL_NTS, wb, 512, &mut 0),
+        ),
+        (
+            "SQLConnect",
+            SQLConnect(c, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+        ),
+        (
+            "SQLConnectW",
+            SQLConnectW(c, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+        ),
+        (
+            "SQLDriverConnect",
+            SQLDriverConnect(
+                c,
+                null_mut(),
+                t,
+                SMALL_NTS,
+                b,
+                512,
+                &mut 0,
+                DriverConnectOption::NoPrompt,
+            ),
+        ),
+        (
+            "SQLDriverConnectW",
+            SQLDriverConnectW(
+                c,
+                null_mut(),
+                w,
+                SMALL_NTS,
+                wb,
+                512,
+                &mut 0,
+                DriverConnectOption::NoPrompt,
+            ),
+        ),
+        (
+            "SQLGetConnectAttr",
+            SQLGetConnectAttr(c, ConnectionAttribute::LoginTimeout, p, 0, &mut 0),
+        ),
+        (
+            "SQLGetConnectAttrW",
+            SQLGetConnectAttrW(c, ConnectionAttribute::LoginTimeout, p, 0, &mut 0),
+        ),
+        (
+            "SQLSetConnectAttr",
+            SQLSetConnectAttr(c, ConnectionAttribute::LoginTimeout, null_mut(), 0),
+        ),
+        (
+            "SQLSetConnectAttrW",
+            SQLSetConnectAttrW(c, ConnectionAttribute::LoginTimeout, null_mut(), 0),
+        ),
+        (
+            "SQLGetFunctions",
+            SQLGetFunctions(c, 0, &mut [0 as USmallInt; 100] as *mut _ as *mut _),
+        ),
+        ("SQLGetInfo", SQLGetInfo(c, 17, p, 512, &mut 0)),
+        ("SQLGetInfoW", SQLGetInfoW(c, 17, p, 512, &mut 0)),
+        (
+            "SQLNativeSql",
+            SQLNativeSql(c, t, INTEGER_NTS, b, 512, &mut 0),
+        ),
+        (
+            "SQLNativeSqlW",
+            SQLNativeSqlW(c, w, INTEGER_NTS, wb, 512, &mut 0),
+        ),
+    ] {
+        assert_no_panic(name, conn, sql_return);
+    }
+
+    // Statement functions, before and after the statement has a result set.
+    call_statement_functions(stmt);
+    assert_no_panic(
+        "SQLGetTypeInfo",
+        stmt,
+        SQLGetTypeInfo(stmt as *mut _, SqlDataType::UNKNOWN_TYPE),
+    );
+    call_statement_functions(stmt);
+
+    // Descriptor functions.
+    for (name, sql_return) in [
+        (
+            "SQLSetDescField",
+            SQLSetDescField(d, 1, Desc::Type as SmallInt, 1 as Pointer, 0),
+        ),
+        (
+            "SQLSetDescRec",
+            SQLSetDescRec(d, 1, 1, 0, 512, 0, 0, p, &0, &0),
+        ),
+        (
+            "SQLGetDescField",
+            SQLGetDescField(d, 1, Desc::Type as SmallInt, p, 512, &mut 0),
+        ),
+        (
+            "SQLGetDescFieldW",
+            SQLGetDescFieldW(d, 1, Desc::Name as SmallInt, p, 512, &mut 0),
+        ),
+        (
+            "SQLGetDescRec",
+            SQLGetDescRec(
+                d,
+                1,
+                b,
+                512,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut nullability,
+            ),
+        ),
+        (
+            "SQLGetDescRecW",
+            SQLGetDescRecW(
+                d,
+                1,
+                wb,
+                512,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut 0,
+                &mut nullability,
+            ),
+        ),
+        ("SQLCopyDesc", SQLCopyDesc(d, other_desc as *mut _)),
+    ] {
+        assert_no_panic(name, desc, sql_return);
+    }
+
+    // Freeing the handles, the statement and the descriptors being freed by SQLDisconnect.
+    assert_no_panic("SQLDisconnect", conn, SQLDisconnect(c));
+    for (handle_type, handle) in [(HandleType::Dbc, conn), (HandleType::Env, env)] {
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLFreeHandle(handle_type, handle as Handle)
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    api::test_fixtures::{free_handles, new_statement, sql_state, statement},
+    handles::definitions::StatementState,
+    SQLPrimaryKeysW, SQLSpecialColumnsW,
+};
+use odbc_sys::{Nullability, SqlReturn, NTS};
+use std::ptr::{null, null_mut};
+
+#[test]
+fn primary_keys_without_connection() {
+    let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+    let table_name: Vec<u16> = "foo\0".encode_utf16().collect();
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLPrimaryKeysW(
+            stmt_handle as *mut _,
+            null(),
+            0,
+            null(),
+            0,
+            table_name.as_ptr(),
+            NTS as i16,
+        )
+    );
+    assert_eq!("08003", sql_state(stmt_handle));
+    assert!(statement(stmt_handle)
+        .read()
+        .unwrap()
+        .mongo_statement
+        .is_none());
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn primary_keys_without_table_name() {
+    let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLPrimaryKeysW(stmt_handle as *mut _, null(), 0, null(), 0, null(), 0)
+    );
+    assert_eq!("HY009", sql_state(stmt_handle));
+    assert!(statement(stmt_handle)
+        .read()
+        .unwrap()
+        .mongo_statement
+        .is_none());
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn special_columns_invalid_arguments() {
+    let table_name: Vec<u16> = "foo\0".encode_utf16().collect();
+    // (identifier type, table name, scope, expected SQL state)
+    for (identifier_type, table_name, scope, expected) in [
+        (3, table_name.as_ptr(), 2, "HY097"),
+        (1, table_name.as_ptr(), 3, "HY098"),
+        (1, null(), 2, "HY009"),
+        (1, table_name.as_ptr(), 2, "08003"),
+    ] {
+        let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+        assert_eq!(
+            SqlReturn::ERROR,
+            SQLSpecialColumnsW(
+                stmt_handle as *mut _,
+                identifier_type,
+                null(),
+                0,
+                null(),
+                0,
+                table_name,
+                NTS as i16,
+                scope,
+                Nullability::NULLABLE,
+            )
+        );
+        assert_eq!(expected, sql_state(stmt_handle));
+        assert!(statement(stmt_handle)
+            .read()
+            .unwrap()
+            .mongo_statement
+            .is_none());
+        free_handles(&[stmt_handle]);
+    }
+}
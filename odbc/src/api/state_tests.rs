@@ -0,0 +1,201 @@
+use crate::{
+    api::test_fixtures::{connection, free_handles, new_statement, sql_state, statement},
+    handles::definitions::*,
+    SQLAllocHandle, SQLDisconnect, SQLExecDirectW, SQLExecute, SQLFetch, SQLFreeHandle, SQLGetData,
+    SQLGetTypeInfo, SQLMoreResults, SQLNumResultCols, SQLPrepareW, SQLSetEnvAttr, SQLSetStmtAttr,
+};
+use mongo_odbc_core::MongoTypesInfo;
+use odbc_sys::{
+    CDataType, EnvironmentAttribute, Handle, HandleType, Integer, Pointer, SqlDataType, SqlReturn,
+    StatementAttribute, WChar, NTS,
+};
+use std::ptr::null_mut;
+
+fn state(stmt_handle: *mut MongoHandle) -> StatementState {
+    statement(stmt_handle).read().unwrap().state
+}
+
+fn get_data(stmt_handle: *mut MongoHandle) -> SqlReturn {
+    let mut value = 0i64;
+    SQLGetData(
+        stmt_handle as *mut _,
+        2,
+        CDataType::SBigInt,
+        &mut value as *mut i64 as Pointer,
+        0,
+        null_mut(),
+    )
+}
+
+#[test]
+fn result_set_transitions() {
+    let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+    let stmt = stmt_handle as *mut _;
+    // Without a result set, nothing can be fetched or described.
+    for sql_return in [
+        SQLFetch(stmt),
+        get_data(stmt_handle),
+        SQLNumResultCols(stmt, &mut 0),
+        SQLExecute(stmt),
+    ] {
+        assert_eq!(SqlReturn::ERROR, sql_return);
+        assert_eq!("HY010", sql_state(stmt_handle));
+    }
+    assert_eq!(SqlReturn::NO_DATA, SQLMoreResults(stmt));
+    assert_eq!(StatementState::Allocated, state(stmt_handle));
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetTypeInfo(stmt, SqlDataType::UNKNOWN_TYPE)
+    );
+    assert_eq!(StatementState::ExecutedHasResultSet, state(stmt_handle));
+    // The cursor is open but not positioned on a row yet, and must be closed before the
+    // statement is reused or its cursor attributes changed.
+    let query = [0 as WChar];
+    for sql_return in [
+        get_data(stmt_handle),
+        SQLGetTypeInfo(stmt, SqlDataType::UNKNOWN_TYPE),
+        SQLPrepareW(stmt, query.as_ptr(), 0),
+        SQLSetStmtAttr(stmt, StatementAttribute::CursorType, null_mut(), 0),
+    ] {
+        assert_eq!(SqlReturn::ERROR, sql_return);
+        assert_eq!("24000", sql_state(stmt_handle));
+    }
+    assert_eq!(StatementState::ExecutedHasResultSet, state(stmt_handle));
+
+    assert_eq!(SqlReturn::SUCCESS, SQLFetch(stmt));
+    assert_eq!(StatementState::CursorFetchSet, state(stmt_handle));
+    assert_eq!(SqlReturn::SUCCESS, get_data(stmt_handle));
+    // There is a single result set, so SQLMoreResults closes the cursor.
+    assert_eq!(SqlReturn::NO_DATA, SQLMoreResults(stmt));
+    assert_eq!(StatementState::Allocated, state(stmt_handle));
+    assert_eq!(SqlReturn::ERROR, SQLFetch(stmt));
+    assert_eq!("HY010", sql_state(stmt_handle));
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn prepared_statement_transitions() {
+    let stmt_handle = new_statement(null_mut(), StatementState::PreparedHasResultSet);
+    let stmt = stmt_handle as *mut _;
+    {
+        let mut stmt_contents = statement(stmt_handle).write().unwrap();
+        stmt_contents.is_prepared = true;
+        stmt_contents.mongo_statement = Some(Box::new(MongoTypesInfo::list_types_info(
+            SqlDataType::UNKNOWN_TYPE,
+        )));
+    }
+    // The result set of a prepared statement can be described, but not fetched.
+    let mut column_count = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLNumResultCols(stmt, &mut column_count)
+    );
+    assert_eq!(19, column_count);
+    assert_eq!(SqlReturn::ERROR, SQLFetch(stmt));
+    assert_eq!("HY010", sql_state(stmt_handle));
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLSetStmtAttr(stmt, StatementAttribute::Concurrency, null_mut(), 0)
+    );
+    assert_eq!("HY011", sql_state(stmt_handle));
+    // An execution failing leaves the statement prepared, and so does a new query failing to
+    // find an open connection.
+    let query: Vec<WChar> = "select * from bar\0".encode_utf16().collect();
+    for sql_return in [
+        SQLExecute(stmt),
+        SQLPrepareW(stmt, query.as_ptr(), NTS as Integer),
+        SQLExecDirectW(stmt, query.as_ptr(), NTS as Integer),
+    ] {
+        assert_eq!(SqlReturn::ERROR, sql_return);
+        assert_eq!("08003", sql_state(stmt_handle));
+        assert_eq!(StatementState::PreparedHasResultSet, state(stmt_handle));
+        assert!(statement(stmt_handle)
+            .read()
+            .unwrap()
+            .mongo_statement
+            .is_some());
+    }
+
+    // Once executed, the cursor must be closed before executing the statement again, which
+    // goes back to the prepared state.
+    statement(stmt_handle).write().unwrap().state = StatementState::ExecutedHasResultSet;
+    assert_eq!(SqlReturn::ERROR, SQLExecute(stmt));
+    assert_eq!("24000", sql_state(stmt_handle));
+    assert_eq!(SqlReturn::NO_DATA, SQLMoreResults(stmt));
+    assert_eq!(StatementState::PreparedHasResultSet, state(stmt_handle));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLNumResultCols(stmt, &mut column_count)
+    );
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn connection_and_environment_transitions() {
+    let mut env_handle: Handle = null_mut();
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLAllocHandle(HandleType::Env, null_mut(), &mut env_handle)
+    );
+    let env = env_handle as *mut MongoHandle;
+    let mut conn_handle: Handle = null_mut();
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLAllocHandle(HandleType::Dbc, env_handle, &mut conn_handle)
+    );
+    let conn = conn_handle as *mut MongoHandle;
+
+    // The environment attributes can't change, nor the environment be freed, while it has
+    // connections.
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLSetEnvAttr(
+            env_handle as *mut _,
+            EnvironmentAttribute::OdbcVersion,
+            3 as Pointer,
+            0
+        )
+    );
+    assert_eq!("HY011", sql_state(env));
+    assert_eq!(SqlReturn::ERROR, SQLFreeHandle(HandleType::Env, env_handle));
+    assert_eq!("HY010", sql_state(env));
+
+    // Statements can only be allocated on an open connection, and a connection must be
+    // disconnected before it is freed.
+    let mut stmt_handle: Handle = null_mut();
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLAllocHandle(HandleType::Stmt, conn_handle, &mut stmt_handle)
+    );
+    assert_eq!("08003", sql_state(conn));
+    connection(conn).write().unwrap().state = ConnectionState::Connected;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLAllocHandle(HandleType::Stmt, conn_handle, &mut stmt_handle)
+    );
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLFreeHandle(HandleType::Dbc, conn_handle)
+    );
+    assert_eq!("HY010", sql_state(conn));
+    assert_eq!(SqlReturn::SUCCESS, SQLDisconnect(conn_handle as *mut _));
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Dbc, conn_handle)
+    );
+
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLSetEnvAttr(
+            env_handle as *mut _,
+            EnvironmentAttribute::OdbcVersion,
+            3 as Pointer,
+            0
+        )
+    );
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLFreeHandle(HandleType::Env, env_handle)
+    );
+}
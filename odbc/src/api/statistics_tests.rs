@@ -0,0 +1,42 @@
+use crate::{
+    api::test_fixtures::{free_handles, new_statement, sql_state, statement},
+    handles::definitions::StatementState,
+    SQLStatisticsW,
+};
+use odbc_sys::{SqlReturn, NTS};
+use std::ptr::{null, null_mut};
+
+#[test]
+fn statistics_invalid_arguments() {
+    let table_name: Vec<u16> = "foo\0".encode_utf16().collect();
+    // (table name, unique, reserved, expected SQL state)
+    for (table_name, unique, reserved, expected) in [
+        (table_name.as_ptr(), 2, 0, "HY100"),
+        (table_name.as_ptr(), 1, 2, "HY101"),
+        (null(), 1, 1, "HY009"),
+        (table_name.as_ptr(), 0, 1, "08003"),
+    ] {
+        let stmt_handle = new_statement(null_mut(), StatementState::Allocated);
+        assert_eq!(
+            SqlReturn::ERROR,
+            SQLStatisticsW(
+                stmt_handle as *mut _,
+                null(),
+                0,
+                null(),
+                0,
+                table_name,
+                NTS as i16,
+                unique,
+                reserved,
+            )
+        );
+        assert_eq!(expected, sql_state(stmt_handle));
+        assert!(statement(stmt_handle)
+            .read()
+            .unwrap()
+            .mongo_statement
+            .is_none());
+        free_handles(&[stmt_handle]);
+    }
+}
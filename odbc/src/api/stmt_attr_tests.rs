@@ -1,10 +1,13 @@
 use crate::{
-    api::definitions::*,
-    handles::definitions::{MongoHandle, Statement, StatementState},
-    map, SQLGetStmtAttrW, SQLSetStmtAttrW,
+    api::{
+        definitions::*,
+        test_fixtures::{free_handles, new_statement, statement},
+    },
+    handles::definitions::{MongoHandle, StatementState},
+    SQLGetStmtAttrW, SQLSetStmtAttrW,
 };
 use odbc_sys::{HStmt, Integer, Pointer, SqlReturn, StatementAttribute, ULen, USmallInt};
-use std::{collections::BTreeMap, mem::size_of, sync::RwLock};
+use std::{collections::BTreeMap, mem::size_of, ptr::null_mut};
 
 fn get_set_stmt_attr(
     handle: *mut MongoHandle,
@@ -115,10 +118,7 @@ fn get_set_ptr(
 #[test]
 fn test_supported_attributes() {
     use crate::map;
-    let stmt_handle: *mut _ = &mut MongoHandle::Statement(RwLock::new(Statement::with_state(
-        std::ptr::null_mut(),
-        StatementState::Allocated,
-    )));
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
 
     get_set_stmt_attr(
         stmt_handle,
@@ -210,6 +210,12 @@ fn test_supported_attributes() {
         },
         0,
     );
+    get_set_ptr(
+        stmt_handle,
+        StatementAttribute::RowBindOffsetPtr,
+        true,
+        size_of::<*mut ULen>(),
+    );
     get_set_ptr(
         stmt_handle,
         StatementAttribute::RowStatusPtr,
@@ -239,6 +245,28 @@ fn test_supported_attributes() {
         },
         UseBookmarks::Off as usize,
     );
+    free_handles(&[stmt_handle]);
+}
+
+// test_query_timeout tests that a query timeout too large for the core is clamped instead of
+// being dropped.
+#[test]
+fn test_query_timeout() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLSetStmtAttrW(
+            stmt_handle as HStmt,
+            StatementAttribute::QueryTimeout,
+            (i32::MAX as usize + 1) as Pointer,
+            0,
+        )
+    );
+    let stmt = statement(stmt_handle);
+    assert_eq!(Some(i32::MAX), stmt.read().unwrap().query_timeout());
+    stmt.write().unwrap().attributes.query_timeout = 30;
+    assert_eq!(Some(30), stmt.read().unwrap().query_timeout());
+    free_handles(&[stmt_handle]);
 }
 
 // test_unsupported_attributes tests SQLGetStmtAttr and SQLSetStmtAttr with every
@@ -246,10 +274,7 @@ fn test_supported_attributes() {
 #[test]
 fn test_unsupported_attributes() {
     use crate::map;
-    let stmt_handle: *mut _ = &mut MongoHandle::Statement(RwLock::new(Statement::with_state(
-        std::ptr::null_mut(),
-        StatementState::Allocated,
-    )));
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
 
     get_set_stmt_attr(
         stmt_handle,
@@ -293,18 +318,6 @@ fn test_unsupported_attributes() {
         },
         0,
     );
-    get_set_ptr(
-        stmt_handle,
-        StatementAttribute::AppRowDesc,
-        false,
-        size_of::<Pointer>(),
-    );
-    get_set_ptr(
-        stmt_handle,
-        StatementAttribute::AppParamDesc,
-        false,
-        size_of::<Pointer>(),
-    );
     get_set_ptr(
         stmt_handle,
         StatementAttribute::AsyncStmtEvent,
@@ -317,18 +330,6 @@ fn test_unsupported_attributes() {
         false,
         size_of::<Pointer>(),
     );
-    get_set_ptr(
-        stmt_handle,
-        StatementAttribute::ImpParamDesc,
-        false,
-        size_of::<Pointer>(),
-    );
-    get_set_ptr(
-        stmt_handle,
-        StatementAttribute::ImpRowDesc,
-        false,
-        size_of::<Pointer>(),
-    );
     get_set_ptr(
         stmt_handle,
         StatementAttribute::ParamBindOffsetPtr,
@@ -353,16 +354,11 @@ fn test_unsupported_attributes() {
         false,
         size_of::<*mut ULen>(),
     );
-    get_set_ptr(
-        stmt_handle,
-        StatementAttribute::RowBindOffsetPtr,
-        false,
-        size_of::<*mut ULen>(),
-    );
     get_set_ptr(
         stmt_handle,
         StatementAttribute::RowOperationPtr,
         false,
         size_of::<*mut USmallInt>(),
     );
+    free_handles(&[stmt_handle]);
 }
@@ -0,0 +1,132 @@
+use crate::api::definitions::SqlBool;
+
+/// OdbcFunction is an entry of the registry of the ODBC functions the driver exports.
+#[derive(Clone, Copy, Debug)]
+pub struct OdbcFunction {
+    /// The name of the function, without the W suffix of its Unicode variant. Only the test
+    /// checking the registry against the exported functions reads it.
+    #[allow(dead_code)]
+    pub name: &'static str,
+    /// The SQL_API_* identifier of the function.
+    pub id: u16,
+    /// Whether the function does more than returning HYC00. When the driver exports a Unicode
+    /// variant, this is the status of the Unicode variant, since driver managers call it in place
+    /// of the ANSI one.
+    pub implemented: bool,
+}
+
+impl OdbcFunction {
+    const fn new(name: &'static str, id: u16, implemented: bool) -> Self {
+        OdbcFunction {
+            name,
+            id,
+            implemented,
+        }
+    }
+}
+
+// SQL_API_ALL_FUNCTIONS, the ODBC 2 identifier asking for the status of the functions with an
+// identifier lower than SQL_API_ALL_FUNCTIONS_SIZE.
+pub const SQL_API_ALL_FUNCTIONS: u16 = 0;
+pub const SQL_API_ALL_FUNCTIONS_SIZE: usize = 100;
+// SQL_API_ODBC3_ALL_FUNCTIONS, the identifier asking for the bitmap of the status of all the
+// functions, made of SQL_API_ODBC3_ALL_FUNCTIONS_SIZE SQLUSMALLINTs.
+pub const SQL_API_ODBC3_ALL_FUNCTIONS: u16 = 999;
+pub const SQL_API_ODBC3_ALL_FUNCTIONS_SIZE: usize = 250;
+
+/// ODBC_FUNCTIONS is the registry of the ODBC functions exported by the driver, which
+/// SQLGetFunctions reports on. Every exported function must have an entry.
+pub const ODBC_FUNCTIONS: &[OdbcFunction] = &[
+    OdbcFunction::new("SQLAllocHandle", 1001, true),
+    OdbcFunction::new("SQLBindCol", 4, true),
+    OdbcFunction::new("SQLBindParameter", 72, false),
+    OdbcFunction::new("SQLBrowseConnect", 55, false),
+    OdbcFunction::new("SQLBulkOperations", 24, false),
+    OdbcFunction::new("SQLCancel", 5, false),
+    OdbcFunction::new("SQLCancelHandle", 1550, false),
+    OdbcFunction::new("SQLCloseCursor", 1003, true),
+    OdbcFunction::new("SQLColAttribute", 6, true),
+    OdbcFunction::new("SQLColumnPrivileges", 56, false),
+    OdbcFunction::new("SQLColumns", 40, true),
+    OdbcFunction::new("SQLCompleteAsync", 1551, false),
+    OdbcFunction::new("SQLConnect", 7, true),
+    OdbcFunction::new("SQLCopyDesc", 1004, true),
+    OdbcFunction::new("SQLDataSources", 57, false),
+    OdbcFunction::new("SQLDescribeCol", 8, true),
+    OdbcFunction::new("SQLDescribeParam", 58, false),
+    OdbcFunction::new("SQLDisconnect", 9, true),
+    OdbcFunction::new("SQLDriverConnect", 41, true),
+    OdbcFunction::new("SQLDrivers", 71, false),
+    OdbcFunction::new("SQLEndTran", 1005, false),
+    OdbcFunction::new("SQLExecDirect", 11, true),
+    OdbcFunction::new("SQLExecute", 12, true),
+    OdbcFunction::new("SQLFetch", 13, true),
+    OdbcFunction::new("SQLFetchScroll", 1021, true),
+    OdbcFunction::new("SQLForeignKeys", 60, false),
+    OdbcFunction::new("SQLFreeHandle", 1006, true),
+    OdbcFunction::new("SQLFreeStmt", 16, true),
+    OdbcFunction::new("SQLGetConnectAttr", 1007, false),
+    OdbcFunction::new("SQLGetCursorName", 17, false),
+    OdbcFunction::new("SQLGetData", 43, true),
+    OdbcFunction::new("SQLGetDescField", 1008, true),
+    OdbcFunction::new("SQLGetDescRec", 1009, true),
+    OdbcFunction::new("SQLGetDiagField", 1010, true),
+    OdbcFunction::new("SQLGetDiagRec", 1011, true),
+    OdbcFunction::new("SQLGetEnvAttr", 1012, true),
+    OdbcFunction::new("SQLGetFunctions", 44, true),
+    OdbcFunction::new("SQLGetInfo", 45, true),
+    OdbcFunction::new("SQLGetStmtAttr", 1014, true),
+    OdbcFunction::new("SQLGetTypeInfo", 47, true),
+    OdbcFunction::new("SQLMoreResults", 61, true),
+    OdbcFunction::new("SQLNativeSql", 62, false),
+    OdbcFunction::new("SQLNumParams", 63, false),
+    OdbcFunction::new("SQLNumResultCols", 18, true),
+    OdbcFunction::new("SQLParamData", 48, false),
+    OdbcFunction::new("SQLPrepare", 19, true),
+    OdbcFunction::new("SQLPrimaryKeys", 65, true),
+    OdbcFunction::new("SQLProcedureColumns", 66, false),
+    OdbcFunction::new("SQLProcedures", 67, false),
+    OdbcFunction::new("SQLPutData", 49, false),
+    OdbcFunction::new("SQLRowCount", 20, false),
+    OdbcFunction::new("SQLSetConnectAttr", 1016, false),
+    OdbcFunction::new("SQLSetCursorName", 21, false),
+    OdbcFunction::new("SQLSetDescField", 1017, true),
+    OdbcFunction::new("SQLSetDescRec", 1018, true),
+    OdbcFunction::new("SQLSetEnvAttr", 1019, true),
+    OdbcFunction::new("SQLSetPos", 68, false),
+    OdbcFunction::new("SQLSetStmtAttr", 1020, true),
+    OdbcFunction::new("SQLSpecialColumns", 52, true),
+    OdbcFunction::new("SQLStatistics", 53, true),
+    OdbcFunction::new("SQLTablePrivileges", 70, false),
+    OdbcFunction::new("SQLTables", 54, true),
+];
+
+/// find_function returns the registry entry of the function with the SQL_API_* identifier
+/// [`id`], or [`None`] if the driver doesn't export it.
+pub fn find_function(id: u16) -> Option<&'static OdbcFunction> {
+    ODBC_FUNCTIONS.iter().find(|function| function.id == id)
+}
+
+/// odbc3_functions_bitmap returns the SQL_API_ODBC3_ALL_FUNCTIONS bitmap, where the bit of
+/// each implemented function is set.
+pub fn odbc3_functions_bitmap() -> [u16; SQL_API_ODBC3_ALL_FUNCTIONS_SIZE] {
+    let mut bitmap = [0; SQL_API_ODBC3_ALL_FUNCTIONS_SIZE];
+    ODBC_FUNCTIONS
+        .iter()
+        .filter(|function| function.implemented)
+        .for_each(|function| bitmap[function.id as usize >> 4] |= 1 << (function.id & 0xF));
+    bitmap
+}
+
+/// odbc2_functions_array returns the SQL_API_ALL_FUNCTIONS array, where the element of each
+/// implemented function with an identifier lower than SQL_API_ALL_FUNCTIONS_SIZE is SQL_TRUE.
+pub fn odbc2_functions_array() -> [u16; SQL_API_ALL_FUNCTIONS_SIZE] {
+    let mut functions = [0; SQL_API_ALL_FUNCTIONS_SIZE];
+    ODBC_FUNCTIONS
+        .iter()
+        .filter(|function| {
+            function.implemented && (function.id as usize) < SQL_API_ALL_FUNCTIONS_SIZE
+        })
+        .for_each(|function| functions[function.id as usize] = SqlBool::True as u16);
+    functions
+}
@@ -0,0 +1,297 @@
+use crate::{
+    api::{
+        errors::ODBCError,
+        supported_functions::{
+            ODBC_FUNCTIONS, SQL_API_ALL_FUNCTIONS, SQL_API_ALL_FUNCTIONS_SIZE,
+            SQL_API_ODBC3_ALL_FUNCTIONS, SQL_API_ODBC3_ALL_FUNCTIONS_SIZE,
+        },
+        test_fixtures::{
+            free_handles, new_connection, new_env, new_statement, sql_state, with_errors,
+        },
+    },
+    handles::definitions::*,
+    *,
+};
+use odbc_sys::{
+    BulkOperation, CDataType, Char, CompletionType, ConnectionAttribute, FetchOrientation, Handle,
+    HandleType, Integer, ParamType, SmallInt, SqlDataType, SqlReturn, USmallInt, WChar, NTS,
+};
+use std::{collections::BTreeSet, ptr::null_mut};
+
+const SMALL_NTS: SmallInt = NTS as SmallInt;
+
+// Whether the only error posted on the handle is the HYC00 for the unimplemented function.
+fn unimplemented(handle: *mut MongoHandle, name: &str) -> bool {
+    with_errors(
+        handle,
+        |errors| matches!(errors.as_slice(), [ODBCError::Unimplemented(function)] if *function == name),
+    )
+}
+
+#[test]
+fn registry_matches_stubs() {
+    let env = new_env();
+    let conn = new_connection(env, ConnectionState::Allocated);
+    let stmt = new_statement(null_mut(), StatementState::Allocated);
+    let (e, c, h) = (env as *mut _, conn as *mut _, stmt as *mut _);
+    let (t, w): (*const Char, *const WChar) = (c"".as_ptr() as *const Char, [0].as_ptr());
+    let (b, wb) = (null_mut::<Char>(), null_mut::<WChar>());
+    let fetch_next = FetchOrientation::Next;
+    let mut stubbed = BTreeSet::new();
+    let mut stub = |name: &'static str, handle: *mut MongoHandle, sql_return: SqlReturn| {
+        assert_eq!(SqlReturn::ERROR, sql_return, "{name}");
+        assert!(unimplemented(handle, name), "{name} is not a stub");
+        stubbed.insert(name.strip_suffix('W').unwrap_or(name));
+    };
+    stub(
+        "SQLBindParameter",
+        stmt,
+        SQLBindParameter(
+            h,
+            1,
+            ParamType::Input,
+            CDataType::Char,
+            SqlDataType::VARCHAR,
+            0,
+            0,
+            null_mut(),
+            0,
+            null_mut(),
+        ),
+    );
+    stub(
+        "SQLBrowseConnect",
+        conn,
+        SQLBrowseConnect(c, t, SMALL_NTS, b, 0, null_mut()),
+    );
+    stub(
+        "SQLBrowseConnectW",
+        conn,
+        SQLBrowseConnectW(c, w, SMALL_NTS, wb, 0, null_mut()),
+    );
+    stub(
+        "SQLBulkOperations",
+        stmt,
+        SQLBulkOperations(h, BulkOperation::Add),
+    );
+    stub("SQLCancel", stmt, SQLCancel(h));
+    stub(
+        "SQLCancelHandle",
+        stmt,
+        SQLCancelHandle(HandleType::Stmt, stmt as Handle),
+    );
+    stub(
+        "SQLColumnPrivileges",
+        stmt,
+        SQLColumnPrivileges(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+    );
+    stub(
+        "SQLColumnPrivilegesW",
+        stmt,
+        SQLColumnPrivilegesW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+    );
+    stub(
+        "SQLCompleteAsync",
+        stmt,
+        SQLCompleteAsync(HandleType::Stmt, stmt as Handle, null_mut()),
+    );
+    stub(
+        "SQLDataSources",
+        env,
+        SQLDataSources(e, fetch_next, b, 0, null_mut(), b, 0, null_mut()),
+    );
+    stub(
+        "SQLDataSourcesW",
+        env,
+        SQLDataSourcesW(e, fetch_next, wb, 0, null_mut(), wb, 0, null_mut()),
+    );
+    stub(
+        "SQLDescribeParam",
+        stmt,
+        SQLDescribeParam(h, 1, null_mut(), null_mut(), null_mut(), null_mut()),
+    );
+    stub(
+        "SQLDrivers",
+        env,
+        SQLDrivers(e, fetch_next, b, 0, null_mut(), b, 0, null_mut()),
+    );
+    stub(
+        "SQLDriversW",
+        env,
+        SQLDriversW(e, fetch_next, wb, 0, null_mut(), wb, 0, null_mut()),
+    );
+    stub(
+        "SQLEndTran",
+        conn,
+        SQLEndTran(HandleType::Dbc, conn as Handle, CompletionType::Commit),
+    );
+    stub(
+        "SQLForeignKeys",
+        stmt,
+        SQLForeignKeys(
+            h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS,
+        ),
+    );
+    stub(
+        "SQLForeignKeysW",
+        stmt,
+        SQLForeignKeysW(
+            h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS,
+        ),
+    );
+    stub(
+        "SQLGetConnectAttr",
+        conn,
+        SQLGetConnectAttr(
+            c,
+            ConnectionAttribute::AutoCommit,
+            null_mut(),
+            0,
+            null_mut(),
+        ),
+    );
+    stub(
+        "SQLGetConnectAttrW",
+        conn,
+        SQLGetConnectAttrW(
+            c,
+            ConnectionAttribute::AutoCommit,
+            null_mut(),
+            0,
+            null_mut(),
+        ),
+    );
+    stub(
+        "SQLGetCursorName",
+        stmt,
+        SQLGetCursorName(h, b, 0, null_mut()),
+    );
+    stub(
+        "SQLGetCursorNameW",
+        stmt,
+        SQLGetCursorNameW(h, wb, 0, null_mut()),
+    );
+    stub(
+        "SQLNativeSql",
+        conn,
+        SQLNativeSql(c, t, NTS as Integer, b, 0, null_mut()),
+    );
+    stub(
+        "SQLNativeSqlW",
+        conn,
+        SQLNativeSqlW(c, w, NTS as Integer, wb, 0, null_mut()),
+    );
+    stub("SQLNumParams", stmt, SQLNumParams(h, null_mut()));
+    stub("SQLParamData", stmt, SQLParamData(h, null_mut()));
+    stub(
+        "SQLProcedureColumns",
+        stmt,
+        SQLProcedureColumns(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+    );
+    stub(
+        "SQLProcedureColumnsW",
+        stmt,
+        SQLProcedureColumnsW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+    );
+    stub(
+        "SQLProcedures",
+        stmt,
+        SQLProcedures(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+    );
+    stub(
+        "SQLProceduresW",
+        stmt,
+        SQLProceduresW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+    );
+    stub("SQLPutData", stmt, SQLPutData(h, null_mut(), 0));
+    stub("SQLRowCount", stmt, SQLRowCount(h, null_mut()));
+    stub(
+        "SQLSetConnectAttr",
+        conn,
+        SQLSetConnectAttr(c, ConnectionAttribute::AutoCommit, null_mut(), 0),
+    );
+    stub(
+        "SQLSetConnectAttrW",
+        conn,
+        SQLSetConnectAttrW(c, ConnectionAttribute::AutoCommit, null_mut(), 0),
+    );
+    stub("SQLSetCursorName", stmt, SQLSetCursorName(h, t, SMALL_NTS));
+    stub(
+        "SQLSetCursorNameW",
+        stmt,
+        SQLSetCursorNameW(h, w, SMALL_NTS),
+    );
+    stub("SQLSetPos", stmt, SQLSetPos(h, 1, 0, 0));
+    stub(
+        "SQLTablePrivileges",
+        stmt,
+        SQLTablePrivileges(h, t, SMALL_NTS, t, SMALL_NTS, t, SMALL_NTS),
+    );
+    stub(
+        "SQLTablePrivilegesW",
+        stmt,
+        SQLTablePrivilegesW(h, w, SMALL_NTS, w, SMALL_NTS, w, SMALL_NTS),
+    );
+
+    // Every stub, ANSI or wide, must be registered as unimplemented, and every unimplemented
+    // function must have a stub.
+    let registered: BTreeSet<&str> = ODBC_FUNCTIONS
+        .iter()
+        .filter(|function| !function.implemented)
+        .map(|function| function.name)
+        .collect();
+    assert_eq!(registered, stubbed);
+
+    free_handles(&[stmt, conn, env]);
+}
+
+#[test]
+fn get_functions() {
+    let conn_handle = new_connection(null_mut(), ConnectionState::Allocated);
+    // SQL_API_SQLFETCH is implemented, SQL_API_SQLROWCOUNT is not.
+    let (sql_api_sqlfetch, sql_api_sqlrowcount) = (13, 20);
+
+    let mut bitmap = [0 as USmallInt; SQL_API_ODBC3_ALL_FUNCTIONS_SIZE];
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetFunctions(
+            conn_handle as *mut _,
+            SQL_API_ODBC3_ALL_FUNCTIONS,
+            bitmap.as_mut_ptr()
+        )
+    );
+    let is_set = |id: usize| bitmap[id >> 4] & (1 << (id & 0xF)) != 0;
+    assert!(is_set(sql_api_sqlfetch));
+    assert!(!is_set(sql_api_sqlrowcount));
+    // SQL_API_SQLFETCHSCROLL
+    assert!(is_set(1021));
+
+    let mut functions = [0 as USmallInt; SQL_API_ALL_FUNCTIONS_SIZE];
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetFunctions(
+            conn_handle as *mut _,
+            SQL_API_ALL_FUNCTIONS,
+            functions.as_mut_ptr()
+        )
+    );
+    assert_eq!(1, functions[sql_api_sqlfetch]);
+    assert_eq!(0, functions[sql_api_sqlrowcount]);
+
+    let mut supported: USmallInt = 42;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLGetFunctions(
+            conn_handle as *mut _,
+            sql_api_sqlrowcount as u16,
+            &mut supported
+        )
+    );
+    assert_eq!(0, supported);
+    assert_eq!(
+        SqlReturn::ERROR,
+        SQLGetFunctions(conn_handle as *mut _, 500, &mut supported)
+    );
+    assert_eq!("HY095", sql_state(conn_handle));
+    free_handles(&[conn_handle]);
+}
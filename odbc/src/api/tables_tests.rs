@@ -0,0 +1,139 @@
+use crate::{
+    api::test_fixtures::{free_handles, new_statement, sql_state},
+    handles::definitions::{MongoHandle, StatementState},
+    SQLFetch, SQLGetData, SQLNumResultCols, SQLTables, SQLTablesW,
+};
+use odbc_sys::{CDataType, Len, Pointer, SmallInt, SqlReturn, NTS, NULL_DATA};
+use std::ptr::{null, null_mut};
+
+// Call SQLTablesW with the given arguments, None standing for a null pointer.
+fn tables(
+    stmt_handle: *mut MongoHandle,
+    catalog_name: Option<&str>,
+    schema_name: Option<&str>,
+    table_name: Option<&str>,
+    table_type: Option<&str>,
+) -> SqlReturn {
+    let to_wide = |argument: Option<&str>| {
+        argument.map(|argument| argument.encode_utf16().collect::<Vec<u16>>())
+    };
+    let (catalog_name, schema_name, table_name, table_type) = (
+        to_wide(catalog_name),
+        to_wide(schema_name),
+        to_wide(table_name),
+        to_wide(table_type),
+    );
+    let argument = |argument: &Option<Vec<u16>>| match argument {
+        None => (null(), 0),
+        Some(argument) => (argument.as_ptr(), argument.len() as SmallInt),
+    };
+    let (catalog_name, catalog_name_length) = argument(&catalog_name);
+    let (schema_name, schema_name_length) = argument(&schema_name);
+    let (table_name, table_name_length) = argument(&table_name);
+    let (table_type, table_type_length) = argument(&table_type);
+    SQLTablesW(
+        stmt_handle as *mut _,
+        catalog_name,
+        catalog_name_length,
+        schema_name,
+        schema_name_length,
+        table_name,
+        table_name_length,
+        table_type,
+        table_type_length,
+    )
+}
+
+#[test]
+fn all_table_types() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        tables(stmt_handle, Some(""), Some(""), Some(""), Some("%"))
+    );
+    let mut column_count: SmallInt = 0;
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLNumResultCols(stmt_handle as *mut _, &mut column_count)
+    );
+    assert_eq!(5, column_count);
+
+    let mut table_types = vec![];
+    while SQLFetch(stmt_handle as *mut _) == SqlReturn::SUCCESS {
+        let mut table_type = [0u8; 8];
+        let mut indicator: Len = 0;
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLGetData(
+                stmt_handle as *mut _,
+                1,
+                CDataType::Char,
+                table_type.as_mut_ptr() as Pointer,
+                table_type.len() as Len,
+                &mut indicator,
+            )
+        );
+        assert_eq!(NULL_DATA, indicator);
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLGetData(
+                stmt_handle as *mut _,
+                4,
+                CDataType::Char,
+                table_type.as_mut_ptr() as Pointer,
+                table_type.len() as Len,
+                &mut indicator,
+            )
+        );
+        table_types.push(String::from_utf8_lossy(&table_type[..indicator as usize]).to_string());
+    }
+    assert_eq!(vec!["TABLE", "VIEW"], table_types);
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn all_schemas() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+    let (empty, all) = (b"\0", b"%\0");
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLTables(
+            stmt_handle as *mut _,
+            empty.as_ptr(),
+            NTS as SmallInt,
+            all.as_ptr(),
+            NTS as SmallInt,
+            empty.as_ptr(),
+            NTS as SmallInt,
+            null_mut(),
+            0,
+        )
+    );
+    // MongoDB has no schemas.
+    assert_eq!(SqlReturn::NO_DATA, SQLFetch(stmt_handle as *mut _));
+    free_handles(&[stmt_handle]);
+}
+
+#[test]
+fn tables_without_connection() {
+    for (catalog_name, table_name) in [(Some("%"), Some("")), (None, Some("foo%")), (None, None)] {
+        let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::Allocated);
+        assert_eq!(
+            SqlReturn::ERROR,
+            tables(stmt_handle, catalog_name, Some(""), table_name, None)
+        );
+        assert_eq!("08003", sql_state(stmt_handle));
+        free_handles(&[stmt_handle]);
+    }
+}
+
+#[test]
+fn tables_with_open_cursor() {
+    let stmt_handle: *mut _ = new_statement(null_mut(), StatementState::ExecutedHasResultSet);
+    assert_eq!(
+        SqlReturn::ERROR,
+        tables(stmt_handle, Some(""), Some(""), Some(""), Some("%"))
+    );
+    assert_eq!("24000", sql_state(stmt_handle));
+    free_handles(&[stmt_handle]);
+}
@@ -0,0 +1,92 @@
+use crate::{api::errors::ODBCError, handles::definitions::*, SQLAllocHandle};
+use odbc_sys::{Handle, HandleType, SqlReturn};
+use std::{ptr::null_mut, sync::RwLock};
+
+/// allocate allocates a handle of type [`handle_type`] on [`input_handle`] with SQLAllocHandle.
+pub(crate) fn allocate(
+    handle_type: HandleType,
+    input_handle: *mut MongoHandle,
+) -> *mut MongoHandle {
+    let mut handle: Handle = null_mut();
+    assert_eq!(
+        SqlReturn::SUCCESS,
+        SQLAllocHandle(handle_type, input_handle as Handle, &mut handle)
+    );
+    handle as *mut _
+}
+
+/// open_connection allocates a connection on [`env_handle`] which statements and descriptors can
+/// be allocated on, as if it were connected.
+pub(crate) fn open_connection(env_handle: *mut MongoHandle) -> *mut MongoHandle {
+    let conn_handle = allocate(HandleType::Dbc, env_handle);
+    connection(conn_handle).write().unwrap().state = ConnectionState::Connected;
+    conn_handle
+}
+
+/// new_env creates an allocated environment, without going through SQLAllocHandle.
+pub(crate) fn new_env() -> *mut MongoHandle {
+    MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated))).into_raw()
+}
+
+/// new_connection creates a connection in [`state`] on [`env_handle`], without going through
+/// SQLAllocHandle. The environment can be null.
+pub(crate) fn new_connection(
+    env_handle: *mut MongoHandle,
+    state: ConnectionState,
+) -> *mut MongoHandle {
+    MongoHandle::Connection(RwLock::new(Connection::with_state(env_handle, state))).into_raw()
+}
+
+/// new_statement creates a statement in [`state`] on [`conn_handle`], without going through
+/// SQLAllocHandle. The connection can be null.
+pub(crate) fn new_statement(
+    conn_handle: *mut MongoHandle,
+    state: StatementState,
+) -> *mut MongoHandle {
+    MongoHandle::Statement(RwLock::new(Statement::with_state(conn_handle, state))).into_raw()
+}
+
+/// environment returns the environment behind [`env_handle`].
+pub(crate) fn environment(env_handle: *mut MongoHandle) -> &'static RwLock<Env> {
+    unsafe { &*env_handle }.as_env().unwrap()
+}
+
+/// connection returns the connection behind [`conn_handle`].
+pub(crate) fn connection(conn_handle: *mut MongoHandle) -> &'static RwLock<Connection> {
+    unsafe { &*conn_handle }.as_connection().unwrap()
+}
+
+/// statement returns the statement behind [`stmt_handle`].
+pub(crate) fn statement(stmt_handle: *mut MongoHandle) -> &'static RwLock<Statement> {
+    unsafe { &*stmt_handle }.as_statement().unwrap()
+}
+
+/// with_errors calls [`f`] with the diagnostics posted on [`handle`], whatever its type.
+pub(crate) fn with_errors<T>(handle: *mut MongoHandle, f: impl FnOnce(&Vec<ODBCError>) -> T) -> T {
+    match unsafe { &*handle } {
+        MongoHandle::Env(env) => f(&env.read().unwrap().errors),
+        MongoHandle::Connection(conn) => f(&conn.read().unwrap().errors),
+        MongoHandle::Statement(stmt) => f(&stmt.read().unwrap().errors),
+        MongoHandle::Descriptor(desc) => f(&desc.read().unwrap().errors),
+    }
+}
+
+/// sql_state returns the SQL state of the first diagnostic posted on [`handle`], or an empty
+/// string if there is none.
+pub(crate) fn sql_state(handle: *mut MongoHandle) -> String {
+    with_errors(handle, |errors| {
+        errors
+            .first()
+            .map(|error| error.get_sql_state().to_string())
+            .unwrap_or_default()
+    })
+}
+
+/// free_handles frees [`handles`], listed from the children to their parents, at the end of a
+/// test. They are dropped without going through SQLFreeHandle, which would reject the handles
+/// created directly in a state it can't free them in.
+pub(crate) fn free_handles(handles: &[*mut MongoHandle]) {
+    for handle in handles {
+        assert!(MongoHandle::free(*handle));
+    }
+}
@@ -1,15 +1,84 @@
-use crate::api::{definitions::*, errors::ODBCError};
-use odbc_sys::{HDbc, HEnv, HStmt, Handle, Len, Pointer, ULen, USmallInt};
-use std::{borrow::BorrowMut, collections::HashSet, ptr::null_mut, sync::RwLock};
+use crate::api::{
+    data::{c_type_from_code, c_type_size},
+    definitions::*,
+    encoding::Charset,
+    errors::ODBCError,
+};
+use lazy_static::lazy_static;
+use mongo_odbc_core::{MongoConnection, MongoStatement};
+use odbc_sys::{
+    CDataType, HDbc, HDesc, HEnv, HStmt, Handle, HandleType, Len, Pointer, SmallInt, SqlDataType,
+    SqlReturn, ULen, USmallInt,
+};
+use std::{
+    borrow::BorrowMut,
+    collections::{HashMap, HashSet},
+    mem::size_of,
+    ptr::null_mut,
+    sync::{PoisonError, RwLock},
+};
+
+lazy_static! {
+    // The addresses of the handles allocated by the driver and not freed yet. The handles passed
+    // in by the application are checked against it before being dereferenced.
+    static ref LIVE_HANDLES: RwLock<HashSet<usize>> = RwLock::new(HashSet::new());
+}
 
 #[derive(Debug)]
 pub enum MongoHandle {
     Env(RwLock<Env>),
     Connection(RwLock<Connection>),
     Statement(RwLock<Statement>),
+    Descriptor(RwLock<Descriptor>),
 }
 
 impl MongoHandle {
+    /// into_raw moves the handle to the heap and registers it as live, returning the pointer the
+    /// application refers to it by.
+    pub fn into_raw(self) -> *mut MongoHandle {
+        let handle = Box::into_raw(Box::new(self));
+        LIVE_HANDLES
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(handle as usize);
+        handle
+    }
+
+    /// free unregisters and drops the handle, unless it is not live. It returns whether the
+    /// handle was freed.
+    pub fn free(handle: *mut MongoHandle) -> bool {
+        let live = LIVE_HANDLES
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&(handle as usize));
+        if live {
+            let _ = unsafe { Box::from_raw(handle) };
+        }
+        live
+    }
+
+    /// from_live returns the handle [`handle`] if it is live and of the type [`handle_type`].
+    pub fn from_live(handle: Handle, handle_type: HandleType) -> Option<MongoHandleRef> {
+        if !LIVE_HANDLES
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&(handle as usize))
+        {
+            return None;
+        }
+        let handle = MongoHandleRef::from(handle);
+        (handle.handle_type() == handle_type).then_some(handle)
+    }
+
+    pub fn handle_type(&self) -> HandleType {
+        match self {
+            MongoHandle::Env(_) => HandleType::Env,
+            MongoHandle::Connection(_) => HandleType::Dbc,
+            MongoHandle::Statement(_) => HandleType::Stmt,
+            MongoHandle::Descriptor(_) => HandleType::Desc,
+        }
+    }
+
     pub fn as_env(&self) -> Option<&RwLock<Env>> {
         match self {
             MongoHandle::Env(e) => Some(e),
@@ -31,42 +100,127 @@ impl MongoHandle {
         }
     }
 
+    pub fn as_descriptor(&self) -> Option<&RwLock<Descriptor>> {
+        match self {
+            MongoHandle::Descriptor(d) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// charset returns the charset of the narrow strings of the ANSI functions called on this
+    /// handle: the one of the connection for connections and their statements and descriptors,
+    /// and UTF-8 for environments.
+    pub fn charset(&self) -> Charset {
+        match self {
+            MongoHandle::Env(_) => Charset::default(),
+            MongoHandle::Connection(c) => c.read().unwrap_or_else(PoisonError::into_inner).charset,
+            MongoHandle::Statement(s) => unsafe {
+                s.read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .connection
+                    .as_ref()
+            }
+            .map_or_else(Charset::default, MongoHandle::charset),
+            MongoHandle::Descriptor(d) => unsafe {
+                d.read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .connection
+                    .as_ref()
+            }
+            .map_or_else(Charset::default, MongoHandle::charset),
+        }
+    }
+
     /// add_diag_info appends a new ODBCError object to the `errors` field.
     pub fn add_diag_info(&mut self, error: ODBCError) {
         match self {
             MongoHandle::Env(e) => {
-                let mut env_contents = (*e).write().unwrap();
+                let mut env_contents = (*e).write().unwrap_or_else(PoisonError::into_inner);
                 env_contents.errors.push(error);
             }
             MongoHandle::Connection(c) => {
-                let mut dbc_contents = (*c).write().unwrap();
+                let mut dbc_contents = (*c).write().unwrap_or_else(PoisonError::into_inner);
                 dbc_contents.errors.push(error);
             }
             MongoHandle::Statement(s) => {
-                let mut stmt_contents = (*s).write().unwrap();
+                let mut stmt_contents = (*s).write().unwrap_or_else(PoisonError::into_inner);
                 stmt_contents.errors.push(error);
             }
+            MongoHandle::Descriptor(d) => {
+                let mut desc_contents = (*d).write().unwrap_or_else(PoisonError::into_inner);
+                desc_contents.errors.push(error);
+            }
+        }
+    }
+
+    /// return_code returns the return code of the last function called on the handle.
+    pub fn return_code(&self) -> SqlReturn {
+        match self {
+            MongoHandle::Env(e) => e.read().unwrap_or_else(PoisonError::into_inner).return_code,
+            MongoHandle::Connection(c) => {
+                c.read().unwrap_or_else(PoisonError::into_inner).return_code
+            }
+            MongoHandle::Statement(s) => {
+                s.read().unwrap_or_else(PoisonError::into_inner).return_code
+            }
+            MongoHandle::Descriptor(d) => {
+                d.read().unwrap_or_else(PoisonError::into_inner).return_code
+            }
+        }
+    }
+
+    /// set_return_code records [`return_code`] as the return code of the last function called on
+    /// the handle.
+    pub fn set_return_code(&mut self, return_code: SqlReturn) {
+        match self {
+            MongoHandle::Env(e) => {
+                e.write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .return_code = return_code
+            }
+            MongoHandle::Connection(c) => {
+                c.write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .return_code = return_code
+            }
+            MongoHandle::Statement(s) => {
+                s.write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .return_code = return_code
+            }
+            MongoHandle::Descriptor(d) => {
+                d.write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .return_code = return_code
+            }
         }
     }
 
     pub fn clear_diagnostics(&mut self) {
         match self {
             MongoHandle::Env(e) => {
-                let mut env_contents = (*e).write().unwrap();
+                let mut env_contents = (*e).write().unwrap_or_else(PoisonError::into_inner);
                 env_contents.errors.clear();
             }
             MongoHandle::Connection(c) => {
-                let mut dbc_contents = (*c).write().unwrap();
+                let mut dbc_contents = (*c).write().unwrap_or_else(PoisonError::into_inner);
                 dbc_contents.errors.clear();
             }
             MongoHandle::Statement(s) => {
-                let mut stmt_contents = (*s).write().unwrap();
+                let mut stmt_contents = (*s).write().unwrap_or_else(PoisonError::into_inner);
+                stmt_contents.dynamic_function = DynamicFunction::UnknownStatement;
                 stmt_contents.errors.clear();
             }
+            MongoHandle::Descriptor(d) => {
+                let mut desc_contents = (*d).write().unwrap_or_else(PoisonError::into_inner);
+                desc_contents.errors.clear();
+            }
         }
     }
 }
 
+// The From implementations don't check the handle, which must have been checked with
+// MongoHandle::from_live first.
 pub type MongoHandleRef = &'static mut MongoHandle;
 
 impl From<Handle> for MongoHandleRef {
@@ -93,6 +247,12 @@ impl From<HDbc> for MongoHandleRef {
     }
 }
 
+impl From<HDesc> for MongoHandleRef {
+    fn from(handle: HDesc) -> Self {
+        unsafe { (*(handle as *mut MongoHandle)).borrow_mut() }
+    }
+}
+
 #[derive(Debug)]
 pub struct Env {
     // attributes for this Env. We box the attributes so that the MongoHandle type
@@ -101,6 +261,9 @@ pub struct Env {
     // state of this Env
     pub state: EnvState,
     pub connections: HashSet<*mut MongoHandle>,
+    // The return code of the last function called on the handle, reported in the header of the
+    // diagnostics.
+    pub return_code: SqlReturn,
     pub errors: Vec<ODBCError>,
 }
 
@@ -110,6 +273,7 @@ impl Env {
             attributes: Box::new(EnvAttributes::default()),
             state,
             connections: HashSet::new(),
+            return_code: SqlReturn::SUCCESS,
             errors: vec![],
         }
     }
@@ -134,7 +298,7 @@ impl Default for EnvAttributes {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnvState {
     Allocated,
     ConnectionAllocated,
@@ -147,22 +311,44 @@ pub struct Connection {
     // this Connection was allocated
     pub env: *mut MongoHandle,
     // all the possible Connection settings
-    pub _attributes: Box<ConnectionAttributes>,
+    pub attributes: Box<ConnectionAttributes>,
     // state of this connection
     pub state: ConnectionState,
     // MongoDB Client for issuing commands
-    // pub client: Option<MongoClient>,
+    pub mongo_connection: Option<MongoConnection>,
+    // The DSN, server and user the connection was established with, as reported by SQLGetInfo
+    pub data_source: DataSource,
+    // The charset of the narrow strings of the ANSI functions, from the CHARSET keyword of the
+    // connection string
+    pub charset: Charset,
     // all Statements allocated from this Connection
     pub statements: HashSet<*mut MongoHandle>,
+    // all the Descriptors the application explicitly allocated from this Connection
+    pub descriptors: HashSet<*mut MongoHandle>,
+    // The return code of the last function called on the handle, reported in the header of the
+    // diagnostics.
+    pub return_code: SqlReturn,
     pub errors: Vec<ODBCError>,
 }
 
+#[derive(Debug, Default)]
+pub struct DataSource {
+    pub name: String,
+    pub server: String,
+    pub user: String,
+}
+
 #[derive(Debug, Default)]
 pub struct ConnectionAttributes {
+    // SQL_ATTR_CURRENT_CATALOG
     pub current_db: Option<String>,
+    // SQL_ATTR_LOGIN_TIMEOUT, in seconds
+    pub login_timeout: Option<i32>,
+    // SQL_ATTR_CONNECTION_TIMEOUT, in seconds
+    pub connection_timeout: Option<i32>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionState {
     Allocated,
     _ConnectionFunctionNeedsDataEnv,
@@ -175,9 +361,14 @@ impl Connection {
     pub fn with_state(env: *mut MongoHandle, state: ConnectionState) -> Self {
         Self {
             env,
-            _attributes: Box::new(ConnectionAttributes::default()),
+            attributes: Box::new(ConnectionAttributes::default()),
             state,
+            mongo_connection: None,
+            data_source: DataSource::default(),
+            charset: Charset::default(),
             statements: HashSet::new(),
+            descriptors: HashSet::new(),
+            return_code: SqlReturn::SUCCESS,
             errors: vec![],
         }
     }
@@ -188,14 +379,70 @@ pub struct Statement {
     pub connection: *mut MongoHandle,
     pub attributes: Box<StatementAttributes>,
     pub state: StatementState,
-    //pub cursor: Option<Box<Peekable<Cursor>>>,
+    pub mongo_statement: Option<Box<dyn MongoStatement>>,
+    // Whether the result set comes from a query prepared with SQLPrepare, which each call to
+    // SQLExecute runs again.
+    pub is_prepared: bool,
+    // The application descriptors allocated with the statement, which SQL_ATTR_APP_ROW_DESC and
+    // SQL_ATTR_APP_PARAM_DESC revert to when explicitly allocated descriptors are detached.
+    pub implicit_app_row_desc: *mut MongoHandle,
+    pub implicit_app_param_desc: *mut MongoHandle,
+    // The progress of the retrieval of the current row's values with SQLGetData, keyed by column
+    // number: the offset to resume from, or None once the value has been entirely returned.
+    pub get_data_offsets: HashMap<USmallInt, Option<usize>>,
+    // The SQL statement executed by the last function called, reported in the header of the
+    // diagnostics.
+    pub dynamic_function: DynamicFunction,
+    // The return code of the last function called on the handle, reported in the header of the
+    // diagnostics.
+    pub return_code: SqlReturn,
     pub errors: Vec<ODBCError>,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct BoundColInfo {
+    pub target_type: CDataType,
+    pub target_buffer: Pointer,
+    pub buffer_length: Len,
+    pub length_or_indicator: *mut Len,
+}
+
+impl BoundColInfo {
+    /// for_row returns the buffers bound for the [`row`]-th row of a rowset, offset by
+    /// [`bind_offset`] bytes. A [`row_bind_type`] of SQL_BIND_BY_COLUMN means the buffers are
+    /// arrays of values and lengths, otherwise it is the size of the structure holding a row.
+    pub fn for_row(&self, row: usize, row_bind_type: ULen, bind_offset: ULen) -> BoundColInfo {
+        let (value_size, length_size) = if row_bind_type == BindType::BindByColumn as usize {
+            (
+                c_type_size(self.target_type).unwrap_or(self.buffer_length as usize),
+                size_of::<Len>(),
+            )
+        } else {
+            (row_bind_type, row_bind_type)
+        };
+        let offset = |ptr: *mut u8, size: usize| {
+            if ptr.is_null() {
+                ptr
+            } else {
+                ptr.wrapping_add(bind_offset + row * size)
+            }
+        };
+        BoundColInfo {
+            target_type: self.target_type,
+            target_buffer: offset(self.target_buffer as *mut u8, value_size) as Pointer,
+            buffer_length: self.buffer_length,
+            length_or_indicator: offset(self.length_or_indicator as *mut u8, length_size)
+                as *mut Len,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct StatementAttributes {
-    pub app_row_desc: Pointer,
-    pub app_param_desc: Pointer,
+    // The descriptors of the statement. The headers of the application row descriptor and the
+    // implementation row descriptor hold the SQL_ATTR_ROW_* attributes.
+    pub app_row_desc: *mut MongoHandle,
+    pub app_param_desc: *mut MongoHandle,
     pub async_enable: AsyncEnable,
     pub async_stmt_event: Pointer,
     pub cursor_scrollable: CursorScrollable,
@@ -204,8 +451,8 @@ pub struct StatementAttributes {
     pub cursor_type: CursorType,
     pub enable_auto_ipd: SqlBool,
     pub fetch_bookmark_ptr: *mut Len,
-    pub imp_row_desc: Pointer,
-    pub imp_param_desc: Pointer,
+    pub imp_row_desc: *mut MongoHandle,
+    pub imp_param_desc: *mut MongoHandle,
     pub max_length: ULen,
     pub max_rows: ULen,
     pub no_scan: NoScan,
@@ -217,25 +464,20 @@ pub struct StatementAttributes {
     pub paramset_size: ULen,
     pub query_timeout: ULen,
     pub retrieve_data: RetrieveData,
-    pub row_array_size: ULen,
-    pub row_bind_offset_ptr: *mut ULen,
-    pub row_bind_type: ULen,
     pub row_number: ULen,
     pub row_operation_ptr: *mut USmallInt,
-    pub row_status_ptr: *mut USmallInt,
-    pub rows_fetched_ptr: *mut ULen,
     pub simulate_cursor: ULen,
     pub use_bookmarks: UseBookmarks,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StatementState {
     Allocated,
     _Prepared,
-    _PreparedHasResultSet,
+    PreparedHasResultSet,
     _ExecutedNoResultSet,
-    _ExecutedHasResultSet,
-    _CursorFetchSet,
+    ExecutedHasResultSet,
+    CursorFetchSet,
     _CursorExtendedFetchSet,
     _FunctionNeedsDataNoParam,
     _FunctionNeedsDataNoPut,
@@ -246,12 +488,16 @@ pub enum StatementState {
 
 impl Statement {
     pub fn with_state(connection: *mut MongoHandle, state: StatementState) -> Self {
+        let implicit_descriptor =
+            |desc_kind| Descriptor::allocate(connection, desc_kind, AllocType::Auto);
+        let implicit_app_row_desc = implicit_descriptor(DescriptorKind::Application);
+        let implicit_app_param_desc = implicit_descriptor(DescriptorKind::Application);
         Self {
             connection,
             state,
             attributes: Box::new(StatementAttributes {
-                app_row_desc: null_mut(),
-                app_param_desc: null_mut(),
+                app_row_desc: implicit_app_row_desc,
+                app_param_desc: implicit_app_param_desc,
                 async_enable: AsyncEnable::Off,
                 async_stmt_event: null_mut(),
                 cursor_scrollable: CursorScrollable::NonScrollable,
@@ -260,8 +506,8 @@ impl Statement {
                 cursor_type: CursorType::ForwardOnly,
                 enable_auto_ipd: SqlBool::False,
                 fetch_bookmark_ptr: null_mut(),
-                imp_row_desc: null_mut(),
-                imp_param_desc: null_mut(),
+                imp_row_desc: implicit_descriptor(DescriptorKind::ImplementationRow),
+                imp_param_desc: implicit_descriptor(DescriptorKind::ImplementationParam),
                 max_length: 0,
                 max_rows: 0,
                 no_scan: NoScan::Off,
@@ -273,17 +519,371 @@ impl Statement {
                 paramset_size: 0,
                 query_timeout: 0,
                 retrieve_data: RetrieveData::Off,
-                row_array_size: 1,
-                row_bind_offset_ptr: null_mut(),
-                row_bind_type: BindType::BindByColumn as usize,
                 row_number: 0,
                 row_operation_ptr: null_mut(),
-                row_status_ptr: null_mut(),
-                rows_fetched_ptr: null_mut(),
                 simulate_cursor: SimulateCursor::NonUnique as usize,
                 use_bookmarks: UseBookmarks::Off,
             }),
+            mongo_statement: None,
+            is_prepared: false,
+            implicit_app_row_desc,
+            implicit_app_param_desc,
+            get_data_offsets: HashMap::new(),
+            dynamic_function: DynamicFunction::UnknownStatement,
+            return_code: SqlReturn::SUCCESS,
             errors: vec![],
         }
     }
+
+    /// implicit_descriptors returns the handles of the four descriptors allocated with the
+    /// statement, whether or not they are currently attached to it.
+    pub fn implicit_descriptors(&self) -> [*mut MongoHandle; 4] {
+        [
+            self.implicit_app_row_desc,
+            self.implicit_app_param_desc,
+            self.attributes.imp_row_desc,
+            self.attributes.imp_param_desc,
+        ]
+    }
+
+    /// ard returns the application row descriptor currently attached to the statement.
+    pub fn ard(&self) -> Result<&RwLock<Descriptor>, ODBCError> {
+        attached_descriptor(self.attributes.app_row_desc, "SQL_ATTR_APP_ROW_DESC")
+    }
+
+    /// apd returns the application parameter descriptor currently attached to the statement.
+    pub fn apd(&self) -> Result<&RwLock<Descriptor>, ODBCError> {
+        attached_descriptor(self.attributes.app_param_desc, "SQL_ATTR_APP_PARAM_DESC")
+    }
+
+    /// ird returns the implementation row descriptor of the statement.
+    pub fn ird(&self) -> Result<&RwLock<Descriptor>, ODBCError> {
+        attached_descriptor(self.attributes.imp_row_desc, "SQL_ATTR_IMP_ROW_DESC")
+    }
+
+    /// query_timeout returns SQL_ATTR_QUERY_TIMEOUT in seconds, clamped to the largest timeout
+    /// the core accepts.
+    pub fn query_timeout(&self) -> Option<i32> {
+        Some(i32::try_from(self.attributes.query_timeout).unwrap_or(i32::MAX))
+    }
+
+    /// check_state returns the error of calling [`function`] in the current state of the
+    /// statement, following the statement transitions of the ODBC specification: HY010 when the
+    /// function can't be called in this state, and 24000 when it requires a cursor to be open or
+    /// closed and it isn't.
+    pub fn check_state(&self, function: StatementFunction) -> Result<(), ODBCError> {
+        use StatementFunction::*;
+        use StatementState::*;
+        let sequence_error = Err(ODBCError::FunctionSequenceError(function.name()));
+        let cursor_open = matches!(
+            self.state,
+            ExecutedHasResultSet | CursorFetchSet | _CursorExtendedFetchSet
+        );
+        match (function, &self.state) {
+            // No function but the ones completing the execution can be called while the
+            // statement is executing or needs data, and the driver doesn't implement those.
+            (
+                _,
+                _FunctionNeedsDataNoParam
+                | _FunctionNeedsDataNoPut
+                | _FunctionNeedsDataPutCalled
+                | _Executing
+                | _AsyncCancelled,
+            ) => sequence_error,
+            (Prepare | ExecDirect(_), _) if cursor_open => Err(ODBCError::InvalidCursorState),
+            (Execute, _) if !self.is_prepared => sequence_error,
+            (Execute, _) if cursor_open => Err(ODBCError::InvalidCursorState),
+            (Fetch(_), Allocated | _Prepared | PreparedHasResultSet) => sequence_error,
+            (Fetch(_), _ExecutedNoResultSet) => Err(ODBCError::CursorNotOpen),
+            // SQLExtendedFetch and SQLFetchScroll can't be mixed.
+            (Fetch(_), _CursorExtendedFetchSet) => sequence_error,
+            (GetData, Allocated | _Prepared | PreparedHasResultSet) => sequence_error,
+            (GetData, _ExecutedNoResultSet) => Err(ODBCError::CursorNotOpen),
+            (GetData, ExecutedHasResultSet) => Err(ODBCError::CursorNotPositioned),
+            (DescribeResultSet(_), Allocated) => sequence_error,
+            (CloseCursor, _) if !cursor_open => Err(ODBCError::CursorNotOpen),
+            (SetCursorAttr(attr), PreparedHasResultSet) => {
+                Err(ODBCError::AttributeCannotBeSetNow(attr))
+            }
+            (SetCursorAttr(_), _) if cursor_open => Err(ODBCError::InvalidCursorState),
+            _ => Ok(()),
+        }
+    }
+
+    /// discard_result_set drops the result set of the statement, prepared or not, which goes
+    /// back to the allocated state before executing a new query.
+    pub fn discard_result_set(&mut self) {
+        self.mongo_statement = None;
+        self.is_prepared = false;
+        self.get_data_offsets.clear();
+        self.state = StatementState::Allocated;
+    }
+
+    /// close_cursor closes the cursor of the statement, if open. A prepared statement keeps the
+    /// metadata of its result set and can be executed again, others go back to the allocated
+    /// state.
+    pub fn close_cursor(&mut self) {
+        if !matches!(
+            self.state,
+            StatementState::ExecutedHasResultSet
+                | StatementState::CursorFetchSet
+                | StatementState::_CursorExtendedFetchSet
+        ) {
+            return;
+        }
+        if self.is_prepared {
+            if let Some(mongo_statement) = self.mongo_statement.as_mut() {
+                mongo_statement.close_cursor();
+            }
+            self.get_data_offsets.clear();
+            self.state = StatementState::PreparedHasResultSet;
+        } else {
+            self.discard_result_set();
+        }
+    }
+}
+
+/// attached_descriptor returns the descriptor of [`handle`], attached to a statement as the
+/// descriptor [`attribute`], or an HY000 error if the handle is not a descriptor.
+fn attached_descriptor<'a>(
+    handle: *mut MongoHandle,
+    attribute: &'static str,
+) -> Result<&'a RwLock<Descriptor>, ODBCError> {
+    unsafe { handle.as_ref() }
+        .and_then(MongoHandle::as_descriptor)
+        .ok_or(ODBCError::InvalidAttachedDescriptor(attribute))
+}
+
+/// StatementFunction groups the functions called on a statement by the way the state of the
+/// statement restricts them.
+#[derive(Clone, Copy, Debug)]
+pub enum StatementFunction {
+    Prepare,
+    Execute,
+    // SQLExecDirect and the catalog functions, which create a result set.
+    ExecDirect(&'static str),
+    // SQLFetch and SQLFetchScroll.
+    Fetch(&'static str),
+    GetData,
+    // SQLNumResultCols, SQLDescribeCol and SQLColAttribute.
+    DescribeResultSet(&'static str),
+    CloseCursor,
+    // SQLSetStmtAttr for the given attribute describing the cursor, which can't change once the
+    // statement is prepared or has an open cursor.
+    SetCursorAttr(&'static str),
+    // The functions that only can't be called while the statement executes or needs data.
+    Other(&'static str),
+}
+
+impl StatementFunction {
+    pub fn name(self) -> &'static str {
+        match self {
+            StatementFunction::Prepare => "SQLPrepare",
+            StatementFunction::Execute => "SQLExecute",
+            StatementFunction::GetData => "SQLGetData",
+            StatementFunction::CloseCursor => "SQLCloseCursor",
+            StatementFunction::SetCursorAttr(_) => "SQLSetStmtAttr",
+            StatementFunction::ExecDirect(name)
+            | StatementFunction::Fetch(name)
+            | StatementFunction::DescribeResultSet(name)
+            | StatementFunction::Other(name) => name,
+        }
+    }
+}
+
+impl Drop for Statement {
+    fn drop(&mut self) {
+        // The implicitly allocated descriptors live as long as their statement, while the
+        // explicitly allocated ones are freed with SQLFreeHandle.
+        for desc in self.implicit_descriptors() {
+            MongoHandle::free(desc);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Descriptor {
+    // The Connection the descriptor was allocated on
+    pub connection: *mut MongoHandle,
+    // The Statement an implicitly allocated descriptor belongs to, set once the statement handle
+    // is allocated. Null for explicitly allocated descriptors.
+    pub statement: *mut MongoHandle,
+    pub desc_kind: DescriptorKind,
+    pub alloc_type: AllocType,
+    // The header fields
+    pub array_size: ULen,
+    pub array_status_ptr: *mut USmallInt,
+    pub bind_offset_ptr: *mut ULen,
+    pub bind_type: ULen,
+    pub rows_processed_ptr: *mut ULen,
+    // The descriptor records, record n being at index n - 1. The records of implementation row
+    // descriptors are not stored, they describe the columns of the statement's result set.
+    pub records: Vec<DescRecord>,
+    // The return code of the last function called on the handle, reported in the header of the
+    // diagnostics.
+    pub return_code: SqlReturn,
+    pub errors: Vec<ODBCError>,
+}
+
+/// DescriptorKind is what the records of a descriptor describe: application descriptors describe
+/// the application's buffers, and can be used either as row or parameter descriptors, while
+/// implementation descriptors describe the result set columns or the statement's parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescriptorKind {
+    Application,
+    ImplementationRow,
+    ImplementationParam,
+}
+
+impl Descriptor {
+    pub fn new(
+        connection: *mut MongoHandle,
+        desc_kind: DescriptorKind,
+        alloc_type: AllocType,
+    ) -> Self {
+        Self {
+            connection,
+            statement: null_mut(),
+            desc_kind,
+            alloc_type,
+            array_size: 1,
+            array_status_ptr: null_mut(),
+            bind_offset_ptr: null_mut(),
+            bind_type: BindType::BindByColumn as usize,
+            rows_processed_ptr: null_mut(),
+            records: vec![],
+            return_code: SqlReturn::SUCCESS,
+            errors: vec![],
+        }
+    }
+
+    /// allocate returns the handle of a new descriptor.
+    pub fn allocate(
+        connection: *mut MongoHandle,
+        desc_kind: DescriptorKind,
+        alloc_type: AllocType,
+    ) -> *mut MongoHandle {
+        let desc = RwLock::new(Descriptor::new(connection, desc_kind, alloc_type));
+        MongoHandle::Descriptor(desc).into_raw()
+    }
+
+    /// record_mut returns the record [`record_number`], adding default records up to it if the
+    /// descriptor has fewer records.
+    pub fn record_mut(&mut self, record_number: USmallInt) -> &mut DescRecord {
+        let index = record_number as usize - 1;
+        if self.records.len() <= index {
+            self.records.resize(index + 1, DescRecord::default());
+        }
+        &mut self.records[index]
+    }
+
+    /// unbind unbinds the record [`record_number`]. SQL_DESC_COUNT drops to the highest bound
+    /// record when the record was the last one.
+    pub fn unbind(&mut self, record_number: USmallInt) {
+        if let Some(record) = self.records.get_mut(record_number as usize - 1) {
+            record.data_ptr = null_mut();
+        }
+        while self
+            .records
+            .last()
+            .is_some_and(|record| record.data_ptr.is_null())
+        {
+            self.records.pop();
+        }
+    }
+
+    /// bound_cols returns the columns bound through the records of the application row
+    /// descriptor, along with their column numbers.
+    pub fn bound_cols(&self) -> impl Iterator<Item = (USmallInt, BoundColInfo)> + '_ {
+        self.records
+            .iter()
+            .enumerate()
+            .filter_map(|(index, record)| Some((index as USmallInt + 1, record.bound_col()?)))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DescRecord {
+    // SQL_DESC_TYPE, the verbose type, which is SQL_DATETIME for the datetime types along with
+    // the SQL_DESC_DATETIME_INTERVAL_CODE of the concise type.
+    pub desc_type: SmallInt,
+    pub datetime_interval_code: SmallInt,
+    pub data_ptr: Pointer,
+    pub octet_length: Len,
+    pub octet_length_ptr: *mut Len,
+    pub indicator_ptr: *mut Len,
+    pub length: ULen,
+    pub precision: SmallInt,
+    pub scale: SmallInt,
+}
+
+impl Default for DescRecord {
+    fn default() -> Self {
+        Self {
+            desc_type: CDataType::Default as SmallInt,
+            datetime_interval_code: 0,
+            data_ptr: null_mut(),
+            octet_length: 0,
+            octet_length_ptr: null_mut(),
+            indicator_ptr: null_mut(),
+            length: 0,
+            precision: 0,
+            scale: 0,
+        }
+    }
+}
+
+// SQL_TYPE_DATE - SQL_CODE_DATE: the concise datetime types are the datetime interval codes
+// offset by this value.
+const DATETIME_CONCISE_TYPE_OFFSET: SmallInt = 90;
+
+impl DescRecord {
+    /// concise_type returns SQL_DESC_CONCISE_TYPE, derived from the verbose type and the datetime
+    /// interval code.
+    pub fn concise_type(&self) -> SmallInt {
+        if self.desc_type == SqlDataType::DATETIME.0 && self.datetime_interval_code != 0 {
+            DATETIME_CONCISE_TYPE_OFFSET + self.datetime_interval_code
+        } else {
+            self.desc_type
+        }
+    }
+
+    /// set_concise_type sets SQL_DESC_CONCISE_TYPE, along with the verbose type and the datetime
+    /// interval code it implies.
+    pub fn set_concise_type(&mut self, concise_type: SmallInt) {
+        let datetime_interval_code = concise_type - DATETIME_CONCISE_TYPE_OFFSET;
+        if (1..=SQL_CODE_TIMESTAMP as SmallInt).contains(&datetime_interval_code) {
+            self.desc_type = SqlDataType::DATETIME.0;
+            self.datetime_interval_code = datetime_interval_code;
+        } else {
+            self.desc_type = concise_type;
+            self.datetime_interval_code = 0;
+        }
+    }
+
+    /// bound_col returns the binding described by the record, or None if the record is unbound.
+    pub fn bound_col(&self) -> Option<BoundColInfo> {
+        if self.data_ptr.is_null() {
+            return None;
+        }
+        Some(BoundColInfo {
+            target_type: c_type_from_code(self.concise_type())?,
+            target_buffer: self.data_ptr,
+            buffer_length: self.octet_length,
+            length_or_indicator: if self.indicator_ptr.is_null() {
+                self.octet_length_ptr
+            } else {
+                self.indicator_ptr
+            },
+        })
+    }
+
+    /// bind makes the record describe the binding [`bound_col`], as SQLBindCol does.
+    pub fn bind(&mut self, bound_col: &BoundColInfo) {
+        self.set_concise_type(bound_col.target_type as SmallInt);
+        self.data_ptr = bound_col.target_buffer;
+        self.octet_length = bound_col.buffer_length;
+        self.octet_length_ptr = bound_col.length_or_indicator;
+        self.indicator_ptr = bound_col.length_or_indicator;
+    }
 }
@@ -1,12 +1,17 @@
-use crate::{handles::definitions::*, SQLAllocHandle, SQLFreeHandle};
+use crate::{
+    api::test_fixtures::{
+        connection, environment, free_handles, new_connection, new_env, new_statement, sql_state,
+        statement,
+    },
+    handles::definitions::*,
+    SQLAllocHandle, SQLDisconnect, SQLFreeHandle,
+};
 use odbc_sys::{Handle, HandleType, SqlReturn};
-use std::sync::RwLock;
 
 #[test]
 fn env_alloc_free() {
     unsafe {
-        let mut handle: *mut _ =
-            &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+        let mut handle: *mut MongoHandle = std::ptr::null_mut();
         let handle_ptr: *mut _ = &mut handle;
         assert_eq!(
             SqlReturn::SUCCESS,
@@ -18,7 +23,7 @@ fn env_alloc_free() {
         );
         assert_eq!(
             EnvState::Allocated,
-            (*handle).as_env().unwrap().read().unwrap().state
+            environment(handle).read().unwrap().state
         );
         assert_eq!(
             SqlReturn::SUCCESS,
@@ -33,13 +38,9 @@ fn env_alloc_free() {
 #[test]
 fn connection_alloc_free() {
     unsafe {
-        let env_handle: *mut _ =
-            &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+        let env_handle = new_env();
 
-        let mut handle: *mut _ = &mut MongoHandle::Connection(RwLock::new(Connection::with_state(
-            std::ptr::null_mut(),
-            ConnectionState::Allocated,
-        )));
+        let mut handle: *mut MongoHandle = std::ptr::null_mut();
         let handle_ptr: *mut _ = &mut handle;
         assert_eq!(
             SqlReturn::SUCCESS,
@@ -51,21 +52,12 @@ fn connection_alloc_free() {
         );
         assert_eq!(
             ConnectionState::Allocated,
-            (*handle).as_connection().unwrap().read().unwrap().state
-        );
-        assert_eq!(
-            1,
-            (*env_handle)
-                .as_env()
-                .unwrap()
-                .read()
-                .unwrap()
-                .connections
-                .len()
+            connection(handle).read().unwrap().state
         );
+        assert_eq!(1, environment(env_handle).read().unwrap().connections.len());
         assert_eq!(
             EnvState::ConnectionAllocated,
-            (*env_handle).as_env().unwrap().read().unwrap().state
+            environment(env_handle).read().unwrap().state
         );
         assert_eq!(
             SqlReturn::SUCCESS,
@@ -74,37 +66,23 @@ fn connection_alloc_free() {
                 std::mem::transmute::<*mut MongoHandle, Handle>(handle),
             )
         );
-        assert_eq!(
-            0,
-            (*env_handle)
-                .as_env()
-                .unwrap()
-                .read()
-                .unwrap()
-                .connections
-                .len()
-        );
+        assert_eq!(0, environment(env_handle).read().unwrap().connections.len());
         assert_eq!(
             EnvState::Allocated,
-            (*env_handle).as_env().unwrap().read().unwrap().state
+            environment(env_handle).read().unwrap().state
         );
+        free_handles(&[env_handle]);
     }
 }
 
 #[test]
 fn statement_alloc_free() {
     unsafe {
-        let env_handle: *mut _ =
-            &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+        let env_handle = new_env();
 
-        let conn_handle: *mut _ = &mut MongoHandle::Connection(RwLock::new(
-            Connection::with_state(env_handle, ConnectionState::Allocated),
-        ));
+        let conn_handle = new_connection(env_handle, ConnectionState::Connected);
 
-        let mut handle: *mut _ = &mut MongoHandle::Statement(RwLock::new(Statement::with_state(
-            std::ptr::null_mut(),
-            StatementState::Allocated,
-        )));
+        let mut handle: *mut MongoHandle = std::ptr::null_mut();
         let handle_ptr: *mut _ = &mut handle;
         assert_eq!(
             SqlReturn::SUCCESS,
@@ -116,18 +94,9 @@ fn statement_alloc_free() {
         );
         assert_eq!(
             StatementState::Allocated,
-            (*handle).as_statement().unwrap().write().unwrap().state
-        );
-        assert_eq!(
-            1,
-            (*conn_handle)
-                .as_connection()
-                .unwrap()
-                .read()
-                .unwrap()
-                .statements
-                .len()
+            statement(handle).write().unwrap().state
         );
+        assert_eq!(1, connection(conn_handle).read().unwrap().statements.len());
         assert_eq!(
             SqlReturn::SUCCESS,
             SQLFreeHandle(
@@ -135,24 +104,15 @@ fn statement_alloc_free() {
                 std::mem::transmute::<*mut MongoHandle, Handle>(handle),
             )
         );
-        assert_eq!(
-            0,
-            (*conn_handle)
-                .as_connection()
-                .unwrap()
-                .read()
-                .unwrap()
-                .statements
-                .len()
-        );
+        assert_eq!(0, connection(conn_handle).read().unwrap().statements.len());
+        free_handles(&[conn_handle, env_handle]);
     }
 }
 
 #[test]
 fn invalid_free() {
     unsafe {
-        let mut env_handle: *mut _ =
-            &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+        let mut env_handle: *mut MongoHandle = std::ptr::null_mut();
         let env_handle_ptr: *mut _ = &mut env_handle;
         assert_eq!(
             SqlReturn::SUCCESS,
@@ -164,7 +124,7 @@ fn invalid_free() {
         );
         assert_eq!(
             EnvState::Allocated,
-            (*env_handle).as_env().unwrap().read().unwrap().state
+            environment(env_handle).read().unwrap().state
         );
         assert_eq!(
             SqlReturn::INVALID_HANDLE,
@@ -181,9 +141,7 @@ fn invalid_free() {
             )
         );
 
-        let mut conn_handle: *mut _ = &mut MongoHandle::Connection(RwLock::new(
-            Connection::with_state(env_handle, ConnectionState::Allocated),
-        ));
+        let mut conn_handle: *mut MongoHandle = std::ptr::null_mut();
         let conn_handle_ptr: *mut _ = &mut conn_handle;
         assert_eq!(
             SqlReturn::SUCCESS,
@@ -195,12 +153,7 @@ fn invalid_free() {
         );
         assert_eq!(
             ConnectionState::Allocated,
-            (*conn_handle)
-                .as_connection()
-                .unwrap()
-                .read()
-                .unwrap()
-                .state
+            connection(conn_handle).read().unwrap().state
         );
         assert_eq!(
             SqlReturn::INVALID_HANDLE,
@@ -239,8 +192,7 @@ fn invalid_free() {
 #[test]
 fn invalid_alloc() {
     unsafe {
-        let mut handle: *mut _ =
-            &mut MongoHandle::Env(RwLock::new(Env::with_state(EnvState::Allocated)));
+        let mut handle: *mut MongoHandle = std::ptr::null_mut();
         let handle_ptr: *mut _ = &mut handle;
         // first check null ptrs for the two handles that require parent handles
         assert_eq!(
@@ -260,10 +212,7 @@ fn invalid_alloc() {
             )
         );
 
-        let stmt_handle: *mut _ = &mut MongoHandle::Statement(RwLock::new(Statement::with_state(
-            std::ptr::null_mut(),
-            StatementState::Allocated,
-        )));
+        let stmt_handle = new_statement(std::ptr::null_mut(), StatementState::Allocated);
 
         // now test wrong parent handle type (Dbc needs Env, and Stmt needs Connection).
         assert_eq!(
@@ -282,5 +231,71 @@ fn invalid_alloc() {
                 std::mem::transmute::<*mut *mut MongoHandle, *mut Handle>(handle_ptr),
             )
         );
+        free_handles(&[stmt_handle]);
+    }
+}
+
+#[test]
+fn disconnect() {
+    unsafe {
+        let env_handle = new_env();
+
+        let mut conn_handle: *mut MongoHandle = std::ptr::null_mut();
+        let conn_handle_ptr: *mut _ = &mut conn_handle;
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLAllocHandle(
+                HandleType::Dbc,
+                env_handle as *mut _,
+                std::mem::transmute::<*mut *mut MongoHandle, *mut Handle>(conn_handle_ptr),
+            )
+        );
+
+        // The connection is not open
+        assert_eq!(SqlReturn::ERROR, SQLDisconnect(conn_handle as *mut _));
+        assert_eq!("08003", sql_state(conn_handle));
+
+        // Simulate an established connection with an allocated statement
+        connection(conn_handle).write().unwrap().state = ConnectionState::Connected;
+        let mut stmt_handle: *mut MongoHandle = std::ptr::null_mut();
+        let stmt_handle_ptr: *mut _ = &mut stmt_handle;
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLAllocHandle(
+                HandleType::Stmt,
+                conn_handle as *mut _,
+                std::mem::transmute::<*mut *mut MongoHandle, *mut Handle>(stmt_handle_ptr),
+            )
+        );
+
+        // A transaction in progress prevents disconnecting
+        connection(conn_handle).write().unwrap().state = ConnectionState::_TransactionInProgress;
+        assert_eq!(SqlReturn::ERROR, SQLDisconnect(conn_handle as *mut _));
+        assert_eq!("25000", sql_state(conn_handle));
+
+        // A statement still executing prevents disconnecting
+        connection(conn_handle).write().unwrap().state = ConnectionState::StatementAllocated;
+        statement(stmt_handle).write().unwrap().state = StatementState::_Executing;
+        assert_eq!(SqlReturn::ERROR, SQLDisconnect(conn_handle as *mut _));
+        assert_eq!("HY010", sql_state(conn_handle));
+
+        // Disconnecting frees the statements and the connection can be connected again
+        statement(stmt_handle).write().unwrap().state = StatementState::Allocated;
+        assert_eq!(SqlReturn::SUCCESS, SQLDisconnect(conn_handle as *mut _));
+        {
+            let conn_contents = connection(conn_handle).read().unwrap();
+            assert_eq!(ConnectionState::Allocated, conn_contents.state);
+            assert!(conn_contents.statements.is_empty());
+            assert!(conn_contents.mongo_connection.is_none());
+        }
+
+        assert_eq!(
+            SqlReturn::SUCCESS,
+            SQLFreeHandle(
+                HandleType::Dbc,
+                std::mem::transmute::<*mut MongoHandle, Handle>(conn_handle),
+            )
+        );
+        free_handles(&[env_handle]);
     }
 }
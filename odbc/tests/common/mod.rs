@@ -8,6 +8,14 @@ lazy_static! {
     pub static ref ODBC_ENV: Environment = Environment::new().unwrap();
 }
 
+/// The name of the driver under test, 'ADL_ODBC_DRIVER' if 'ADL_TEST_DRIVER' is not set.
+pub fn driver_name() -> String {
+    match env::var("ADL_TEST_DRIVER") {
+        Ok(val) => val,
+        Err(_e) => "ADL_ODBC_DRIVER".to_string(), //Default driver name
+    }
+}
+
 /// Generate the default connection setting defined for the tests using a connection string
 /// of the form 'Driver={};PWD={};USER={};SERVER={};AUTH_SRC={}'.
 /// The default driver is 'ADL_ODBC_DRIVER' if not specified.
@@ -23,19 +31,19 @@ fn generate_default_connection_str() -> String {
     };
 
     let db = env::var("ADL_TEST_DB");
-    let driver = match env::var("ADL_TEST_DRIVER") {
-        Ok(val) => val,
-        Err(_e) => "ADL_ODBC_DRIVER".to_string(), //Default driver name
-    };
 
     let mut connection_string = format!(
         "Driver={};PWD={};USER={};SERVER={};AUTH_SRC={}",
-        driver, user_name, password, host, auth_db,
+        driver_name(),
+        password,
+        user_name,
+        host,
+        auth_db,
     );
 
     // If a db is specified add it to the connection string
     match db {
-        Ok(val) => connection_string.push_str(&("DATABASE=".to_owned() + &val)),
+        Ok(val) => connection_string.push_str(&(";DATABASE=".to_owned() + &val)),
         Err(_e) => (), // Do nothing
     };
 
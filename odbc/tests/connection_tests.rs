@@ -1,6 +1,7 @@
 mod common;
 
-use common::connect;
+use common::{connect, driver_name};
+use odbc_api::Error;
 
 #[test]
 fn test_invalid_connection() {
@@ -12,6 +13,21 @@ fn test_invalid_connection() {
         "The connection should have failed, but it was successful."
     );
 }
+#[test]
+fn test_unreachable_server() {
+    // No server listens on port 1, so the connection fails once the server selection has timed
+    // out.
+    let conn_str = format!(
+        "Driver={};USER=N/A;PWD=N/A;SERVER=localhost:1",
+        driver_name()
+    );
+    match connect(Some(&conn_str)) {
+        Err(Error::Diagnostics { record, .. }) => assert_eq!("HYT00", record.state.as_str()),
+        Err(error) => panic!("unexpected error: {}", error),
+        Ok(_) => panic!("The connection should have failed, but it was successful."),
+    }
+}
+
 #[allow(dead_code)]
 //#[test]
 // Uncomment to verify that driver is installed.